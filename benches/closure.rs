@@ -0,0 +1,29 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use inferdf::System;
+
+mod common;
+
+/// Closes the generated data under the rules compiled from its ontology.
+fn ontology_closure(c: &mut Criterion) {
+	let mut group = c.benchmark_group("ontology_closure");
+	group.sample_size(10);
+
+	for size in common::sizes(10_000) {
+		let dataset = common::dataset(size);
+		let system = System::from_ontology(&dataset);
+
+		group.throughput(Throughput::Elements(size as u64));
+		group.bench_with_input(BenchmarkId::from_parameter(size), &dataset, |b, dataset| {
+			b.iter_batched(
+				|| dataset.clone(),
+				|mut dataset| system.close(&mut dataset).unwrap(),
+				BatchSize::LargeInput,
+			)
+		});
+	}
+
+	group.finish()
+}
+
+criterion_group!(benches, ontology_closure);
+criterion_main!(benches);
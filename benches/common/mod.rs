@@ -0,0 +1,149 @@
+//! Generated benchmark data.
+//!
+//! Instance data loosely follows the shape of the Lehigh University
+//! Benchmark (LUBM): students attend courses taught by professors working
+//! for departments of universities.
+use iref::IriBuf;
+use rdf_types::{dataset::IndexedBTreeDataset, Term, Triple};
+
+/// Univ-Bench ontology namespace.
+const UB: &str = "http://swat.cse.lehigh.edu/onto/univ-bench.owl#";
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDFS_SUB_CLASS_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subClassOf";
+const RDFS_SUB_PROPERTY_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subPropertyOf";
+const RDFS_DOMAIN: &str = "http://www.w3.org/2000/01/rdf-schema#domain";
+const RDFS_RANGE: &str = "http://www.w3.org/2000/01/rdf-schema#range";
+const OWL_TRANSITIVE_PROPERTY: &str = "http://www.w3.org/2002/07/owl#TransitiveProperty";
+
+/// Benchmarked dataset sizes, in number of facts.
+const SIZES: [usize; 4] = [10_000, 100_000, 1_000_000, 10_000_000];
+
+/// Returns the dataset sizes to benchmark.
+///
+/// Sizes above the `INFERDF_BENCH_MAX_FACTS` environment variable, or
+/// `default_max` if it is not set, are skipped. Larger datasets are only
+/// generated on demand.
+pub fn sizes(default_max: usize) -> impl Iterator<Item = usize> {
+	let max = std::env::var("INFERDF_BENCH_MAX_FACTS")
+		.ok()
+		.and_then(|v| v.parse().ok())
+		.unwrap_or(default_max);
+
+	SIZES.into_iter().filter(move |&size| size <= max)
+}
+
+fn iri(iri: String) -> Term {
+	Term::iri(IriBuf::new(iri).unwrap())
+}
+
+fn ub(name: &str) -> Term {
+	iri(format!("{UB}{name}"))
+}
+
+fn entity(kind: &str, i: usize) -> Term {
+	iri(format!("http://www.example.org/{kind}/{i}"))
+}
+
+/// Generates at least `facts` instance triples.
+fn university(facts: usize) -> Vec<Triple> {
+	let ty = iri(RDF_TYPE.to_owned());
+	let mut triples = Vec::with_capacity(facts + 8);
+
+	let mut n = 0;
+	while triples.len() < facts {
+		let professor = entity("professor", n / 10);
+		let department = entity("department", n / 1000);
+
+		if n % 1000 == 0 {
+			triples.push(Triple(department.clone(), ty.clone(), ub("Department")));
+			triples.push(Triple(
+				department.clone(),
+				ub("subOrganizationOf"),
+				entity("university", n / 10_000),
+			));
+			triples.push(Triple(professor.clone(), ub("headOf"), department.clone()));
+		}
+
+		if n % 10 == 0 {
+			triples.push(Triple(professor.clone(), ty.clone(), ub("FullProfessor")));
+			triples.push(Triple(
+				professor.clone(),
+				ub("worksFor"),
+				department.clone(),
+			));
+			triples.push(Triple(
+				professor.clone(),
+				ub("teacherOf"),
+				entity("course", n / 10),
+			));
+		}
+
+		let student = entity("student", n);
+		let class = if n % 4 == 0 {
+			"GraduateStudent"
+		} else {
+			"UndergraduateStudent"
+		};
+		triples.push(Triple(student.clone(), ty.clone(), ub(class)));
+		triples.push(Triple(student.clone(), ub("memberOf"), department));
+		triples.push(Triple(
+			student.clone(),
+			ub("takesCourse"),
+			entity("course", (n * 7 / 10) % (n / 10 + 1)),
+		));
+		triples.push(Triple(student, ub("advisor"), professor));
+
+		n += 1
+	}
+
+	triples
+}
+
+/// Returns the ontology axioms of the generated data.
+fn ontology() -> Vec<Triple> {
+	let sub_class_of = iri(RDFS_SUB_CLASS_OF.to_owned());
+	let sub_property_of = iri(RDFS_SUB_PROPERTY_OF.to_owned());
+	let domain = iri(RDFS_DOMAIN.to_owned());
+	let range = iri(RDFS_RANGE.to_owned());
+
+	let mut triples: Vec<Triple> = [
+		("GraduateStudent", "Student"),
+		("UndergraduateStudent", "Student"),
+		("Student", "Person"),
+		("FullProfessor", "Professor"),
+		("Professor", "Faculty"),
+		("Faculty", "Person"),
+		("Department", "Organization"),
+		("University", "Organization"),
+	]
+	.into_iter()
+	.map(|(a, b)| Triple(ub(a), sub_class_of.clone(), ub(b)))
+	.collect();
+
+	triples.extend([
+		Triple(ub("headOf"), sub_property_of.clone(), ub("worksFor")),
+		Triple(ub("worksFor"), sub_property_of, ub("memberOf")),
+		Triple(ub("takesCourse"), domain.clone(), ub("Student")),
+		Triple(ub("teacherOf"), domain, ub("Faculty")),
+		Triple(ub("teacherOf"), range.clone(), ub("Course")),
+		Triple(ub("advisor"), range, ub("Professor")),
+		Triple(
+			ub("subOrganizationOf"),
+			iri(RDF_TYPE.to_owned()),
+			iri(OWL_TRANSITIVE_PROPERTY.to_owned()),
+		),
+	]);
+
+	triples
+}
+
+/// Generates a dataset with the ontology axioms and at least `facts`
+/// instance triples.
+pub fn dataset(facts: usize) -> IndexedBTreeDataset {
+	ontology()
+		.into_iter()
+		.chain(university(facts))
+		.map(|t| t.into_quad(None))
+		.collect()
+}
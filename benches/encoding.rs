@@ -0,0 +1,47 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use iref::IriBuf;
+use rdf_types::{dataset::IndexedBTreeDataset, Quad, Term};
+
+use inferdf::System;
+
+/// Numbers of rules of the decoded systems.
+const SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+fn class(i: usize) -> Term {
+	Term::iri(IriBuf::new(format!("http://www.example.org/class/{i}")).unwrap())
+}
+
+/// Builds a system of `rules` rules, compiled from a chain of
+/// `rdfs:subClassOf` axioms.
+fn system(rules: usize) -> System {
+	let sub_class_of = Term::iri(
+		IriBuf::new("http://www.w3.org/2000/01/rdf-schema#subClassOf".to_owned()).unwrap(),
+	);
+
+	let ontology: IndexedBTreeDataset = (0..rules)
+		.map(|i| Quad(class(i), sub_class_of.clone(), class(i + 1), None))
+		.collect();
+
+	System::from_ontology(&ontology)
+}
+
+/// Decodes the binary encoding of rule systems.
+fn decode(c: &mut Criterion) {
+	let mut group = c.benchmark_group("decode");
+
+	for size in SIZES {
+		let bytes = system(size).to_bytes().unwrap();
+
+		group.throughput(Throughput::Bytes(bytes.len() as u64));
+		group.bench_with_input(BenchmarkId::from_parameter(size), &bytes, |b, bytes| {
+			b.iter(|| System::<Term>::from_bytes(black_box(bytes)).unwrap())
+		});
+	}
+
+	group.finish()
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
@@ -0,0 +1,56 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use inferdf::{
+	pattern::{conjunctive, PatternSubstitution},
+	patterns, rule,
+};
+
+mod common;
+
+/// Matches a single pattern with a constant predicate.
+fn single_pattern(c: &mut Criterion) {
+	let mut group = c.benchmark_group("single_pattern");
+	let (x, y) = (0, 1);
+	let patterns = patterns! [
+		?x <"http://swat.cse.lehigh.edu/onto/univ-bench.owl#takesCourse"> ?y .
+	];
+
+	for size in common::sizes(100_000) {
+		let dataset = common::dataset(size);
+		group.throughput(Throughput::Elements(size as u64));
+		group.bench_with_input(BenchmarkId::from_parameter(size), &dataset, |b, dataset| {
+			b.iter(|| conjunctive(dataset, &patterns, PatternSubstitution::new()).count())
+		});
+	}
+
+	group.finish()
+}
+
+/// Matches the hypothesis of a rule joining three patterns.
+fn multi_join(c: &mut Criterion) {
+	let mut group = c.benchmark_group("multi_join");
+	group.sample_size(10);
+	let rule = rule! {
+		for ?s, ?c, ?p {
+			?s <"http://swat.cse.lehigh.edu/onto/univ-bench.owl#takesCourse"> ?c .
+			?p <"http://swat.cse.lehigh.edu/onto/univ-bench.owl#teacherOf"> ?c .
+			?s <"http://swat.cse.lehigh.edu/onto/univ-bench.owl#advisor"> ?p .
+		} => {
+			?s <"http://swat.cse.lehigh.edu/onto/univ-bench.owl#studiesWithAdvisor"> ?p .
+		}
+	};
+
+	for size in common::sizes(10_000) {
+		let dataset = common::dataset(size);
+		group.throughput(Throughput::Elements(size as u64));
+		group.bench_with_input(BenchmarkId::from_parameter(size), &dataset, |b, dataset| {
+			b.iter(|| black_box(&rule).deductions(dataset).count())
+		});
+	}
+
+	group.finish()
+}
+
+criterion_group!(benches, single_pattern, multi_join);
+criterion_main!(benches);
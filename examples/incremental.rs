@@ -0,0 +1,56 @@
+//! Incremental insertion with deduction.
+//!
+//! Facts are inserted one at a time, and the dataset is closed under the
+//! deduction system after each insertion, reporting the newly deduced facts.
+use inferdf::{rule, System};
+use rdf_types::{dataset::IndexedBTreeDataset, grdf_triples, Triple};
+
+fn main() {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?x <"https://example.org/#ancestor"> ?y .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#ancestor"> ?y .
+			?y <"https://example.org/#parent"> ?z .
+		} => {
+			?x <"https://example.org/#ancestor"> ?z .
+		}
+	});
+
+	let facts: Vec<Triple> = grdf_triples![
+		_:"alice" <"https://example.org/#parent"> _:"bob" .
+		_:"bob" <"https://example.org/#parent"> _:"carol" .
+		_:"carol" <"https://example.org/#parent"> _:"dave" .
+	]
+	.into_iter()
+	.collect();
+
+	let mut dataset = IndexedBTreeDataset::new();
+	for fact in facts {
+		println!("insert {fact} .");
+		dataset.insert(fact.into_quad(None));
+
+		system
+			.close_observed(&mut dataset, |triple, cause| {
+				let rule = system.iter().position(|r| std::ptr::eq(r, cause.rule));
+				println!("  deduced {triple} . (rule {})", rule.unwrap())
+			})
+			.expect("closure failed");
+	}
+
+	// 3 parent triples, and 6 ancestor triples.
+	assert_eq!(dataset.len(), 9)
+}
+
+#[test]
+fn run() {
+	main()
+}
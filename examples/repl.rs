@@ -0,0 +1,145 @@
+//! Interactive rule exploration.
+//!
+//! Reads commands from the standard input and prints their output. Files
+//! given as arguments are loaded first. Type `:help` to list the commands.
+//!
+//! With the `--validate` flag, the loaded dataset is validated against the
+//! loaded rules instead, and the process exits with the corresponding
+//! `testing::ExitCode`. Add `--report json` to print the report as JSON (see
+//! `repl::JsonReport`).
+//!
+//! With `--batch <directory>`, every N-Quads file of the directory is
+//! validated in parallel against the loaded rules.
+use std::{
+	io::{self, BufRead, Write},
+	time::Instant,
+};
+
+use inferdf::{
+	repl::Session,
+	testing::{Batch, ExitCode},
+};
+
+fn run(
+	session: &mut Session,
+	input: impl BufRead,
+	mut output: impl Write,
+	prompt: bool,
+) -> io::Result<()> {
+	if prompt {
+		write!(output, "> ")?;
+		output.flush()?;
+	}
+
+	for line in input.lines() {
+		let line = line?;
+		if line.trim() == ":quit" {
+			break;
+		}
+
+		match session.execute(&line) {
+			Ok(result) if result.is_empty() => (),
+			Ok(result) => writeln!(output, "{result}")?,
+			Err(e) => writeln!(output, "error: {e}")?,
+		}
+
+		if prompt {
+			write!(output, "> ")?;
+			output.flush()?;
+		}
+	}
+
+	Ok(())
+}
+
+fn main() -> io::Result<()> {
+	let mut session = Session::new();
+	let mut validate = false;
+	let mut json = false;
+	let mut batch = None;
+	let mut args = std::env::args().skip(1);
+	while let Some(arg) = args.next() {
+		if arg == "--validate" {
+			validate = true;
+		} else if arg == "--batch" {
+			match args.next() {
+				Some(directory) => batch = Some(directory),
+				None => {
+					eprintln!("--batch expects a directory");
+					std::process::exit(ExitCode::Error.code())
+				}
+			}
+		} else if arg == "--report" {
+			match args.next().as_deref() {
+				Some("json") => json = true,
+				Some("text") => json = false,
+				_ => {
+					eprintln!("--report expects `json` or `text`");
+					std::process::exit(ExitCode::Error.code())
+				}
+			}
+		} else if let Err(e) = session.execute(&format!(":load {arg}")) {
+			eprintln!("{arg}: {e}");
+			std::process::exit(ExitCode::Error.code())
+		}
+	}
+
+	if let Some(directory) = batch {
+		let report = match Batch::from_dir(&directory) {
+			Ok(batch) => batch.run(session.system(), 0),
+			Err(e) => {
+				eprintln!("{directory}: {e}");
+				std::process::exit(ExitCode::Error.code())
+			}
+		};
+
+		println!("{report}");
+		std::process::exit(ExitCode::from(&report).code())
+	}
+
+	if validate {
+		let start = Instant::now();
+		match session.report() {
+			Ok(report) => {
+				if json {
+					println!("{}", session.report_json(&report, start.elapsed()))
+				} else {
+					println!("{}", session.format_report(&report))
+				}
+
+				std::process::exit(ExitCode::from(&report).code())
+			}
+			Err(e) => {
+				eprintln!("{e}");
+				std::process::exit(ExitCode::Error.code())
+			}
+		}
+	}
+
+	run(&mut session, io::stdin().lock(), io::stdout(), true)
+}
+
+#[test]
+fn script() {
+	let script = "\
+		@prefix ex: <https://example.org/#> .\n\
+		triple(ex:a, ex:parent, ex:b).\n\
+		triple(X, ex:ancestor, Y) :- triple(X, ex:parent, Y).\n\
+		:close\n\
+		:why triple(ex:a, ex:ancestor, Y)\n\
+		:quit\n\
+		:facts\n";
+
+	let mut output = Vec::new();
+	run(&mut Session::new(), script.as_bytes(), &mut output, false).unwrap();
+	assert_eq!(
+		String::from_utf8(output).unwrap(),
+		"1 fact added\n\
+		 rule #0 added\n\
+		 ex:a ex:ancestor ex:b .\n\
+		 1 entailed fact\n\
+		 ex:a ex:ancestor ex:b .\n  \
+		 by rule #0 with ?X = ex:a, ?Y = ex:b\n    \
+		 ex:a ex:parent ex:b .\n"
+	)
+}
@@ -0,0 +1,43 @@
+//! Storage round-trip.
+//!
+//! A deduction system is encoded into bytes, decoded back, and the decoded
+//! system deduces the same facts as the original.
+use inferdf::{rule, System};
+use rdf_types::{dataset::IndexedBTreeDataset, grdf_triples, Triple};
+
+fn main() {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		<"https://example.org/#citizensAreHuman">
+		for ?person, ?country {
+			?person <"https://example.org/#citizenOf"> ?country .
+		} => {
+			?person <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		}
+	});
+
+	let bytes = system.to_bytes().expect("encoding failed");
+	println!("encoded system: {} bytes", bytes.len());
+
+	let decoded = System::from_bytes(&bytes).expect("decoding failed");
+	assert_eq!(decoded.len(), system.len());
+
+	let dataset: IndexedBTreeDataset = grdf_triples![
+		_:"FrançoisDupont" <"https://example.org/#citizenOf"> _:"France" .
+	]
+	.into_iter()
+	.map(|t: Triple| t.into_quad(None))
+	.collect();
+
+	let mut a = dataset.clone();
+	let mut b = dataset;
+	system.close(&mut a).expect("closure failed");
+	decoded.close(&mut b).expect("closure failed");
+	assert_eq!(a, b)
+}
+
+#[test]
+fn run() {
+	main()
+}
@@ -0,0 +1,70 @@
+//! Validation with explanations.
+//!
+//! A dataset is validated against a deduction system, and every violation is
+//! explained with the violated rule and the reason of the violation.
+use inferdf::{rule, Reason, Sign, Signed, System};
+use rdf_types::{dataset::IndexedBTreeDataset, grdf_triples, Triple};
+
+fn main() {
+	let mut system = System::new();
+
+	// Every citizen is human.
+	system.insert(rule! {
+		<"https://example.org/#citizensAreHuman">
+		for ?person, ?country {
+			?person <"https://example.org/#citizenOf"> ?country .
+		} => {
+			?person <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		}
+	});
+
+	// Citizens should be adults (advisory).
+	system.insert(rule! {
+		warning <"https://example.org/#citizensAreAdults">
+		for ?person, ?country, ?age {
+			?person <"https://example.org/#citizenOf"> ?country .
+			?person <"https://example.org/#age"> ?age .
+		} => {
+			(>= ?age 18) .
+		}
+	});
+
+	let dataset: IndexedBTreeDataset = grdf_triples![
+		_:"FrançoisDupont" <"https://example.org/#citizenOf"> _:"France" .
+		_:"FrançoisDupont" <"https://example.org/#age"> "12"^^"http://www.w3.org/2001/XMLSchema#int" .
+	]
+	.into_iter()
+	.map(|t: Triple| t.into_quad(None))
+	.collect();
+
+	let report = system.report(&dataset).expect("validation failed");
+
+	for violation in &report.violations {
+		let rule = system.get(violation.rule).unwrap();
+		let name = match &rule.id {
+			Some(id) => id.to_string(),
+			None => format!("#{}", violation.rule),
+		};
+
+		let explanation = match &violation.reason {
+			Reason::MissingTriple(Signed(Sign::Positive, t)) => format!("missing triple {t} ."),
+			Reason::MissingTriple(Signed(Sign::Negative, t)) => {
+				format!("unexpected triple {t} .")
+			}
+			Reason::NotEq(a, b) => format!("{a} is not equal to {b}"),
+			Reason::NotNe(a, b) => format!("{a} is equal to {b}"),
+			Reason::NotTrue(r) => format!("{r} is not true"),
+			Reason::NotFalse(r) => format!("{r} is not false"),
+		};
+
+		println!("{:?} {name}: {explanation}", violation.severity)
+	}
+
+	assert_eq!(report.violations.len(), 2);
+	assert!(report.is_invalid());
+}
+
+#[test]
+fn run() {
+	main()
+}
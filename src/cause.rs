@@ -1,20 +1,46 @@
 #[cfg(feature = "paged")]
 use paged::Paged;
 
-use crate::Rule;
+use rdf_types::{pattern::ResourceOrVar, Triple};
+
+use crate::{rule::Variable, Rule, Signed};
+
+/// Source location of a stated fact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "paged", derive(Paged))]
+pub struct Source {
+	/// Index of the source document.
+	pub document: u32,
+
+	/// Line of the fact in the source document, starting from 1.
+	pub line: u32,
+}
+
+impl Source {
+	pub fn new(document: u32, line: u32) -> Self {
+		Self { document, line }
+	}
+}
 
 /// Cause of a deduction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "paged", derive(Paged))]
 pub enum Cause {
-	/// The deducted fact is stated.
-	Stated(u32),
+	/// The deducted fact is stated at the given source location.
+	Stated(Source),
 
 	/// The deducted fact is entailed.
 	Entailed(u32),
 }
 
 impl Cause {
+	pub fn into_stated(self) -> Option<Source> {
+		match self {
+			Self::Stated(source) => Some(source),
+			Self::Entailed(_) => None,
+		}
+	}
+
 	pub fn into_entailed(self) -> Option<u32> {
 		match self {
 			Self::Stated(_) => None,
@@ -37,4 +63,32 @@ impl<'r, T> Entailment<'r, T> {
 	pub fn new(rule: &'r Rule<T>, substitution: Vec<Option<T>>) -> Self {
 		Self { rule, substitution }
 	}
+
+	/// Returns the bound variables of the substitution, with their value.
+	pub fn bindings(&self) -> impl Iterator<Item = (Variable<'r>, &T)> {
+		let rule = self.rule;
+		self.substitution
+			.iter()
+			.enumerate()
+			.filter_map(move |(x, value)| Some((rule.variable(x), value.as_ref()?)))
+	}
+
+	/// Returns the hypothesis triples matched by this entailment.
+	pub fn premises(&self) -> impl '_ + Iterator<Item = Signed<Triple<T>>>
+	where
+		T: Clone,
+	{
+		let term = |r: &ResourceOrVar<T, usize>| match r {
+			ResourceOrVar::Resource(t) => t.clone(),
+			ResourceOrVar::Var(x) => self.substitution[*x].clone().unwrap(),
+		};
+
+		self.rule
+			.hypothesis
+			.patterns
+			.iter()
+			.map(move |Signed(sign, Triple(s, p, o))| {
+				Signed(*sign, Triple(term(s), term(p), term(o)))
+			})
+	}
 }
@@ -0,0 +1,58 @@
+//! Signed datasets storing positive and negative quads in two datasets.
+use rdf_types::{
+	dataset::{DatasetMut, PatternMatchingDataset, TraversableDataset},
+	Dataset, Quad,
+};
+
+use crate::{pattern::Canonical, utils::OptionIterator, Bipolar, Signed};
+
+use super::{SignedDatasetMut, SignedPatternMatchingDataset, TraversableSignedDataset};
+
+impl<D: Dataset> Dataset for Bipolar<D> {
+	type Resource = D::Resource;
+}
+
+impl<D: TraversableDataset> TraversableSignedDataset for Bipolar<D> {
+	type SignedQuads<'a>
+		= Bipolar<D::Quads<'a>>
+	where
+		Self: 'a;
+
+	fn signed_quads(&self) -> Self::SignedQuads<'_> {
+		Bipolar {
+			positive: self.positive.quads(),
+			negative: self.negative.quads(),
+		}
+	}
+}
+
+impl<D: PatternMatchingDataset> SignedPatternMatchingDataset for Bipolar<D> {
+	type SignedPatternMatching<'a, 'p>
+		= Bipolar<OptionIterator<D::QuadPatternMatching<'a, 'p>>>
+	where
+		Self: 'a,
+		Self::Resource: 'p;
+
+	fn signed_pattern_matching<'p>(
+		&self,
+		Signed(sign, pattern): Signed<Canonical<&'p Self::Resource>>,
+	) -> Self::SignedPatternMatching<'_, 'p> {
+		let mut result = Bipolar {
+			positive: OptionIterator(None),
+			negative: OptionIterator(None),
+		};
+
+		*result.get_mut(sign) = OptionIterator(Some(
+			self.get(sign)
+				.quad_pattern_matching(pattern.with_any_graph()),
+		));
+
+		result
+	}
+}
+
+impl<D: DatasetMut> SignedDatasetMut for Bipolar<D> {
+	fn insert(&mut self, Signed(sign, quad): Signed<Quad<Self::Resource>>) {
+		self.get_mut(sign).insert(quad)
+	}
+}
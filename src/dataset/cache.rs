@@ -0,0 +1,172 @@
+//! Pattern matching cache.
+//!
+//! Rules of a system often share hypothesis patterns (such as
+//! `?x rdf:type ?c`). Deducing from a [`CachedDataset`] scans the dataset
+//! once for each distinct pattern, whatever the number of rules using it.
+//!
+//! The cache lives for a single call: it borrows the dataset and has no
+//! notion of dataset version, so it is never reused across the rounds of a
+//! closure, which modify the dataset. Only
+//! [`System::deduce`](crate::System::deduce) uses it. Caching the per-triple
+//! deductions of the closure rounds showed no measurable gain on the closure
+//! benchmark.
+use std::{
+	cell::{Cell, RefCell},
+	collections::HashMap,
+	hash::Hash,
+	rc::Rc,
+};
+
+use rdf_types::{
+	dataset::FallibleDataset,
+	pattern::triple::canonical::{PatternObject, PatternPredicate, PatternSubject},
+	Quad,
+};
+
+use crate::{pattern::Canonical, FallibleSignedPatternMatchingDataset, Sign, Signed};
+
+/// Owned signed canonical pattern, made of the sign and components of the
+/// pattern.
+pub(crate) type PatternKey<R> = (
+	Sign,
+	PatternSubject<R>,
+	PatternPredicate<R>,
+	PatternObject<R>,
+);
+
+/// Returns the owned key of the given signed canonical pattern.
+pub(crate) fn pattern_key<R: Clone>(Signed(sign, pattern): Signed<Canonical<&R>>) -> PatternKey<R> {
+	(
+		sign,
+		pattern.into_subject().cloned(),
+		pattern.into_predicate().cloned(),
+		pattern.into_object().cloned(),
+	)
+}
+
+/// Cached matches of a pattern.
+type Matches<'d, R> = Rc<Vec<Signed<Quad<&'d R>>>>;
+
+/// Cached matches of each pattern.
+type Cache<'d, R> = RefCell<HashMap<PatternKey<R>, Matches<'d, R>>>;
+
+/// Dataset wrapper caching the results of signed pattern matching.
+///
+/// The wrapper borrows the dataset, which hence cannot change while the
+/// cache is in use: cached matches are always up to date. Matches are
+/// collected eagerly on the first use of a pattern. Errors are not cached.
+///
+/// Collecting and keying the matches has a cost, only paid off when many
+/// rules search the same dataset. The per-triple searches of the closure
+/// are mostly instantiated by the triple, and hence rarely shared.
+pub struct CachedDataset<'d, D: FallibleDataset> {
+	dataset: &'d D,
+	cache: Cache<'d, D::Resource>,
+	hits: Cell<usize>,
+}
+
+impl<'d, D: FallibleDataset> CachedDataset<'d, D> {
+	pub fn new(dataset: &'d D) -> Self {
+		Self {
+			dataset,
+			cache: RefCell::new(HashMap::new()),
+			hits: Cell::new(0),
+		}
+	}
+
+	/// Returns the underlying dataset.
+	pub fn dataset(&self) -> &'d D {
+		self.dataset
+	}
+
+	/// Returns the number of pattern matching requests answered from the
+	/// cache.
+	pub fn hits(&self) -> usize {
+		self.hits.get()
+	}
+
+	/// Returns the number of distinct patterns matched against the
+	/// underlying dataset.
+	pub fn misses(&self) -> usize {
+		self.cache.borrow().len()
+	}
+}
+
+impl<D: FallibleDataset> FallibleDataset for CachedDataset<'_, D> {
+	type Resource = D::Resource;
+	type Error = D::Error;
+}
+
+impl<'d, D> FallibleSignedPatternMatchingDataset for CachedDataset<'d, D>
+where
+	D: FallibleSignedPatternMatchingDataset,
+	D::Resource: Clone + Eq + Hash,
+{
+	type TrySignedPatternMatching<'a, 'p>
+		= CachedMatches<'a, D::Resource, D::Error>
+	where
+		Self: 'a,
+		Self::Resource: 'p;
+
+	fn try_signed_pattern_matching<'p>(
+		&self,
+		pattern: Signed<Canonical<&'p Self::Resource>>,
+	) -> Self::TrySignedPatternMatching<'_, 'p> {
+		let key = pattern_key(pattern);
+
+		if let Some(matches) = self.cache.borrow().get(&key) {
+			self.hits.set(self.hits.get() + 1);
+			return CachedMatches::new(Ok(matches.clone()));
+		}
+
+		let matches: Matches<D::Resource> = match self
+			.dataset
+			.try_signed_pattern_matching(pattern)
+			.collect::<Result<_, _>>()
+		{
+			Ok(matches) => Rc::new(matches),
+			Err(e) => return CachedMatches::new(Err(e)),
+		};
+
+		self.cache.borrow_mut().insert(key, matches.clone());
+		CachedMatches::new(Ok(matches))
+	}
+}
+
+/// Iterator over the cached matches of a pattern.
+pub struct CachedMatches<'a, R, E> {
+	matches: Option<Matches<'a, R>>,
+	index: usize,
+	error: Option<E>,
+}
+
+impl<'a, R, E> CachedMatches<'a, R, E> {
+	fn new(result: Result<Matches<'a, R>, E>) -> Self {
+		match result {
+			Ok(matches) => Self {
+				matches: Some(matches),
+				index: 0,
+				error: None,
+			},
+			Err(e) => Self {
+				matches: None,
+				index: 0,
+				error: Some(e),
+			},
+		}
+	}
+}
+
+impl<'a, R, E> Iterator for CachedMatches<'a, R, E> {
+	type Item = Result<Signed<Quad<&'a R>>, E>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if let Some(e) = self.error.take() {
+			return Some(Err(e));
+		}
+
+		let quad = *self.matches.as_ref()?.get(self.index)?;
+		self.index += 1;
+		Some(Ok(quad))
+	}
+}
@@ -0,0 +1,259 @@
+//! Signed dataset isomorphism.
+//!
+//! Two signed datasets are isomorphic if there exists a blank node
+//! identifier bijection turning one into the other. Blank nodes are first
+//! partitioned by color refinement (each blank node is colored by the signed
+//! quads it appears in, and the colors of its neighbors, until the partition
+//! is stable), then a bijection is searched between blank nodes of the same
+//! color.
+use std::{
+	collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet, HashMap},
+	hash::{Hash, Hasher},
+};
+
+use rdf_types::{BlankIdBuf, Quad, Term};
+
+use crate::{Signed, TraversableSignedDataset};
+
+/// Signed quad reference.
+type SignedQuad<'a> = Signed<Quad<&'a Term>>;
+
+/// Blank node identifier bijection.
+pub type Bijection<'a, 'b> = BTreeMap<&'a BlankIdBuf, &'b BlankIdBuf>;
+
+/// Checks that the signed datasets `a` and `b` are isomorphic.
+pub fn are_isomorphic<A, B>(a: &A, b: &B) -> bool
+where
+	A: TraversableSignedDataset<Resource = Term>,
+	B: TraversableSignedDataset<Resource = Term>,
+{
+	find_bijection(a, b).is_some()
+}
+
+/// Finds a blank node identifier bijection from `a` to `b`, if the signed
+/// datasets are isomorphic.
+pub fn find_bijection<'a, 'b, A, B>(a: &'a A, b: &'b B) -> Option<Bijection<'a, 'b>>
+where
+	A: TraversableSignedDataset<Resource = Term>,
+	B: TraversableSignedDataset<Resource = Term>,
+{
+	find_quads_bijection(a.signed_quads(), b.signed_quads())
+}
+
+/// Checks that the given two collections of signed quads are isomorphic.
+pub fn are_quads_isomorphic<'a, 'b>(
+	a: impl IntoIterator<Item = SignedQuad<'a>>,
+	b: impl IntoIterator<Item = SignedQuad<'b>>,
+) -> bool {
+	find_quads_bijection(a, b).is_some()
+}
+
+/// Finds a blank node identifier bijection from `a` to `b`, if the given
+/// collections of signed quads are isomorphic.
+pub fn find_quads_bijection<'a, 'b>(
+	a: impl IntoIterator<Item = SignedQuad<'a>>,
+	b: impl IntoIterator<Item = SignedQuad<'b>>,
+) -> Option<Bijection<'a, 'b>> {
+	let a: BTreeSet<_> = a.into_iter().collect();
+	let b: BTreeSet<_> = b.into_iter().collect();
+
+	if a.len() != b.len() {
+		return None;
+	}
+
+	let (a_ground, a_quads): (BTreeSet<_>, Vec<_>) = split_ground(a);
+	let (b_ground, b_quads): (BTreeSet<_>, Vec<_>) = split_ground(b);
+
+	if a_ground != b_ground || a_quads.len() != b_quads.len() {
+		return None;
+	}
+
+	let a_colors = refine(&a_quads);
+	let b_colors = refine(&b_quads);
+
+	let mut a_histogram: Vec<_> = a_colors.values().copied().collect();
+	let mut b_histogram: Vec<_> = b_colors.values().copied().collect();
+	a_histogram.sort_unstable();
+	b_histogram.sort_unstable();
+	if a_histogram != b_histogram {
+		return None;
+	}
+
+	let mut candidates: HashMap<u64, Vec<&'b BlankIdBuf>> = HashMap::new();
+	for (&blank, &color) in &b_colors {
+		candidates.entry(color).or_default().push(blank)
+	}
+
+	// Blank nodes with the fewest candidates first.
+	let mut order: Vec<_> = a_colors.keys().copied().collect();
+	order.sort_by_key(|blank| candidates[&a_colors[blank]].len());
+
+	let mut quads_of: BTreeMap<&BlankIdBuf, Vec<usize>> = BTreeMap::new();
+	for (i, quad) in a_quads.iter().enumerate() {
+		for term in quad_terms(quad) {
+			if let Some(blank) = term.as_blank() {
+				quads_of.entry(blank).or_default().push(i)
+			}
+		}
+	}
+
+	let search = Search {
+		a_quads: &a_quads,
+		b_quads: b_quads.into_iter().collect(),
+		a_colors: &a_colors,
+		candidates: &candidates,
+		quads_of: &quads_of,
+		order: &order,
+	};
+
+	let mut bijection = Bijection::new();
+	let mut used = BTreeSet::new();
+	if search.run(0, &mut bijection, &mut used) {
+		Some(bijection)
+	} else {
+		None
+	}
+}
+
+fn split_ground(quads: BTreeSet<SignedQuad>) -> (BTreeSet<SignedQuad>, Vec<SignedQuad>) {
+	let (ground, other): (BTreeSet<_>, BTreeSet<_>) = quads
+		.into_iter()
+		.partition(|q| quad_terms(q).all(|t| !t.is_blank()));
+	(ground, other.into_iter().collect())
+}
+
+fn quad_terms<'q, 'a>(Signed(_, quad): &'q SignedQuad<'a>) -> impl 'q + Iterator<Item = &'a Term> {
+	[Some(quad.0), Some(quad.1), Some(quad.2), quad.3]
+		.into_iter()
+		.flatten()
+}
+
+/// Colors each blank node of the given quads by color refinement.
+fn refine<'a>(quads: &[SignedQuad<'a>]) -> BTreeMap<&'a BlankIdBuf, u64> {
+	let mut colors: BTreeMap<&BlankIdBuf, u64> = quads
+		.iter()
+		.flat_map(quad_terms)
+		.filter_map(Term::as_blank)
+		.map(|b| (b, 0))
+		.collect();
+	let mut classes = 1;
+
+	loop {
+		let mut signatures: BTreeMap<&BlankIdBuf, Vec<u64>> = BTreeMap::new();
+
+		for quad @ Signed(sign, _) in quads {
+			for (i, term) in quad_terms(quad).enumerate() {
+				if let Some(blank) = term.as_blank() {
+					let mut hasher = DefaultHasher::new();
+					sign.hash(&mut hasher);
+					i.hash(&mut hasher);
+
+					for other in quad_terms(quad) {
+						match other.as_blank() {
+							Some(b) if b == blank => 0u8.hash(&mut hasher),
+							Some(b) => {
+								1u8.hash(&mut hasher);
+								colors[b].hash(&mut hasher)
+							}
+							None => {
+								2u8.hash(&mut hasher);
+								other.hash(&mut hasher)
+							}
+						}
+					}
+
+					signatures.entry(blank).or_default().push(hasher.finish())
+				}
+			}
+		}
+
+		let refined: BTreeMap<_, _> = signatures
+			.into_iter()
+			.map(|(blank, mut signature)| {
+				signature.sort_unstable();
+				let mut hasher = DefaultHasher::new();
+				colors[blank].hash(&mut hasher);
+				signature.hash(&mut hasher);
+				(blank, hasher.finish())
+			})
+			.collect();
+
+		let refined_classes = refined.values().collect::<BTreeSet<_>>().len();
+		colors = refined;
+
+		if refined_classes == classes {
+			break colors;
+		}
+
+		classes = refined_classes
+	}
+}
+
+struct Search<'s, 'a, 'b> {
+	a_quads: &'s [SignedQuad<'a>],
+	b_quads: BTreeSet<SignedQuad<'b>>,
+	a_colors: &'s BTreeMap<&'a BlankIdBuf, u64>,
+	candidates: &'s HashMap<u64, Vec<&'b BlankIdBuf>>,
+	quads_of: &'s BTreeMap<&'a BlankIdBuf, Vec<usize>>,
+	order: &'s [&'a BlankIdBuf],
+}
+
+impl<'a, 'b> Search<'_, 'a, 'b> {
+	fn run(
+		&self,
+		i: usize,
+		bijection: &mut Bijection<'a, 'b>,
+		used: &mut BTreeSet<&'b BlankIdBuf>,
+	) -> bool {
+		let Some(&blank) = self.order.get(i) else {
+			return true;
+		};
+
+		for &candidate in &self.candidates[&self.a_colors[blank]] {
+			if used.contains(candidate) {
+				continue;
+			}
+
+			bijection.insert(blank, candidate);
+			used.insert(candidate);
+
+			if self.is_consistent(blank, bijection) && self.run(i + 1, bijection, used) {
+				return true;
+			}
+
+			bijection.remove(blank);
+			used.remove(candidate);
+		}
+
+		false
+	}
+
+	/// Checks that every quad of `a` mentioning `blank` whose blank nodes are
+	/// all mapped has an image in `b`.
+	fn is_consistent(&self, blank: &BlankIdBuf, bijection: &Bijection<'a, 'b>) -> bool {
+		self.quads_of[blank].iter().all(|&q| {
+			let Signed(sign, quad) = &self.a_quads[q];
+			let image = |t: &'a Term| match t.as_blank() {
+				Some(b) => bijection.get(b).map(|b| Term::blank((*b).clone())),
+				None => Some(t.clone()),
+			};
+
+			let mapped = (|| {
+				Some(Quad(
+					image(quad.0)?,
+					image(quad.1)?,
+					image(quad.2)?,
+					match quad.3 {
+						Some(g) => Some(image(g)?),
+						None => None,
+					},
+				))
+			})();
+
+			match mapped {
+				Some(mapped) => self.b_quads.contains(&Signed(*sign, mapped.as_ref())),
+				None => true,
+			}
+		})
+	}
+}
@@ -1,5 +1,5 @@
 use rdf_types::{
-	dataset::{FallibleDataset, PatternMatchingDataset},
+	dataset::{FallibleDataset, PatternMatchingDataset, TraversableDataset},
 	Dataset, Quad, Triple,
 };
 
@@ -9,6 +9,12 @@ use crate::{
 	PositiveIterator, Sign, Signed,
 };
 
+mod bipolar;
+pub mod cache;
+pub mod isomorphism;
+pub mod stats;
+pub mod subset;
+
 /// Collection of signed quads that can be iterated over.
 pub trait TraversableSignedDataset: Dataset {
 	/// Signed quads iterator.
@@ -20,6 +26,14 @@ pub trait TraversableSignedDataset: Dataset {
 	fn signed_quads(&self) -> Self::SignedQuads<'_>;
 }
 
+impl<D: TraversableDataset> TraversableSignedDataset for D {
+	type SignedQuads<'a> = PositiveIterator<D::Quads<'a>> where Self: 'a;
+
+	fn signed_quads(&self) -> Self::SignedQuads<'_> {
+		PositiveIterator(self.quads())
+	}
+}
+
 /// Collection of signed triple with pattern matching method.
 pub trait SignedPatternMatchingDataset: Dataset {
 	/// Matching signed triple iterator.
@@ -0,0 +1,110 @@
+//! Dataset statistics.
+//!
+//! Aggregates the number of facts per predicate, class and graph, to get an
+//! overview of a dataset before and after closure.
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fmt,
+};
+
+use iref::Iri;
+use rdf_types::{dataset::TraversableDataset, Quad, Term};
+use static_iref::iri;
+
+use crate::{
+	display::{DisplayContext, DisplayWithContext},
+	same_as::Identities,
+};
+
+const RDF_TYPE: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+
+/// Dataset statistics.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Statistics {
+	/// Number of quads.
+	pub quads: usize,
+
+	/// Number of distinct subjects and objects.
+	pub resources: usize,
+
+	/// Number of resources merged into another one.
+	///
+	/// Only set by [`Self::with_identities`].
+	pub merged: usize,
+
+	/// Number of quads using each predicate.
+	pub predicates: BTreeMap<Term, usize>,
+
+	/// Number of instances of each class, given by `rdf:type` quads.
+	///
+	/// A resource typed in several graphs is counted once.
+	pub classes: BTreeMap<Term, usize>,
+
+	/// Number of quads in each graph, `None` being the default graph.
+	pub graphs: BTreeMap<Option<Term>, usize>,
+}
+
+impl Statistics {
+	/// Computes the statistics of the given dataset.
+	pub fn new<D>(dataset: &D) -> Self
+	where
+		D: TraversableDataset<Resource = Term>,
+	{
+		let mut result = Self::default();
+		let mut resources = BTreeSet::new();
+		let mut instances = BTreeSet::new();
+
+		for Quad(s, p, o, g) in dataset.quads() {
+			result.quads += 1;
+			resources.insert(s);
+			resources.insert(o);
+			*result.predicates.entry(p.clone()).or_default() += 1;
+			*result.graphs.entry(g.cloned()).or_default() += 1;
+
+			if p.as_iri().is_some_and(|p| p == RDF_TYPE) && instances.insert((o, s)) {
+				*result.classes.entry(o.clone()).or_default() += 1
+			}
+		}
+
+		result.resources = resources.len();
+		result
+	}
+
+	/// Sets the number of merged resources from the given identities.
+	pub fn with_identities(mut self, identities: &Identities) -> Self {
+		self.merged = identities.merged();
+		self
+	}
+}
+
+impl DisplayWithContext for Statistics {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} quads, {} resources, {} merged",
+			self.quads, self.resources, self.merged
+		)?;
+
+		let sections = [("predicates", &self.predicates), ("classes", &self.classes)];
+		for (name, counts) in sections {
+			if !counts.is_empty() {
+				write!(f, "\n{name}:")?;
+				for (term, count) in counts {
+					write!(f, "\n  {} {count}", term.with(context))?;
+				}
+			}
+		}
+
+		if !self.graphs.is_empty() {
+			write!(f, "\ngraphs:")?;
+			for (graph, count) in &self.graphs {
+				match graph {
+					Some(g) => write!(f, "\n  {} {count}", g.with(context))?,
+					None => write!(f, "\n  (default) {count}")?,
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
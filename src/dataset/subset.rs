@@ -0,0 +1,102 @@
+//! Dataset sub-setting.
+//!
+//! Extracts the slice of a dataset relevant to a set of seed resources,
+//! following the links from subjects to objects.
+use std::{collections::BTreeSet, fmt};
+
+use rdf_types::{
+	dataset::PatternMatchingDataset, pattern::CanonicalQuadPattern, Quad, Term, Triple,
+};
+
+/// Extracts the quads reachable from the given seed resources.
+///
+/// A quad is reachable if its subject is a seed, or the object of a
+/// reachable quad, in any graph. At most `depth` links are followed from the
+/// seeds, or any number if `depth` is `None`: with a depth of `0`, only the
+/// quads whose subject is a seed are extracted.
+pub fn extract<D, R>(
+	dataset: &D,
+	seeds: impl IntoIterator<Item = D::Resource>,
+	depth: Option<usize>,
+) -> R
+where
+	D: PatternMatchingDataset,
+	D::Resource: Clone + Ord,
+	R: FromIterator<Quad<D::Resource>>,
+{
+	let mut visited: BTreeSet<D::Resource> = BTreeSet::new();
+	let mut result = BTreeSet::new();
+	let mut frontier: Vec<D::Resource> = seeds.into_iter().collect();
+	let mut distance = 0;
+
+	while !frontier.is_empty() {
+		let mut next = Vec::new();
+
+		for subject in frontier {
+			if visited.insert(subject.clone()) {
+				let pattern =
+					CanonicalQuadPattern::from_option_quad(Quad(Some(&subject), None, None, None));
+
+				for Quad(s, p, o, g) in dataset.quad_pattern_matching(pattern) {
+					next.push(o.clone());
+					result.insert(Quad(s.clone(), p.clone(), o.clone(), g.cloned()));
+				}
+			}
+		}
+
+		if depth.is_some_and(|depth| distance >= depth) {
+			break;
+		}
+
+		distance += 1;
+		frontier = next;
+	}
+
+	result.into_iter().collect()
+}
+
+/// Computes the Concise Bounded Description (CBD) of the given resource.
+///
+/// The description is made of every triple whose subject is the resource,
+/// in any graph, and recursively of the description of every blank node
+/// object of these triples. Reifications are not included.
+///
+/// Use [`write_ntriples`] to render the description.
+pub fn describe<D, R>(dataset: &D, resource: &Term) -> R
+where
+	D: PatternMatchingDataset<Resource = Term>,
+	R: FromIterator<Triple>,
+{
+	let mut visited = BTreeSet::new();
+	let mut result = BTreeSet::new();
+	let mut stack = vec![resource];
+
+	while let Some(subject) = stack.pop() {
+		if visited.insert(subject) {
+			let pattern =
+				CanonicalQuadPattern::from_option_quad(Quad(Some(subject), None, None, None));
+
+			for Quad(s, p, o, _) in dataset.quad_pattern_matching(pattern) {
+				if o.is_blank() {
+					stack.push(o)
+				}
+
+				result.insert(Triple(s.clone(), p.clone(), o.clone()));
+			}
+		}
+	}
+
+	result.into_iter().collect()
+}
+
+/// Writes the given triples as an N-Triples document, one triple per line.
+pub fn write_ntriples<'a>(
+	out: &mut impl fmt::Write,
+	triples: impl IntoIterator<Item = &'a Triple>,
+) -> fmt::Result {
+	for triple in triples {
+		writeln!(out, "{triple} .")?
+	}
+
+	Ok(())
+}
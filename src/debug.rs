@@ -0,0 +1,207 @@
+//! Rule debugging utilities.
+//!
+//! When a rule system misbehaves on a large dataset, the functions of this
+//! module shrink the dataset to a minimal subset still reproducing the
+//! unexpected deduction or violation, using delta debugging.
+use std::{
+	collections::{HashMap, HashSet},
+	convert::Infallible,
+};
+
+use rdf_types::{
+	dataset::{IndexedBTreeDataset, IndexedBTreeGraph},
+	generator, Term, Triple,
+};
+
+use crate::{
+	expression,
+	system::{Deductions, DeductionsInstance},
+	Entailment, Signed, System, TripleStatement, GENERATED_BLANK_ID_PREFIX,
+};
+
+/// Shrinks the given items to a minimal subset passing the given test.
+///
+/// Returns `None` if the whole input does not pass the test. Otherwise the
+/// returned subset is 1-minimal: removing any single item from it makes the
+/// test fail.
+pub fn minimize<T: Clone>(items: Vec<T>, mut test: impl FnMut(&[T]) -> bool) -> Option<Vec<T>> {
+	let result: Result<_, Infallible> = try_minimize(items, |items| Ok(test(items)));
+	result.unwrap()
+}
+
+/// Shrinks the given items to a minimal subset passing the given fallible
+/// test.
+///
+/// See [`minimize`].
+pub fn try_minimize<T: Clone, E>(
+	mut items: Vec<T>,
+	mut test: impl FnMut(&[T]) -> Result<bool, E>,
+) -> Result<Option<Vec<T>>, E> {
+	if !test(&items)? {
+		return Ok(None);
+	}
+
+	let mut granularity = 2;
+	while items.len() >= 2 {
+		let chunk_len = items.len().div_ceil(granularity);
+		let mut reduced = false;
+
+		for start in (0..items.len()).step_by(chunk_len) {
+			let end = (start + chunk_len).min(items.len());
+			let complement: Vec<T> = items[..start]
+				.iter()
+				.chain(&items[end..])
+				.cloned()
+				.collect();
+
+			if test(&complement)? {
+				items = complement;
+				granularity = (granularity - 1).max(2);
+				reduced = true;
+				break;
+			}
+		}
+
+		if !reduced {
+			if granularity >= items.len() {
+				break;
+			}
+
+			granularity = (granularity * 2).min(items.len())
+		}
+	}
+
+	Ok(Some(items))
+}
+
+/// Shrinks the given dataset to a minimal subset from which the given
+/// system still deduces the `target` statement.
+///
+/// The target may be deduced at any depth: each subset is first closed under
+/// the system (see [`System::close`]), and the target must be deduced from
+/// the closed subset. Returns `None` if the target is not deduced from the
+/// whole dataset.
+///
+/// The search starts from the [support](deduction_support) of the target,
+/// and only falls back to the whole dataset if the support alone does not
+/// deduce it (for instance when it relies on list patterns).
+pub fn minimize_deduction(
+	system: &System,
+	dataset: Vec<Triple>,
+	target: &Signed<Triple>,
+) -> Result<Option<Vec<Triple>>, expression::Error> {
+	let Some(support) = deduction_support(system, &dataset, target)? else {
+		return Ok(None);
+	};
+
+	if let Some(minimal) = try_minimize(support, |triples| deduces(system, triples, target))? {
+		return Ok(Some(minimal));
+	}
+
+	try_minimize(dataset, |triples| deduces(system, triples, target))
+}
+
+/// Returns the facts of the given dataset supporting the deduction of the
+/// `target` statement.
+///
+/// The causes of the closure of the dataset (see [`System::close_observed`])
+/// are followed back from the entailments deducing the target, up to the
+/// positive hypothesis facts of the dataset. Facts are returned in dataset
+/// order. Returns `None` if the target is not deduced from the dataset.
+pub fn deduction_support(
+	system: &System,
+	dataset: &[Triple],
+	target: &Signed<Triple>,
+) -> Result<Option<Vec<Triple>>, expression::Error> {
+	let mut closed: IndexedBTreeDataset =
+		dataset.iter().cloned().map(|t| t.into_quad(None)).collect();
+
+	// Positive premises of the first entailment of each inserted triple.
+	let mut causes: HashMap<Triple, Vec<Triple>> = HashMap::new();
+	system.close_observed(&mut closed, |t, e| {
+		causes.insert(t.clone(), positive_premises(e).collect());
+	})?;
+
+	let mut stack = Vec::new();
+	let mut deduced = false;
+	for d in eval(system.deduce(&closed))? {
+		if deduces_statement(&d.statements, target) {
+			stack.extend(positive_premises(&d.entailment));
+			deduced = true
+		}
+	}
+
+	if !deduced {
+		return Ok(None);
+	}
+
+	let mut visited = HashSet::new();
+	while let Some(t) = stack.pop() {
+		if let Some(premises) = causes.get(&t) {
+			if !visited.contains(&t) {
+				stack.extend(premises.iter().cloned())
+			}
+		}
+
+		visited.insert(t);
+	}
+
+	Ok(Some(
+		dataset
+			.iter()
+			.filter(|t| visited.contains(*t))
+			.cloned()
+			.collect(),
+	))
+}
+
+/// Checks that the closure of the given triples deduces the target.
+fn deduces(
+	system: &System,
+	triples: &[Triple],
+	target: &Signed<Triple>,
+) -> Result<bool, expression::Error> {
+	let mut closed: IndexedBTreeDataset =
+		triples.iter().cloned().map(|t| t.into_quad(None)).collect();
+	system.close(&mut closed)?;
+
+	Ok(eval(system.deduce(&closed))?
+		.into_iter()
+		.any(|d| deduces_statement(&d.statements, target)))
+}
+
+fn eval(deductions: Deductions) -> Result<DeductionsInstance, expression::Error> {
+	deductions.eval(generator::Blank::new_with_prefix(
+		GENERATED_BLANK_ID_PREFIX.to_owned(),
+	))
+}
+
+fn deduces_statement(
+	statements: &[Signed<TripleStatement<Term>>],
+	target: &Signed<Triple>,
+) -> bool {
+	statements.iter().any(|Signed(sign, statement)| {
+		*sign == target.0 && matches!(statement, TripleStatement::Triple(t) if *t == target.1)
+	})
+}
+
+fn positive_premises<'a>(entailment: &'a Entailment<Term>) -> impl 'a + Iterator<Item = Triple> {
+	entailment
+		.premises()
+		.filter_map(|Signed(sign, t)| sign.is_positive().then_some(t))
+}
+
+/// Shrinks the given dataset to a minimal subset still violating the given
+/// system.
+///
+/// Only [`Severity::Error`](crate::Severity::Error) rules are considered.
+/// Returns `None` if the whole dataset is valid.
+pub fn minimize_violation(
+	system: &System,
+	dataset: Vec<Triple>,
+) -> Result<Option<Vec<Triple>>, expression::Error> {
+	try_minimize(dataset, |triples| {
+		let graph: IndexedBTreeGraph = triples.iter().cloned().collect();
+		Ok(system.report(&graph)?.is_invalid())
+	})
+}
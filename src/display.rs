@@ -0,0 +1,253 @@
+//! Compact rendering of terms in diagnostics.
+//!
+//! Fully expanded IRIs make error messages and validation reports hard to
+//! read. A [`DisplayContext`] holds a prefix map and a base IRI used to
+//! compact IRIs, in the Turtle syntax, when rendering terms, triples and
+//! violation reasons.
+//!
+//! ```
+//! use inferdf::display::{DisplayContext, DisplayWithContext};
+//! use iref::IriBuf;
+//! use rdf_types::{Id, Term};
+//!
+//! let context = DisplayContext::new().with_prefix(
+//!   "ex".to_owned(),
+//!   IriBuf::new("https://example.org/#".to_owned()).unwrap()
+//! );
+//!
+//! let term = Term::Id(Id::Iri(IriBuf::new("https://example.org/#alice".to_owned()).unwrap()));
+//! assert_eq!(term.with(&context).to_string(), "ex:alice");
+//! ```
+use std::{collections::BTreeMap, fmt};
+
+use iref::{Iri, IriBuf};
+use rdf_types::{Id, Literal, LiteralType, RdfDisplay, Term, Triple};
+
+use crate::{Reason, Sign, Signed, TripleStatement};
+
+/// Display context, used to compact IRIs in diagnostics.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DisplayContext {
+	/// Namespace IRI of each prefix.
+	prefixes: BTreeMap<String, IriBuf>,
+
+	/// Base IRI, against which other IRIs are written relative.
+	base: Option<IriBuf>,
+}
+
+impl DisplayContext {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_prefix(mut self, prefix: String, namespace: IriBuf) -> Self {
+		self.insert_prefix(prefix, namespace);
+		self
+	}
+
+	pub fn with_base(mut self, base: IriBuf) -> Self {
+		self.base = Some(base);
+		self
+	}
+
+	/// Declares a prefix, replacing any previous declaration of the same
+	/// prefix.
+	pub fn insert_prefix(&mut self, prefix: String, namespace: IriBuf) -> Option<IriBuf> {
+		self.prefixes.insert(prefix, namespace)
+	}
+
+	pub fn set_base(&mut self, base: Option<IriBuf>) {
+		self.base = base
+	}
+
+	pub fn base(&self) -> Option<&Iri> {
+		self.base.as_deref()
+	}
+
+	/// Returns the namespace IRI of the given prefix.
+	pub fn prefix(&self, prefix: &str) -> Option<&Iri> {
+		self.prefixes.get(prefix).map(IriBuf::as_iri)
+	}
+
+	pub fn prefixes(&self) -> impl Iterator<Item = (&str, &Iri)> {
+		self.prefixes
+			.iter()
+			.map(|(prefix, namespace)| (prefix.as_str(), namespace.as_iri()))
+	}
+
+	/// Adds the prefixes of the given context to this one.
+	///
+	/// Prefixes of `other` take precedence, and its base IRI is used if this
+	/// context has none.
+	pub fn extend(&mut self, other: &Self) {
+		self.prefixes.extend(other.prefixes.clone());
+		if self.base.is_none() {
+			self.base = other.base.clone()
+		}
+	}
+
+	/// Writes the given IRI as compactly as possible.
+	///
+	/// The IRI is written as a prefixed name using the longest matching
+	/// namespace, provided the remaining local name is a valid Turtle local
+	/// name. Otherwise it is written relative to the base IRI, if any, or in
+	/// full.
+	pub fn fmt_iri(&self, iri: &Iri, f: &mut fmt::Formatter) -> fmt::Result {
+		let prefixed = self
+			.prefixes
+			.iter()
+			.filter_map(|(prefix, namespace)| {
+				let local = iri.as_str().strip_prefix(namespace.as_str())?;
+				is_local_name(local).then_some((prefix, local))
+			})
+			.min_by_key(|(_, local)| local.len());
+
+		if let Some((prefix, local)) = prefixed {
+			return write!(f, "{prefix}:{local}");
+		}
+
+		if let Some(relative) = self
+			.base
+			.as_ref()
+			.and_then(|base| iri.as_str().strip_prefix(base.as_str()))
+		{
+			return write!(f, "<{relative}>");
+		}
+
+		iri.rdf_fmt(f)
+	}
+}
+
+/// Checks that the given string is a valid (unescaped) Turtle local name.
+fn is_local_name(local: &str) -> bool {
+	local
+		.chars()
+		.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'))
+		&& !local.starts_with(['-', '.'])
+		&& !local.ends_with('.')
+}
+
+/// Type that can be displayed with a [`DisplayContext`].
+pub trait DisplayWithContext {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result;
+
+	/// Binds the given context to this value, for use with `format!`.
+	fn with<'a>(&'a self, context: &'a DisplayContext) -> WithContext<'a, Self> {
+		WithContext(self, context)
+	}
+}
+
+/// Value bound to a display context.
+pub struct WithContext<'a, T: ?Sized>(&'a T, &'a DisplayContext);
+
+impl<T: ?Sized + DisplayWithContext> fmt::Display for WithContext<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt_with(self.1, f)
+	}
+}
+
+impl<T: ?Sized + DisplayWithContext> DisplayWithContext for &T {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		T::fmt_with(*self, context, f)
+	}
+}
+
+impl DisplayWithContext for Iri {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		context.fmt_iri(self, f)
+	}
+}
+
+impl DisplayWithContext for IriBuf {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		context.fmt_iri(self, f)
+	}
+}
+
+impl DisplayWithContext for Id {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Iri(iri) => context.fmt_iri(iri, f),
+			Self::Blank(b) => write!(f, "{b}"),
+		}
+	}
+}
+
+impl DisplayWithContext for Literal {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		self.value.rdf_fmt(f)?;
+		match &self.type_ {
+			LiteralType::Any(_) if self.type_.is_xsd_string() => Ok(()),
+			LiteralType::Any(ty) => {
+				write!(f, "^^")?;
+				context.fmt_iri(ty, f)
+			}
+			LiteralType::LangString(tag) => write!(f, "@{tag}"),
+		}
+	}
+}
+
+impl DisplayWithContext for Term {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Id(id) => id.fmt_with(context, f),
+			Self::Literal(l) => l.fmt_with(context, f),
+		}
+	}
+}
+
+impl<S, P, O> DisplayWithContext for Triple<S, P, O>
+where
+	S: DisplayWithContext,
+	P: DisplayWithContext,
+	O: DisplayWithContext,
+{
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} {} {}",
+			self.0.with(context),
+			self.1.with(context),
+			self.2.with(context)
+		)
+	}
+}
+
+impl<T: DisplayWithContext> DisplayWithContext for TripleStatement<T> {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Triple(t) => t.fmt_with(context, f),
+			Self::Eq(a, b) => write!(f, "{} = {}", a.with(context), b.with(context)),
+			Self::True(r) => write!(f, "{} = true", r.with(context)),
+		}
+	}
+}
+
+impl<T: DisplayWithContext> DisplayWithContext for Signed<T> {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_negative() {
+			write!(f, "!")?;
+		}
+
+		self.1.fmt_with(context, f)
+	}
+}
+
+impl<R: DisplayWithContext> DisplayWithContext for Reason<R> {
+	fn fmt_with(&self, context: &DisplayContext, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::MissingTriple(Signed(Sign::Positive, t)) => {
+				write!(f, "missing triple {} .", t.with(context))
+			}
+			Self::MissingTriple(Signed(Sign::Negative, t)) => {
+				write!(f, "missing negative triple {} .", t.with(context))
+			}
+			Self::NotEq(a, b) => {
+				write!(f, "{} is not equal to {}", a.with(context), b.with(context))
+			}
+			Self::NotNe(a, b) => write!(f, "{} is equal to {}", a.with(context), b.with(context)),
+			Self::NotTrue(r) => write!(f, "{} is not true", r.with(context)),
+			Self::NotFalse(r) => write!(f, "{} is not false", r.with(context)),
+		}
+	}
+}
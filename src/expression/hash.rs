@@ -0,0 +1,41 @@
+//! Identifier minting functions.
+use iref::IriBuf;
+use sha2::{Digest, Sha256};
+
+/// Returns the lowercase hexadecimal SHA-256 digest of the given string.
+pub fn sha256_hex(value: &str) -> String {
+	Sha256::digest(value.as_bytes())
+		.iter()
+		.map(|b| format!("{b:02x}"))
+		.collect()
+}
+
+/// Returns the lowercase hexadecimal BLAKE3 digest of the given string.
+pub fn blake3_hex(value: &str) -> String {
+	blake3::hash(value.as_bytes()).to_hex().to_string()
+}
+
+/// Returns the `urn:uuid:` IRI of the name-based UUID derived from the given
+/// string.
+///
+/// The UUID is a version 8 UUID built from the SHA-256 digest of the string,
+/// as described in [RFC 9562 Appendix B.2][rfc9562], so the same string always
+/// gives the same UUID.
+///
+/// [rfc9562]: <https://www.rfc-editor.org/rfc/rfc9562#appendix-B.2>
+pub fn uuid(value: &str) -> IriBuf {
+	let mut bytes: [u8; 16] = Sha256::digest(value.as_bytes())[..16].try_into().unwrap();
+	bytes[6] = (bytes[6] & 0x0f) | 0x80;
+	bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+	let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+	IriBuf::new(format!(
+		"urn:uuid:{}-{}-{}-{}-{}",
+		&hex[..8],
+		&hex[8..12],
+		&hex[12..16],
+		&hex[16..20],
+		&hex[20..]
+	))
+	.unwrap()
+}
@@ -1,7 +1,8 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, fmt};
 
+use num_rational::BigRational;
 use serde::{Deserialize, Serialize};
-use xsd_types::Decimal;
+use xsd_types::{Decimal, Double, Float};
 
 use super::{Regex, Value};
 
@@ -9,7 +10,13 @@ use super::{Regex, Value};
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Literal {
 	/// Decimal value.
-	Decimal(#[serde(with = "decimal")] Decimal),
+	Decimal(#[serde(with = "decimal")] DecimalLiteral),
+
+	/// Single precision floating point value.
+	Float(#[serde(with = "float")] Float),
+
+	/// Double precision floating point value.
+	Double(#[serde(with = "double")] Double),
 
 	/// Text string.
 	String(String),
@@ -20,15 +27,46 @@ pub enum Literal {
 
 impl Literal {
 	/// Evaluates the literal expression.
-	pub fn eval<R: Clone>(&self) -> Value<R> {
+	pub fn eval<R: Clone>(&self) -> Value<'_, R> {
 		match self {
-			Self::Decimal(d) => Value::Decimal(Cow::Borrowed(d)),
+			Self::Decimal(d) => Value::Decimal(Cow::Owned(d.to_decimal())),
+			Self::Float(f) => Value::Float(*f),
+			Self::Double(d) => Value::Double(*d),
 			Self::String(s) => Value::String(Cow::Borrowed(s)),
 			Self::Regex(e) => Value::Regex(Cow::Borrowed(e)),
 		}
 	}
 }
 
+/// Decimal literal value.
+///
+/// Unlike [`Decimal`], which lazily computes its lexical representation in an
+/// unsynchronized cell, this type is [`Sync`]: rules can be shared between
+/// threads.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DecimalLiteral(BigRational);
+
+impl DecimalLiteral {
+	/// Returns the decimal value of the literal.
+	pub fn to_decimal(&self) -> Decimal {
+		// The rational comes from a decimal, hence has a finite decimal
+		// representation.
+		Decimal::try_from(self.0.clone()).unwrap()
+	}
+}
+
+impl From<Decimal> for DecimalLiteral {
+	fn from(value: Decimal) -> Self {
+		Self(value.into_big_rational())
+	}
+}
+
+impl fmt::Debug for DecimalLiteral {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.to_decimal().fmt(f)
+	}
+}
+
 impl<'a> From<&'a str> for Literal {
 	fn from(value: &'a str) -> Self {
 		Self::String(value.to_owned())
@@ -40,7 +78,7 @@ macro_rules! literal_from_int {
 		$(
 			impl From<$ty> for Literal {
 				fn from(value: $ty) -> Self {
-					Self::Decimal(value.into())
+					Self::Decimal(Decimal::from(value).into())
 				}
 			}
 		)*
@@ -49,6 +87,56 @@ macro_rules! literal_from_int {
 
 literal_from_int!(u8, u16, u32, u64, i8, i16, i32, i64);
 
+impl From<f32> for Literal {
+	fn from(value: f32) -> Self {
+		Self::Float(value.into())
+	}
+}
+
+impl From<f64> for Literal {
+	fn from(value: f64) -> Self {
+		Self::Double(value.into())
+	}
+}
+
+mod float {
+	use serde::{Deserialize, Deserializer, Serialize, Serializer};
+	use xsd_types::Float;
+
+	pub fn serialize<S>(value: &Float, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		value.into_f32().serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<Float, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		f32::deserialize(deserializer).map(Float::new)
+	}
+}
+
+mod double {
+	use serde::{Deserialize, Deserializer, Serialize, Serializer};
+	use xsd_types::Double;
+
+	pub fn serialize<S>(value: &Double, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		value.into_f64().serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<Double, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		f64::deserialize(deserializer).map(Double::new)
+	}
+}
+
 mod decimal {
 	use serde::{de, Deserializer, Serialize, Serializer};
 	use xsd_types::{
@@ -57,10 +145,13 @@ mod decimal {
 		UnsignedShortDatatype,
 	};
 
-	pub fn serialize<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+	use super::DecimalLiteral;
+
+	pub fn serialize<S>(value: &DecimalLiteral, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: Serializer,
 	{
+		let value = &value.to_decimal();
 		match value.decimal_type() {
 			DecimalDatatype::Decimal => match value.as_f64() {
 				Some(f) => f.serialize(serializer),
@@ -114,7 +205,7 @@ mod decimal {
 		}
 	}
 
-	pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<DecimalLiteral, D::Error>
 	where
 		D: Deserializer<'de>,
 	{
@@ -184,6 +275,6 @@ mod decimal {
 			}
 		}
 
-		deserializer.deserialize_any(Visitor)
+		deserializer.deserialize_any(Visitor).map(Into::into)
 	}
 }
@@ -3,6 +3,7 @@ use core::fmt;
 use std::borrow::Cow;
 
 use iref::IriBuf;
+use langtag::LangTagBuf;
 use rdf_types::{
 	interpretation::ReverseTermInterpretation, vocabulary::EmbedIntoVocabulary, Interpretation,
 	Term, Triple, Vocabulary,
@@ -10,6 +11,8 @@ use rdf_types::{
 use serde::{Deserialize, Serialize};
 use xsd_types::ParseXsdError;
 
+mod hash;
+
 mod literal;
 pub use literal::*;
 
@@ -19,7 +22,10 @@ pub use value::{Regex, Value};
 use value::Comparable;
 
 use crate::{
-	pattern::{ApplyPartialSubstitution, ApplySubstitution, PatternSubstitution, ResourceOrVar},
+	pattern::{
+		ApplyPartialSubstitution, ApplySubstitution, MapVariables, PatternSubstitution,
+		ResourceOrVar, TryMapResources,
+	},
 	Signed,
 };
 
@@ -65,7 +71,7 @@ where
 	type Output = Value<'e, T>;
 
 	/// Evaluates the expression.
-	fn eval(&'e self, vocabulary: &V, interpretation: &I) -> Result<Value<T>, Error> {
+	fn eval(&'e self, vocabulary: &V, interpretation: &I) -> Result<Value<'e, T>, Error> {
 		match self {
 			Self::Resource(r) => Ok(Value::Resource(Cow::Borrowed(r))),
 			Self::Literal(l) => Ok(l.eval()),
@@ -171,6 +177,31 @@ impl<R, T: ApplySubstitution<R>, F: Clone> ApplySubstitution<R> for Expression<T
 	}
 }
 
+impl<T, U, A: TryMapResources<T, U>, F> TryMapResources<T, U> for Expression<A, F> {
+	type Output = Expression<A::Output, F>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		match self {
+			Self::Resource(r) => Ok(Expression::Resource(r.try_map_resources(f)?)),
+			Self::Literal(l) => Ok(Expression::Literal(l)),
+			Self::Call(g, args) => Ok(Expression::Call(g, args.try_map_resources(f)?)),
+		}
+	}
+}
+
+impl<A: MapVariables, F> MapVariables for Expression<A, F> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		match self {
+			Self::Resource(r) => r.map_variables(f),
+			Self::Literal(_) => (),
+			Self::Call(_, args) => args.map_variables(f),
+		}
+	}
+}
+
 impl<T, F> Expression<ResourceOrVar<T>, F> {
 	pub fn visit_variables(&self, mut f: impl FnMut(usize)) {
 		self.visit_variables_ref_mut(&mut f)
@@ -198,7 +229,7 @@ pub trait Function<V, I: Interpretation> {
 		vocabulary: &V,
 		interpretation: &I,
 		args: &[Value<I::Resource>],
-	) -> Result<Value<I::Resource>, Error>
+	) -> Result<Value<'_, I::Resource>, Error>
 	where
 		I::Resource: Clone;
 }
@@ -215,8 +246,60 @@ pub enum BuiltInFunction {
 	/// Comparison.
 	Compare(ComparisonOperator),
 
+	/// Arithmetic operation.
+	Arithmetic(ArithmeticOperator),
+
 	/// Regular expression matching.
 	Matches,
+
+	/// Language tag of a literal, or the empty string if the literal has no
+	/// language tag.
+	Lang,
+
+	/// Checks that a language tag matches a language range, following the
+	/// basic filtering scheme of [RFC 4647][rfc4647]. The `*` range matches
+	/// any non-empty tag.
+	///
+	/// [rfc4647]: <https://www.rfc-editor.org/rfc/rfc4647#section-3.3.1>
+	LangMatches,
+
+	/// Builds a language-tagged string from a lexical value and a language
+	/// tag.
+	StrLang,
+
+	/// Concatenation of the lexical values of the given literals.
+	Concat,
+
+	/// Builds an IRI from a string.
+	Iri,
+
+	/// Lexical value of a literal, or string of an IRI.
+	Str,
+
+	/// Checks that the argument is an IRI.
+	IsIri,
+
+	/// Checks that the argument is a blank node.
+	IsBlank,
+
+	/// Checks that the argument is a literal.
+	IsLiteral,
+
+	/// Datatype IRI of a literal.
+	Datatype,
+
+	/// Name-based UUID (as a `urn:uuid:` IRI) derived from the lexical value
+	/// of a literal or the string of an IRI. The same input always gives the
+	/// same UUID.
+	Uuid,
+
+	/// Hexadecimal SHA-256 digest of the lexical value of a literal or the
+	/// string of an IRI.
+	Sha256,
+
+	/// Hexadecimal BLAKE3 digest of the lexical value of a literal or the
+	/// string of an IRI.
+	Blake3,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -230,6 +313,12 @@ pub enum Error {
 	#[error("ambiguous literal value")]
 	AmbiguousLiteral,
 
+	#[error("division by zero")]
+	DivisionByZero,
+
+	#[error("invalid IRI `{0}`")]
+	InvalidIri(String),
+
 	#[error("expected {0}, found {1}")]
 	Unexpected(Expected, UnexpectedTerm),
 }
@@ -258,7 +347,7 @@ where
 		vocabulary: &V,
 		interpretation: &I,
 		args: &[Value<I::Resource>],
-	) -> Result<Value<I::Resource>, Error>
+	) -> Result<Value<'_, I::Resource>, Error>
 	where
 		I::Resource: Clone,
 	{
@@ -297,21 +386,213 @@ where
 
 				Ok(Value::Boolean(xsd_types::Boolean(true)))
 			}
+			Self::Arithmetic(op) => match args {
+				[] => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: 0,
+				}),
+				[first, rest @ ..] => {
+					let mut result = first
+						.require_numeric(vocabulary, interpretation)?
+						.into_owned();
+
+					for a in rest {
+						result =
+							result.apply(*op, a.require_numeric(vocabulary, interpretation)?)?
+					}
+
+					Ok(result.into_value())
+				}
+			},
 			Self::Matches => match args {
 				[regex, haystack] => {
 					let regex = regex.require_regex(vocabulary, interpretation)?;
 					let haystack = haystack.require_any_literal(vocabulary, interpretation)?;
-					Ok(Value::Boolean(xsd_types::Boolean(regex.is_match(haystack))))
+					Ok(Value::Boolean(xsd_types::Boolean(
+						regex.is_match(&haystack),
+					)))
 				}
 				_ => Err(Error::InvalidArgumentCount {
 					required: 2,
 					found: args.len(),
 				}),
 			},
+			Self::Lang => match args {
+				[literal] => {
+					let tag = literal.require_lang(vocabulary, interpretation)?;
+					Ok(Value::String(Cow::Owned(
+						tag.map(|t| t.as_str().to_owned()).unwrap_or_default(),
+					)))
+				}
+				_ => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: args.len(),
+				}),
+			},
+			Self::LangMatches => match args {
+				[tag, range] => {
+					let tag = tag.require_any_literal(vocabulary, interpretation)?;
+					let range = range.require_any_literal(vocabulary, interpretation)?;
+					Ok(Value::Boolean(xsd_types::Boolean(lang_matches(
+						&tag, &range,
+					))))
+				}
+				_ => Err(Error::InvalidArgumentCount {
+					required: 2,
+					found: args.len(),
+				}),
+			},
+			Self::StrLang => match args {
+				[value, tag] => {
+					let value = value.require_any_literal(vocabulary, interpretation)?;
+					let tag = tag.require_any_literal(vocabulary, interpretation)?;
+					let tag =
+						LangTagBuf::new(tag.into_owned()).map_err(|_| Error::InvalidLiteral)?;
+					Ok(Value::LangString(
+						Cow::Owned(value.into_owned()),
+						Cow::Owned(tag),
+					))
+				}
+				_ => Err(Error::InvalidArgumentCount {
+					required: 2,
+					found: args.len(),
+				}),
+			},
+			Self::Concat => {
+				let mut result = String::new();
+
+				for a in args {
+					result.push_str(&a.require_any_literal(vocabulary, interpretation)?)
+				}
+
+				Ok(Value::String(Cow::Owned(result)))
+			}
+			Self::Iri => match args {
+				[value] => match value.as_iri(vocabulary, interpretation) {
+					Some(iri) => Ok(Value::Iri(Cow::Owned(iri.to_owned()))),
+					None => {
+						let value = value.require_any_literal(vocabulary, interpretation)?;
+						IriBuf::new(value.into_owned())
+							.map(|iri| Value::Iri(Cow::Owned(iri)))
+							.map_err(|e| Error::InvalidIri(e.0))
+					}
+				},
+				_ => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: args.len(),
+				}),
+			},
+			Self::Str => match args {
+				[value] => Ok(Value::String(Cow::Owned(
+					string_of(vocabulary, interpretation, value)?.into_owned(),
+				))),
+				_ => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: args.len(),
+				}),
+			},
+			Self::IsIri => match args {
+				[value] => Ok(Value::Boolean(xsd_types::Boolean(
+					value.as_iri(vocabulary, interpretation).is_some(),
+				))),
+				_ => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: args.len(),
+				}),
+			},
+			Self::IsBlank => match args {
+				[value] => Ok(Value::Boolean(xsd_types::Boolean(
+					value.is_blank(interpretation),
+				))),
+				_ => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: args.len(),
+				}),
+			},
+			Self::IsLiteral => match args {
+				[value] => Ok(Value::Boolean(xsd_types::Boolean(
+					value.is_literal(interpretation),
+				))),
+				_ => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: args.len(),
+				}),
+			},
+			Self::Uuid => match args {
+				[value] => {
+					let value = string_of(vocabulary, interpretation, value)?;
+					Ok(Value::Iri(Cow::Owned(hash::uuid(&value))))
+				}
+				_ => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: args.len(),
+				}),
+			},
+			Self::Sha256 => match args {
+				[value] => {
+					let value = string_of(vocabulary, interpretation, value)?;
+					Ok(Value::String(Cow::Owned(hash::sha256_hex(&value))))
+				}
+				_ => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: args.len(),
+				}),
+			},
+			Self::Blake3 => match args {
+				[value] => {
+					let value = string_of(vocabulary, interpretation, value)?;
+					Ok(Value::String(Cow::Owned(hash::blake3_hex(&value))))
+				}
+				_ => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: args.len(),
+				}),
+			},
+			Self::Datatype => match args {
+				[value] => {
+					let datatype = value.require_datatype(vocabulary, interpretation)?;
+					Ok(Value::Iri(Cow::Owned(datatype.to_owned())))
+				}
+				_ => Err(Error::InvalidArgumentCount {
+					required: 1,
+					found: args.len(),
+				}),
+			},
 		}
 	}
 }
 
+/// Returns the string of the given IRI, or the lexical value of the given
+/// literal.
+fn string_of<'a, V, I>(
+	vocabulary: &'a V,
+	interpretation: &'a I,
+	value: &'a Value<I::Resource>,
+) -> Result<Cow<'a, str>, Error>
+where
+	V: Vocabulary,
+	V::Iri: PartialEq,
+	I: ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+	I::Resource: Clone,
+{
+	match value.as_iri(vocabulary, interpretation) {
+		Some(iri) => Ok(Cow::Borrowed(iri.as_str())),
+		None => value.require_any_literal(vocabulary, interpretation),
+	}
+}
+
+/// Checks that the given language tag matches the given language range.
+fn lang_matches(tag: &str, range: &str) -> bool {
+	if range == "*" {
+		return !tag.is_empty();
+	}
+
+	tag.len() >= range.len()
+		&& tag.is_char_boundary(range.len())
+		&& tag[..range.len()].eq_ignore_ascii_case(range)
+		&& matches!(tag.as_bytes().get(range.len()), None | Some(b'-'))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ComparisonOperator {
 	/// Equality.
@@ -347,9 +628,29 @@ impl ComparisonOperator {
 	}
 }
 
+/// Arithmetic operator.
+///
+/// Operands are promoted to their least common numeric type (see
+/// [`Numeric`](value::Numeric)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ArithmeticOperator {
+	/// Addition.
+	Add,
+
+	/// Subtraction.
+	Sub,
+
+	/// Multiplication.
+	Mul,
+
+	/// Division.
+	Div,
+}
+
 #[derive(Debug)]
 pub enum Expected {
 	AnyLiteral,
+	Numeric,
 	Literal(IriBuf),
 }
 
@@ -357,6 +658,7 @@ impl fmt::Display for Expected {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
 			Self::AnyLiteral => write!(f, "literal"),
+			Self::Numeric => write!(f, "numeric literal"),
 			Self::Literal(type_) => write!(f, "literal of type <{type_}>"),
 		}
 	}
@@ -1,18 +1,27 @@
 use std::{borrow::Cow, cmp::Ordering};
 
-use rdf_types::{interpretation::ReverseLiteralInterpretation, LexicalLiteralTypeRef, Vocabulary};
+use iref::Iri;
+use langtag::LangTag;
+use rdf_types::{
+	interpretation::{ReverseIriInterpretation, ReverseLiteralInterpretation},
+	LexicalLiteralTypeRef, Vocabulary,
+};
 use replace_with::replace_with_or_abort_and_return;
-use xsd_types::{ParseXsd, XSD_BOOLEAN, XSD_STRING};
+use xsd_types::{ParseXsd, XSD_BOOLEAN, XSD_DOUBLE, XSD_FLOAT, XSD_STRING};
 
-use super::{regex, Error, Regex, Value};
+use super::{regex, Error, Numeric, Regex, Value};
 
 /// Comparable value.
 #[derive(Debug)]
 pub enum Comparable<'a, R> {
 	Any(&'a R),
+	Iri(&'a Iri),
 	Boolean(xsd_types::Boolean),
 	Decimal(Cow<'a, xsd_types::Decimal>),
+	Float(xsd_types::Float),
+	Double(xsd_types::Double),
 	String(&'a str),
+	LangString(&'a str, &'a LangTag),
 	Regex(Cow<'a, Regex>),
 }
 
@@ -25,13 +34,18 @@ impl<'a, R> Comparable<'a, R> {
 	where
 		R: Clone,
 		V: Vocabulary,
-		I: ReverseLiteralInterpretation<Resource = R, Literal = V::Literal>,
+		I: ReverseIriInterpretation<Resource = R, Iri = V::Iri>
+			+ ReverseLiteralInterpretation<Literal = V::Literal>,
 	{
 		match value {
 			Value::Resource(r) => Self::from_resource(vocabulary, interpretation, r),
+			Value::Iri(iri) => Ok(Self::Iri(iri)),
 			Value::Boolean(b) => Ok(Self::Boolean(*b)),
 			Value::Decimal(d) => Ok(Self::Decimal(Cow::Borrowed(d))),
+			Value::Float(f) => Ok(Self::Float(*f)),
+			Value::Double(d) => Ok(Self::Double(*d)),
 			Value::String(s) => Ok(Self::String(s)),
+			Value::LangString(s, tag) => Ok(Self::LangString(s, tag)),
 			Value::Regex(e) => Ok(Self::Regex(Cow::Borrowed(e))),
 		}
 	}
@@ -43,37 +57,62 @@ impl<'a, R> Comparable<'a, R> {
 	) -> Result<Self, Error>
 	where
 		V: Vocabulary,
-		I: ReverseLiteralInterpretation<Resource = R, Literal = V::Literal>,
+		I: ReverseIriInterpretation<Resource = R, Iri = V::Iri>
+			+ ReverseLiteralInterpretation<Literal = V::Literal>,
 	{
 		let mut result = Self::Any(resource);
 
 		for l in interpretation.literals_of(resource) {
 			if let Some(l) = vocabulary.literal(l) {
-				if let LexicalLiteralTypeRef::Any(iri) =
-					l.type_.as_lexical_type_ref_with(vocabulary)
-				{
-					if iri == XSD_BOOLEAN {
-						result
-							.refine(Comparable::Boolean(xsd_types::Boolean::parse_xsd(l.value)?))?
-					}
+				match l.type_.as_lexical_type_ref_with(vocabulary) {
+					LexicalLiteralTypeRef::Any(iri) => {
+						if iri == XSD_BOOLEAN {
+							result.refine(Comparable::Boolean(xsd_types::Boolean::parse_xsd(
+								l.value,
+							)?))?
+						}
 
-					if xsd_types::DecimalDatatype::from_iri(iri).is_some() {
-						result.refine(Comparable::Decimal(Cow::Owned(
-							xsd_types::Decimal::parse_xsd(l.value)?,
-						)))?
-					}
+						if xsd_types::DecimalDatatype::from_iri(iri).is_some() {
+							result.refine(Comparable::Decimal(Cow::Owned(
+								xsd_types::Decimal::parse_xsd(l.value)?,
+							)))?
+						}
 
-					if iri == XSD_STRING {
-						result.refine(Comparable::String(l.value))?;
-					}
+						if iri == XSD_FLOAT {
+							result
+								.refine(Comparable::Float(xsd_types::Float::parse_xsd(l.value)?))?
+						}
 
-					if iri == regex::TYPE_IRI {
-						result.refine(Comparable::Regex(Cow::Owned(Regex::new(l.value)?)))?
+						if iri == XSD_DOUBLE {
+							result.refine(Comparable::Double(xsd_types::Double::parse_xsd(
+								l.value,
+							)?))?
+						}
+
+						if iri == XSD_STRING {
+							result.refine(Comparable::String(l.value))?;
+						}
+
+						if iri == regex::TYPE_IRI {
+							result.refine(Comparable::Regex(Cow::Owned(Regex::new(l.value)?)))?
+						}
+					}
+					LexicalLiteralTypeRef::LangString(tag) => {
+						result.refine(Comparable::LangString(l.value, tag))?
 					}
 				}
 			}
 		}
 
+		if let Self::Any(_) = result {
+			if let Some(iri) = interpretation
+				.iris_of(resource)
+				.find_map(|i| vocabulary.iri(i))
+			{
+				result = Self::Iri(iri)
+			}
+		}
+
 		Ok(result)
 	}
 
@@ -82,31 +121,64 @@ impl<'a, R> Comparable<'a, R> {
 			(Self::Any(_), b) => (Ok(()), b),
 			(Self::Boolean(a), Self::Boolean(b)) if a == b => (Ok(()), Self::Boolean(b)),
 			(Self::Decimal(a), Self::Decimal(b)) if a == b => (Ok(()), Self::Decimal(b)),
+			(Self::Float(a), Self::Float(b)) if a == b => (Ok(()), Self::Float(b)),
+			(Self::Double(a), Self::Double(b)) if a == b => (Ok(()), Self::Double(b)),
 			(Self::String(a), Self::String(b)) if a == b => (Ok(()), Self::String(b)),
+			(Self::LangString(a, t), Self::LangString(b, u)) if a == b && t == u => {
+				(Ok(()), Self::LangString(b, u))
+			}
 			(Self::Regex(a), Self::Regex(b)) if a == b => (Ok(()), Self::Regex(b)),
 			(this, _) => (Err(Error::AmbiguousLiteral), this),
 		})
 	}
 
-	pub fn as_opaque(&self) -> Comparable<()> {
+	pub fn as_opaque(&self) -> Comparable<'_, ()> {
 		match self {
 			Self::Any(_) => Comparable::Any(&()),
+			Self::Iri(iri) => Comparable::Iri(iri),
 			Self::Boolean(b) => Comparable::Boolean(*b),
 			Self::Decimal(d) => Comparable::Decimal(Cow::Borrowed(d)),
+			Self::Float(f) => Comparable::Float(*f),
+			Self::Double(d) => Comparable::Double(*d),
 			Self::String(s) => Comparable::String(s),
+			Self::LangString(s, tag) => Comparable::LangString(s, tag),
 			Self::Regex(r) => Comparable::Regex(Cow::Borrowed(r)),
 		}
 	}
+
+	/// Returns the numeric value, if any.
+	pub fn as_numeric(&self) -> Option<Numeric<'_>> {
+		match self {
+			Self::Decimal(d) => Some(Numeric::Decimal(Cow::Borrowed(d))),
+			Self::Float(f) => Some(Numeric::Float(*f)),
+			Self::Double(d) => Some(Numeric::Double(*d)),
+			_ => None,
+		}
+	}
+
+	/// Turns this value into a numeric value, if possible.
+	pub fn into_numeric(self) -> Option<Numeric<'a>> {
+		match self {
+			Self::Decimal(d) => Some(Numeric::Decimal(d)),
+			Self::Float(f) => Some(Numeric::Float(f)),
+			Self::Double(d) => Some(Numeric::Double(d)),
+			_ => None,
+		}
+	}
 }
 
 impl<'a, R: PartialEq> PartialEq for Comparable<'a, R> {
 	fn eq(&self, other: &Self) -> bool {
 		match (self, other) {
 			(Self::Any(a), Self::Any(b)) => a == b,
+			(Self::Iri(a), Self::Iri(b)) => a == b,
 			(Self::Boolean(a), Self::Boolean(b)) => a == b,
-			(Self::Decimal(a), Self::Decimal(b)) => a == b,
 			(Self::String(a), Self::String(b)) => a == b,
-			_ => false,
+			(Self::LangString(a, t), Self::LangString(b, u)) => a == b && t == u,
+			(a, b) => match (a.as_numeric(), b.as_numeric()) {
+				(Some(a), Some(b)) => a == b,
+				_ => false,
+			},
 		}
 	}
 }
@@ -115,10 +187,11 @@ impl<'a, R: PartialEq> PartialOrd for Comparable<'a, R> {
 	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
 		match (self, other) {
 			(Self::Any(a), Self::Any(b)) if a == b => Some(Ordering::Equal),
+			(Self::Iri(a), Self::Iri(b)) if a == b => Some(Ordering::Equal),
 			(Self::Boolean(a), Self::Boolean(b)) if a == b => Some(Ordering::Equal),
-			(Self::Decimal(a), Self::Decimal(b)) => a.partial_cmp(b),
 			(Self::String(a), Self::String(b)) => a.partial_cmp(b),
-			_ => None,
+			(Self::LangString(a, t), Self::LangString(b, u)) if t == u => a.partial_cmp(b),
+			(a, b) => a.as_numeric()?.partial_cmp(&b.as_numeric()?),
 		}
 	}
 }
@@ -3,7 +3,7 @@ use rdf_types::{
 	interpretation::{LiteralInterpretationMut, ReverseTermInterpretation},
 	LexicalLiteralTypeRef, LiteralType, Vocabulary, VocabularyMut,
 };
-use xsd_types::{ParseXsd, XSD_BOOLEAN, XSD_DECIMAL, XSD_STRING};
+use xsd_types::{ParseXsd, XSD_BOOLEAN, XSD_DECIMAL, XSD_DOUBLE, XSD_FLOAT, XSD_STRING};
 
 use crate::expression::{as_unexpected, Expected};
 
@@ -106,6 +106,44 @@ impl ToLiteralValue for xsd_types::Decimal {
 	}
 }
 
+impl LiteralValue for xsd_types::Float {
+	const TYPE: &'static Iri = XSD_FLOAT;
+
+	fn parse_literal(value: &str, type_: LexicalLiteralTypeRef) -> Result<Option<Self>, Error> {
+		match type_ {
+			LexicalLiteralTypeRef::Any(iri) if iri == XSD_FLOAT => {
+				Ok(Some(xsd_types::Float::parse_xsd(value)?))
+			}
+			_ => Ok(None),
+		}
+	}
+}
+
+impl ToLiteralValue for xsd_types::Float {
+	fn preferred_type(&self) -> &Iri {
+		Self::TYPE
+	}
+}
+
+impl LiteralValue for xsd_types::Double {
+	const TYPE: &'static Iri = XSD_DOUBLE;
+
+	fn parse_literal(value: &str, type_: LexicalLiteralTypeRef) -> Result<Option<Self>, Error> {
+		match type_ {
+			LexicalLiteralTypeRef::Any(iri) if iri == XSD_DOUBLE => {
+				Ok(Some(xsd_types::Double::parse_xsd(value)?))
+			}
+			_ => Ok(None),
+		}
+	}
+}
+
+impl ToLiteralValue for xsd_types::Double {
+	fn preferred_type(&self) -> &Iri {
+		Self::TYPE
+	}
+}
+
 impl LiteralValue for String {
 	const TYPE: &'static Iri = XSD_STRING;
 
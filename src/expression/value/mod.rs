@@ -1,10 +1,13 @@
 use std::borrow::Cow;
 
+use iref::Iri;
+use langtag::LangTag;
 use rdf_types::{
-	interpretation::{LiteralInterpretationMut, ReverseTermInterpretation},
-	Interpretation, LiteralType, Term, Vocabulary, VocabularyMut,
+	interpretation::{IriInterpretationMut, LiteralInterpretationMut, ReverseTermInterpretation},
+	Interpretation, LexicalLiteralTypeRef, LiteralType, Term, Vocabulary, VocabularyMut,
+	RDF_LANG_STRING,
 };
-use xsd_types::{XSD_BOOLEAN, XSD_DECIMAL, XSD_STRING};
+use xsd_types::{XSD_BOOLEAN, XSD_DECIMAL, XSD_DOUBLE, XSD_FLOAT, XSD_STRING};
 
 use super::{as_unexpected, Error, Expected, Instantiate, UnexpectedTerm};
 
@@ -17,20 +20,35 @@ pub use literal::*;
 mod comparable;
 pub use comparable::*;
 
+mod numeric;
+pub use numeric::*;
+
 /// Value.
 pub enum Value<'e, R: Clone> {
 	/// Any resource.
 	Resource(Cow<'e, R>),
 
+	/// IRI.
+	Iri(Cow<'e, Iri>),
+
 	/// Boolean value.
 	Boolean(xsd_types::Boolean),
 
 	/// Decimal value.
 	Decimal(Cow<'e, xsd_types::Decimal>),
 
+	/// Single precision floating point value.
+	Float(xsd_types::Float),
+
+	/// Double precision floating point value.
+	Double(xsd_types::Double),
+
 	/// Text string.
 	String(Cow<'e, str>),
 
+	/// Language-tagged string.
+	LangString(Cow<'e, str>, Cow<'e, LangTag>),
+
 	/// Regular expression.
 	Regex(Cow<'e, Regex>),
 }
@@ -55,6 +73,10 @@ impl<'e, R: Clone> Value<'e, R> {
 			Self::Resource(resource) => {
 				xsd_types::Boolean::from_resource(vocabulary, interpretation, resource)
 			}
+			Self::Iri(iri) => Err(Error::Unexpected(
+				Expected::Literal(XSD_BOOLEAN.to_owned()),
+				UnexpectedTerm::Term(Term::iri(iri.as_ref().to_owned())),
+			)),
 			Self::Boolean(b) => Ok(*b),
 			Self::Decimal(value) => Err(Error::Unexpected(
 				Expected::Literal(XSD_BOOLEAN.to_owned()),
@@ -63,6 +85,20 @@ impl<'e, R: Clone> Value<'e, R> {
 					LiteralType::Any(XSD_DECIMAL.to_owned()),
 				))),
 			)),
+			Self::Float(value) => Err(Error::Unexpected(
+				Expected::Literal(XSD_BOOLEAN.to_owned()),
+				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
+					value.to_string(),
+					LiteralType::Any(XSD_FLOAT.to_owned()),
+				))),
+			)),
+			Self::Double(value) => Err(Error::Unexpected(
+				Expected::Literal(XSD_BOOLEAN.to_owned()),
+				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
+					value.to_string(),
+					LiteralType::Any(XSD_DOUBLE.to_owned()),
+				))),
+			)),
 			Self::String(value) => Err(Error::Unexpected(
 				Expected::Literal(XSD_BOOLEAN.to_owned()),
 				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
@@ -70,6 +106,13 @@ impl<'e, R: Clone> Value<'e, R> {
 					LiteralType::Any(XSD_STRING.to_owned()),
 				))),
 			)),
+			Self::LangString(value, tag) => Err(Error::Unexpected(
+				Expected::Literal(XSD_BOOLEAN.to_owned()),
+				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
+					value.as_ref().to_owned(),
+					LiteralType::LangString(tag.as_ref().to_owned()),
+				))),
+			)),
 			Self::Regex(value) => Err(Error::Unexpected(
 				Expected::Literal(XSD_BOOLEAN.to_owned()),
 				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
@@ -80,11 +123,213 @@ impl<'e, R: Clone> Value<'e, R> {
 		}
 	}
 
+	/// Returns the numeric value of this value.
+	///
+	/// Literals of type `xsd:decimal` (and derived types such as
+	/// `xsd:integer`), `xsd:float` and `xsd:double` are numeric.
+	pub fn require_numeric<'a, V, I>(
+		&'a self,
+		vocabulary: &'a V,
+		interpretation: &'a I,
+	) -> Result<Numeric<'a>, Error>
+	where
+		V: Vocabulary,
+		V::Iri: PartialEq,
+		I: ReverseTermInterpretation<
+			Resource = R,
+			Iri = V::Iri,
+			BlankId = V::BlankId,
+			Literal = V::Literal,
+		>,
+	{
+		match self {
+			Self::Resource(resource) => {
+				Comparable::from_resource(vocabulary, interpretation, resource)?
+					.into_numeric()
+					.ok_or_else(|| {
+						Error::Unexpected(
+							Expected::Numeric,
+							as_unexpected(vocabulary, interpretation, resource),
+						)
+					})
+			}
+			Self::Decimal(d) => Ok(Numeric::Decimal(Cow::Borrowed(d))),
+			Self::Float(f) => Ok(Numeric::Float(*f)),
+			Self::Double(d) => Ok(Numeric::Double(*d)),
+			Self::Iri(iri) => Err(Error::Unexpected(
+				Expected::Numeric,
+				UnexpectedTerm::Term(Term::iri(iri.as_ref().to_owned())),
+			)),
+			_ => Err(Error::Unexpected(
+				Expected::Numeric,
+				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
+					self.require_any_literal(vocabulary, interpretation)?
+						.into_owned(),
+					match self {
+						Self::LangString(_, tag) => {
+							LiteralType::LangString(tag.as_ref().to_owned())
+						}
+						_ => LiteralType::Any(self.literal_type().unwrap().to_owned()),
+					},
+				))),
+			)),
+		}
+	}
+
+	/// Returns the language tag of this value, if it is a language-tagged
+	/// string.
+	///
+	/// Fails if the value is not a literal.
+	pub fn require_lang<'a, V, I>(
+		&'a self,
+		vocabulary: &'a V,
+		interpretation: &'a I,
+	) -> Result<Option<&'a LangTag>, Error>
+	where
+		V: Vocabulary,
+		V::Iri: PartialEq,
+		I: ReverseTermInterpretation<
+			Resource = R,
+			Iri = V::Iri,
+			BlankId = V::BlankId,
+			Literal = V::Literal,
+		>,
+	{
+		match self {
+			Self::Resource(resource) => {
+				match Comparable::from_resource(vocabulary, interpretation, resource)? {
+					Comparable::LangString(_, tag) => Ok(Some(tag)),
+					_ => self
+						.require_any_literal(vocabulary, interpretation)
+						.map(|_| None),
+				}
+			}
+			Self::Iri(iri) => Err(Error::Unexpected(
+				Expected::AnyLiteral,
+				UnexpectedTerm::Term(Term::iri(iri.as_ref().to_owned())),
+			)),
+			Self::LangString(_, tag) => Ok(Some(tag)),
+			_ => Ok(None),
+		}
+	}
+
+	/// Returns the datatype of this value, if it is a typed literal.
+	fn literal_type(&self) -> Option<&'static iref::Iri> {
+		match self {
+			Self::Resource(_) | Self::Iri(_) | Self::LangString(_, _) => None,
+			Self::Boolean(_) => Some(XSD_BOOLEAN),
+			Self::Decimal(_) => Some(XSD_DECIMAL),
+			Self::Float(_) => Some(XSD_FLOAT),
+			Self::Double(_) => Some(XSD_DOUBLE),
+			Self::String(_) => Some(XSD_STRING),
+			Self::Regex(_) => Some(regex::TYPE_IRI),
+		}
+	}
+
+	/// Returns the IRI of this value, if it is an IRI or a resource
+	/// identified by an IRI.
+	pub fn as_iri<'a, V, I>(&'a self, vocabulary: &'a V, interpretation: &'a I) -> Option<&'a Iri>
+	where
+		V: Vocabulary,
+		I: ReverseTermInterpretation<
+			Resource = R,
+			Iri = V::Iri,
+			BlankId = V::BlankId,
+			Literal = V::Literal,
+		>,
+	{
+		match self {
+			Self::Resource(resource) => interpretation
+				.iris_of(resource)
+				.find_map(|i| vocabulary.iri(i)),
+			Self::Iri(iri) => Some(iri),
+			_ => None,
+		}
+	}
+
+	/// Checks that this value is a resource identified by a blank node
+	/// identifier.
+	pub fn is_blank<I>(&self, interpretation: &I) -> bool
+	where
+		I: ReverseTermInterpretation<Resource = R>,
+	{
+		match self {
+			Self::Resource(resource) => interpretation.blank_ids_of(resource).next().is_some(),
+			_ => false,
+		}
+	}
+
+	/// Checks that this value is a literal, or a resource interpreting a
+	/// literal.
+	pub fn is_literal<I>(&self, interpretation: &I) -> bool
+	where
+		I: ReverseTermInterpretation<Resource = R>,
+	{
+		match self {
+			Self::Resource(resource) => interpretation.literals_of(resource).next().is_some(),
+			Self::Iri(_) => false,
+			_ => true,
+		}
+	}
+
+	/// Returns the datatype IRI of this literal value.
+	///
+	/// The datatype of language-tagged strings is `rdf:langString`.
+	pub fn require_datatype<'a, V, I>(
+		&'a self,
+		vocabulary: &'a V,
+		interpretation: &'a I,
+	) -> Result<&'a Iri, Error>
+	where
+		V: Vocabulary,
+		V::Iri: PartialEq,
+		I: ReverseTermInterpretation<
+			Resource = R,
+			Iri = V::Iri,
+			BlankId = V::BlankId,
+			Literal = V::Literal,
+		>,
+	{
+		match self {
+			Self::Resource(resource) => {
+				let mut result: Option<&'a Iri> = None;
+
+				for l in interpretation.literals_of(resource) {
+					if let Some(literal) = vocabulary.literal(l) {
+						let type_ = match literal.type_.as_lexical_type_ref_with(vocabulary) {
+							LexicalLiteralTypeRef::Any(iri) => iri,
+							LexicalLiteralTypeRef::LangString(_) => RDF_LANG_STRING,
+						};
+
+						if let Some(other) = result.replace(type_) {
+							if other != type_ {
+								return Err(Error::AmbiguousLiteral);
+							}
+						}
+					}
+				}
+
+				result.ok_or_else(|| {
+					Error::Unexpected(
+						Expected::AnyLiteral,
+						as_unexpected(vocabulary, interpretation, resource),
+					)
+				})
+			}
+			Self::Iri(iri) => Err(Error::Unexpected(
+				Expected::AnyLiteral,
+				UnexpectedTerm::Term(Term::iri(iri.as_ref().to_owned())),
+			)),
+			Self::LangString(_, _) => Ok(RDF_LANG_STRING),
+			_ => Ok(self.literal_type().unwrap()),
+		}
+	}
+
 	pub fn require_any_literal<'a, V, I>(
 		&'a self,
 		vocabulary: &'a V,
 		interpretation: &'a I,
-	) -> Result<&str, Error>
+	) -> Result<Cow<'a, str>, Error>
 	where
 		V: Vocabulary,
 		V::Iri: PartialEq,
@@ -110,18 +355,24 @@ impl<'e, R: Clone> Value<'e, R> {
 				}
 
 				match value {
-					Some(value) => Ok(value),
+					Some(value) => Ok(Cow::Borrowed(value)),
 					None => Err(Error::Unexpected(
 						Expected::AnyLiteral,
 						as_unexpected(vocabulary, interpretation, resource),
 					)),
 				}
 			}
-			Self::Boolean(xsd_types::Boolean(true)) => Ok("true"),
-			Self::Boolean(xsd_types::Boolean(false)) => Ok("false"),
-			Self::Decimal(value) => Ok(value.lexical_representation().as_str()),
-			Self::String(s) => Ok(s),
-			Self::Regex(value) => Ok(value.as_str()),
+			Self::Iri(iri) => Err(Error::Unexpected(
+				Expected::AnyLiteral,
+				UnexpectedTerm::Term(Term::iri(iri.as_ref().to_owned())),
+			)),
+			Self::Boolean(xsd_types::Boolean(true)) => Ok(Cow::Borrowed("true")),
+			Self::Boolean(xsd_types::Boolean(false)) => Ok(Cow::Borrowed("false")),
+			Self::Decimal(value) => Ok(Cow::Borrowed(value.lexical_representation().as_str())),
+			Self::Float(value) => Ok(Cow::Owned(value.to_string())),
+			Self::Double(value) => Ok(Cow::Owned(value.to_string())),
+			Self::String(s) | Self::LangString(s, _) => Ok(Cow::Borrowed(s)),
+			Self::Regex(value) => Ok(Cow::Borrowed(value.as_str())),
 		}
 	}
 
@@ -129,7 +380,7 @@ impl<'e, R: Clone> Value<'e, R> {
 		&'a self,
 		vocabulary: &'a V,
 		interpretation: &'a I,
-	) -> Result<Cow<Regex>, Error>
+	) -> Result<Cow<'a, Regex>, Error>
 	where
 		V: Vocabulary,
 		V::Iri: PartialEq,
@@ -144,6 +395,10 @@ impl<'e, R: Clone> Value<'e, R> {
 			Self::Resource(resource) => {
 				Regex::from_resource(vocabulary, interpretation, resource).map(Cow::Owned)
 			}
+			Self::Iri(iri) => Err(Error::Unexpected(
+				Expected::Literal(regex::TYPE_IRI.to_owned()),
+				UnexpectedTerm::Term(Term::iri(iri.as_ref().to_owned())),
+			)),
 			Self::Boolean(value) => Err(Error::Unexpected(
 				Expected::Literal(regex::TYPE_IRI.to_owned()),
 				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
@@ -158,6 +413,20 @@ impl<'e, R: Clone> Value<'e, R> {
 					LiteralType::Any(XSD_DECIMAL.to_owned()),
 				))),
 			)),
+			Self::Float(value) => Err(Error::Unexpected(
+				Expected::Literal(regex::TYPE_IRI.to_owned()),
+				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
+					value.to_string(),
+					LiteralType::Any(XSD_FLOAT.to_owned()),
+				))),
+			)),
+			Self::Double(value) => Err(Error::Unexpected(
+				Expected::Literal(regex::TYPE_IRI.to_owned()),
+				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
+					value.to_string(),
+					LiteralType::Any(XSD_DOUBLE.to_owned()),
+				))),
+			)),
 			Self::String(value) => Err(Error::Unexpected(
 				Expected::Literal(regex::TYPE_IRI.to_owned()),
 				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
@@ -165,6 +434,13 @@ impl<'e, R: Clone> Value<'e, R> {
 					LiteralType::Any(XSD_STRING.to_owned()),
 				))),
 			)),
+			Self::LangString(value, tag) => Err(Error::Unexpected(
+				Expected::Literal(regex::TYPE_IRI.to_owned()),
+				UnexpectedTerm::Term(Term::Literal(rdf_types::Literal::new(
+					value.as_ref().to_owned(),
+					LiteralType::LangString(tag.as_ref().to_owned()),
+				))),
+			)),
 			Self::Regex(e) => Ok(Cow::Borrowed(e)),
 		}
 	}
@@ -173,13 +449,26 @@ impl<'e, R: Clone> Value<'e, R> {
 	where
 		R: Clone,
 		V: VocabularyMut,
-		I: Interpretation<Resource = R> + LiteralInterpretationMut<V::Literal>,
+		I: Interpretation<Resource = R>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>,
 	{
 		match self {
 			Self::Resource(r) => r.into_owned(),
+			Self::Iri(iri) => interpretation.interpret_iri(vocabulary.insert(&iri)),
 			Self::Boolean(b) => b.to_resource(vocabulary, interpretation),
 			Self::Decimal(d) => d.to_resource(vocabulary, interpretation),
+			Self::Float(f) => f.to_resource(vocabulary, interpretation),
+			Self::Double(d) => d.to_resource(vocabulary, interpretation),
 			Self::String(s) => s.to_resource(vocabulary, interpretation),
+			Self::LangString(s, tag) => {
+				let literal = rdf_types::Literal::new(
+					s.into_owned(),
+					LiteralType::LangString(tag.into_owned()),
+				);
+				let l = vocabulary.insert_owned_literal(literal);
+				interpretation.interpret_literal(l)
+			}
 			Self::Regex(e) => e.to_resource(vocabulary, interpretation),
 		}
 	}
@@ -189,7 +478,9 @@ impl<'e, V, I, R> Instantiate<V, I> for Value<'e, R>
 where
 	R: Clone,
 	V: VocabularyMut,
-	I: Interpretation<Resource = R> + LiteralInterpretationMut<V::Literal>,
+	I: Interpretation<Resource = R>
+		+ IriInterpretationMut<V::Iri>
+		+ LiteralInterpretationMut<V::Literal>,
 {
 	type Instantiated = R;
 
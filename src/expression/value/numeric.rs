@@ -0,0 +1,154 @@
+use std::{borrow::Cow, cmp::Ordering};
+
+use xsd_types::{Decimal, Double, Float};
+
+use super::{Error, Value};
+use crate::expression::ArithmeticOperator;
+
+/// Numeric value.
+///
+/// Integers are represented as decimals. Operations between numerics of
+/// different types follow the SPARQL type promotion rules: decimals are
+/// promoted to floats, and floats to doubles.
+#[derive(Debug, Clone)]
+pub enum Numeric<'a> {
+	Decimal(Cow<'a, Decimal>),
+	Float(Float),
+	Double(Double),
+}
+
+impl<'a> Numeric<'a> {
+	/// Returns the rank of the numeric type in the promotion tower.
+	fn rank(&self) -> u8 {
+		match self {
+			Self::Decimal(_) => 0,
+			Self::Float(_) => 1,
+			Self::Double(_) => 2,
+		}
+	}
+
+	pub fn as_float(&self) -> Float {
+		match self {
+			Self::Decimal(d) => d.as_float().unwrap_or(if d.is_negative() {
+				Float::NEG_INFINITY
+			} else {
+				Float::INFINITY
+			}),
+			Self::Float(f) => *f,
+			Self::Double(d) => Float::new(d.into_f64() as f32),
+		}
+	}
+
+	pub fn as_double(&self) -> Double {
+		match self {
+			Self::Decimal(d) => d.as_double().unwrap_or(if d.is_negative() {
+				Double::NEG_INFINITY
+			} else {
+				Double::INFINITY
+			}),
+			Self::Float(f) => Double::new(f.into_f32() as f64),
+			Self::Double(d) => *d,
+		}
+	}
+
+	/// Promotes both values to their least common numeric type.
+	fn promote(self, other: Self) -> (Self, Self) {
+		match self.rank().max(other.rank()) {
+			0 => (self, other),
+			1 => (Self::Float(self.as_float()), Self::Float(other.as_float())),
+			_ => (
+				Self::Double(self.as_double()),
+				Self::Double(other.as_double()),
+			),
+		}
+	}
+
+	pub fn into_owned(self) -> Numeric<'static> {
+		match self {
+			Self::Decimal(d) => Numeric::Decimal(Cow::Owned(d.into_owned())),
+			Self::Float(f) => Numeric::Float(f),
+			Self::Double(d) => Numeric::Double(d),
+		}
+	}
+
+	pub fn into_value<R: Clone>(self) -> Value<'a, R> {
+		match self {
+			Self::Decimal(d) => Value::Decimal(d),
+			Self::Float(f) => Value::Float(f),
+			Self::Double(d) => Value::Double(d),
+		}
+	}
+
+	/// Applies the given arithmetic operator.
+	///
+	/// The division of two decimals is a decimal, approximated with double
+	/// precision when the exact quotient has no finite decimal
+	/// representation.
+	pub fn apply(self, op: ArithmeticOperator, other: Self) -> Result<Numeric<'static>, Error> {
+		match self.promote(other) {
+			(Self::Decimal(a), Self::Decimal(b)) => {
+				if op == ArithmeticOperator::Div && b.is_zero() {
+					return Err(Error::DivisionByZero);
+				}
+
+				let (x, y) = (a.as_big_rational(), b.as_big_rational());
+				let result = match op {
+					ArithmeticOperator::Add => x + y,
+					ArithmeticOperator::Sub => x - y,
+					ArithmeticOperator::Mul => x * y,
+					ArithmeticOperator::Div => x / y,
+				};
+
+				match Decimal::try_from(result) {
+					Ok(d) => Ok(Numeric::Decimal(Cow::Owned(d))),
+					Err(_) => {
+						// Non-terminating quotient.
+						let q = Numeric::Decimal(a).as_double() / Numeric::Decimal(b).as_double();
+						Decimal::try_from(q)
+							.map(|d| Numeric::Decimal(Cow::Owned(d)))
+							.map_err(|_| Error::InvalidLiteral)
+					}
+				}
+			}
+			(Self::Float(a), Self::Float(b)) => Ok(Numeric::Float(match op {
+				ArithmeticOperator::Add => a + b,
+				ArithmeticOperator::Sub => a - b,
+				ArithmeticOperator::Mul => a * b,
+				ArithmeticOperator::Div => a / b,
+			})),
+			(a, b) => {
+				let (a, b) = (a.as_double(), b.as_double());
+				Ok(Numeric::Double(match op {
+					ArithmeticOperator::Add => a + b,
+					ArithmeticOperator::Sub => a - b,
+					ArithmeticOperator::Mul => a * b,
+					ArithmeticOperator::Div => a / b,
+				}))
+			}
+		}
+	}
+}
+
+impl<'a> PartialEq for Numeric<'a> {
+	fn eq(&self, other: &Self) -> bool {
+		self.partial_cmp(other) == Some(Ordering::Equal)
+	}
+}
+
+impl<'a> PartialOrd for Numeric<'a> {
+	/// Compares numerics after promotion. `NaN` is not comparable, even to
+	/// itself.
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		match (self, other) {
+			(Self::Decimal(a), Self::Decimal(b)) => a.partial_cmp(b),
+			_ if self.rank().max(other.rank()) == 1 => self
+				.as_float()
+				.into_f32()
+				.partial_cmp(&other.as_float().into_f32()),
+			_ => self
+				.as_double()
+				.into_f64()
+				.partial_cmp(&other.as_double().into_f64()),
+		}
+	}
+}
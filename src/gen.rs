@@ -0,0 +1,221 @@
+//! Synthetic dataset generation.
+//!
+//! Generates datasets of arbitrary size to reproduce performance reports or
+//! stress-test rules. The generated data describes persons working for
+//! organizations, and can come with an ontology compatible with
+//! [`System::from_ontology`](crate::System::from_ontology).
+//!
+//! ```
+//! use inferdf::{gen::Generator, System};
+//!
+//! let mut dataset = Generator::new()
+//!   .with_persons(100)
+//!   .with_organizations(10)
+//!   .with_fanout(3)
+//!   .with_seed(42)
+//!   .dataset();
+//!
+//! System::new().close_with_ontology(&mut dataset).unwrap();
+//! ```
+use iref::IriBuf;
+use rand_core::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use rdf_types::{dataset::IndexedBTreeDataset, Literal, LiteralType, Term, Triple, XSD_STRING};
+
+/// Namespace of the generated classes and properties.
+pub const NAMESPACE: &str = "https://example.org/gen#";
+
+/// Namespace of the generated resources.
+pub const DATA_NAMESPACE: &str = "https://example.org/gen/";
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDFS_SUB_CLASS_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subClassOf";
+const RDFS_SUB_PROPERTY_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subPropertyOf";
+const RDFS_DOMAIN: &str = "http://www.w3.org/2000/01/rdf-schema#domain";
+const RDFS_RANGE: &str = "http://www.w3.org/2000/01/rdf-schema#range";
+const OWL_INVERSE_OF: &str = "http://www.w3.org/2002/07/owl#inverseOf";
+const OWL_TRANSITIVE_PROPERTY: &str = "http://www.w3.org/2002/07/owl#TransitiveProperty";
+const OWL_SYMMETRIC_PROPERTY: &str = "http://www.w3.org/2002/07/owl#SymmetricProperty";
+
+/// Synthetic dataset generator.
+///
+/// Each person has a name, works for one organization and knows `fanout`
+/// other persons. Each organization but the first is a sub-organization of
+/// a previous one. Links are drawn using a pseudo-random number generator
+/// seeded with `seed`, so the same parameters always give the same dataset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generator {
+	/// Number of persons.
+	pub persons: usize,
+
+	/// Number of organizations.
+	pub organizations: usize,
+
+	/// Number of persons known by each person.
+	pub fanout: usize,
+
+	/// Random number generator seed.
+	pub seed: u64,
+
+	/// Whether to include the ontology axioms in the dataset.
+	pub ontology: bool,
+}
+
+impl Default for Generator {
+	fn default() -> Self {
+		Self {
+			persons: 1000,
+			organizations: 10,
+			fanout: 5,
+			seed: 0,
+			ontology: true,
+		}
+	}
+}
+
+impl Generator {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_persons(mut self, persons: usize) -> Self {
+		self.persons = persons;
+		self
+	}
+
+	pub fn with_organizations(mut self, organizations: usize) -> Self {
+		self.organizations = organizations;
+		self
+	}
+
+	pub fn with_fanout(mut self, fanout: usize) -> Self {
+		self.fanout = fanout;
+		self
+	}
+
+	pub fn with_seed(mut self, seed: u64) -> Self {
+		self.seed = seed;
+		self
+	}
+
+	/// Sets whether to include the ontology axioms in the dataset.
+	pub fn with_ontology(mut self, ontology: bool) -> Self {
+		self.ontology = ontology;
+		self
+	}
+
+	/// Generates the triples of the dataset, ontology axioms first.
+	pub fn triples(&self) -> Vec<Triple> {
+		let mut rng = Xoshiro256PlusPlus::seed_from_u64(self.seed);
+		let mut pick = |n: usize| (rng.next_u64() % n as u64) as usize;
+
+		let mut triples = if self.ontology {
+			ontology()
+		} else {
+			Vec::new()
+		};
+		let ty = iri(RDF_TYPE);
+
+		for i in 0..self.organizations {
+			let organization = resource("organization", i);
+			triples.push(Triple(organization.clone(), ty.clone(), vocab("Company")));
+			if i > 0 {
+				triples.push(Triple(
+					organization,
+					vocab("subOrganizationOf"),
+					resource("organization", pick(i)),
+				));
+			}
+		}
+
+		for i in 0..self.persons {
+			let person = resource("person", i);
+			triples.push(Triple(person.clone(), ty.clone(), vocab("Person")));
+			triples.push(Triple(
+				person.clone(),
+				vocab("name"),
+				Term::Literal(Literal::new(
+					format!("Person {i}"),
+					LiteralType::Any(XSD_STRING.to_owned()),
+				)),
+			));
+
+			if self.organizations > 0 {
+				triples.push(Triple(
+					person.clone(),
+					vocab("worksFor"),
+					resource("organization", pick(self.organizations)),
+				));
+			}
+
+			if self.persons > 1 {
+				for _ in 0..self.fanout {
+					let mut j = pick(self.persons - 1);
+					if j >= i {
+						j += 1
+					}
+
+					triples.push(Triple(
+						person.clone(),
+						vocab("knows"),
+						resource("person", j),
+					));
+				}
+			}
+		}
+
+		triples
+	}
+
+	/// Generates the dataset.
+	///
+	/// The dataset may have fewer triples than returned by
+	/// [`Self::triples`], since duplicate links are merged.
+	pub fn dataset(&self) -> IndexedBTreeDataset {
+		self.triples()
+			.into_iter()
+			.map(|t| t.into_quad(None))
+			.collect()
+	}
+}
+
+fn iri(iri: &str) -> Term {
+	Term::iri(IriBuf::new(iri.to_owned()).unwrap())
+}
+
+fn vocab(name: &str) -> Term {
+	Term::iri(IriBuf::new(format!("{NAMESPACE}{name}")).unwrap())
+}
+
+fn resource(kind: &str, i: usize) -> Term {
+	Term::iri(IriBuf::new(format!("{DATA_NAMESPACE}{kind}/{i}")).unwrap())
+}
+
+/// Returns the ontology axioms of the generated data.
+pub fn ontology() -> Vec<Triple> {
+	let ty = iri(RDF_TYPE);
+	let sub_class_of = iri(RDFS_SUB_CLASS_OF);
+	let domain = iri(RDFS_DOMAIN);
+	let range = iri(RDFS_RANGE);
+
+	vec![
+		Triple(vocab("Employee"), sub_class_of.clone(), vocab("Person")),
+		Triple(vocab("Company"), sub_class_of, vocab("Organization")),
+		Triple(vocab("worksFor"), domain.clone(), vocab("Employee")),
+		Triple(vocab("worksFor"), range.clone(), vocab("Organization")),
+		Triple(vocab("knows"), domain, vocab("Person")),
+		Triple(vocab("knows"), range, vocab("Person")),
+		Triple(
+			vocab("worksFor"),
+			iri(RDFS_SUB_PROPERTY_OF),
+			vocab("memberOf"),
+		),
+		Triple(vocab("memberOf"), iri(OWL_INVERSE_OF), vocab("hasMember")),
+		Triple(
+			vocab("subOrganizationOf"),
+			ty.clone(),
+			iri(OWL_TRANSITIVE_PROPERTY),
+		),
+		Triple(vocab("knows"), ty, iri(OWL_SYMMETRIC_PROPERTY)),
+	]
+}
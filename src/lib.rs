@@ -59,13 +59,8 @@
 //! // we need to provide a blank id generator to generate those terms.
 //! let evaluated_deductions = deductions.eval(rdf_types::generator::Blank::new()).expect("evaluation failed");
 //!
-//! for deduction in evaluated_deductions {
-//!   use inferdf::{Signed, Sign, TripleStatement};
-//!   for statement in deduction.statements {
-//!     if let Signed(Sign::Positive, TripleStatement::Triple(triple)) = statement {
-//!       input.insert(triple); // insert the deduced triple into the graph.
-//!     }
-//!   }
+//! for triple in evaluated_deductions.into_positive_triples() {
+//!   input.insert(triple); // insert the deduced triple into the graph.
 //! }
 //!
 //! let mut expected: IndexedBTreeGraph = grdf_triples! [
@@ -76,6 +71,13 @@
 //! assert_eq!(input, expected)
 //! ```
 //!
+//! Use [`System::close`] to insert every deducible triple into a dataset
+//! instead. Deduced equality statements (`?x = ?y`) can be taken into account
+//! with [`System::close_with_identities`], which merges equal resources using
+//! [`same_as::Identities`], or with any other
+//! [`EqualityHandler`](system::EqualityHandler) through
+//! [`DeductionsInstance::apply_equalities`](system::DeductionsInstance::apply_equalities).
+//!
 //! ## Validation
 //!
 //! Use the [`Rule::validate`]/[`System::validate`] to validate a given
@@ -101,6 +103,7 @@
 //!
 //! assert!(rule.validate(&input).unwrap().is_valid())
 //! ```
+use educe::Educe;
 use rdf_types::{Term, Triple};
 use std::hash::Hash;
 
@@ -116,11 +119,14 @@ pub use sign::*;
 mod statement;
 pub use statement::*;
 
+mod trust;
+pub use trust::*;
+
 pub mod pattern;
 pub use pattern::Pattern;
 
 pub mod rule;
-pub use rule::Rule;
+pub use rule::{Rule, Severity};
 
 pub mod system;
 pub use system::System;
@@ -128,15 +134,32 @@ pub use system::System;
 mod cause;
 pub use cause::*;
 
-mod dataset;
-pub use dataset::{FallibleSignedPatternMatchingDataset, SignedPatternMatchingDataset};
+pub mod dataset;
+pub use dataset::{
+	FallibleSignedDatasetMut, FallibleSignedPatternMatchingDataset,
+	FallibleTraversableSignedDataset, SignedDatasetMut, SignedPatternMatchingDataset,
+	TraversableSignedDataset,
+};
 
 pub mod expression;
 pub use expression::Expression;
 
+pub mod debug;
+pub mod display;
+
+#[cfg(feature = "gen")]
+pub mod gen;
+
 mod r#macros;
+pub mod repl;
+pub mod same_as;
+pub mod testing;
 pub mod utils;
 
+/// Prefix of the blank node identifiers generated for the existential
+/// variables of rule conclusions.
+pub const GENERATED_BLANK_ID_PREFIX: &str = "inferdf";
+
 /// Signed triple.
 pub type Fact<T> = Signed<Triple<T, T, T>>;
 
@@ -204,3 +227,90 @@ pub enum Reason<R = Term> {
 	/// is not.
 	NotFalse(R),
 }
+
+impl<R> Reason<R> {
+	/// Returns the offending resources.
+	pub fn terms(&self) -> Vec<&R> {
+		match self {
+			Self::MissingTriple(Signed(_, Triple(s, p, o))) => vec![s, p, o],
+			Self::NotEq(a, b) | Self::NotNe(a, b) => vec![a, b],
+			Self::NotTrue(r) | Self::NotFalse(r) => vec![r],
+		}
+	}
+}
+
+/// Rule violation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Violation<R = Term> {
+	/// Index of the violated rule in the deduction system.
+	pub rule: usize,
+
+	/// Severity of the violated rule.
+	pub severity: Severity,
+
+	/// Reason of the violation.
+	pub reason: Reason<R>,
+
+	/// Substitution of the rule variables in the violating deduction.
+	pub substitution: Vec<Option<R>>,
+}
+
+impl<R> Violation<R> {
+	/// Returns the bound variables of the violating deduction, with their
+	/// value, named after the variables of the given violated rule.
+	pub fn bindings<'a>(
+		&'a self,
+		rule: &'a Rule<R>,
+	) -> impl Iterator<Item = (rule::Variable<'a>, &'a R)> {
+		self.substitution
+			.iter()
+			.enumerate()
+			.filter_map(move |(x, value)| Some((rule.variable(x), value.as_ref()?)))
+	}
+}
+
+/// Validation report, listing the violations of every rule of a system.
+#[derive(Debug, Clone, PartialEq, Eq, Educe)]
+#[educe(Default)]
+pub struct ValidationReport<R = Term> {
+	/// Violations of any severity, in rule order.
+	///
+	/// [`System::report`] records at most one violation per rule.
+	pub violations: Vec<Violation<R>>,
+}
+
+impl<R> ValidationReport<R> {
+	/// Checks that no [`Severity::Error`] rule is violated.
+	///
+	/// Advisory violations do not invalidate the dataset.
+	pub fn is_valid(&self) -> bool {
+		self.errors().next().is_none()
+	}
+
+	/// Checks that at least one [`Severity::Error`] rule is violated.
+	///
+	/// This is the negation of [`Self::is_valid`].
+	pub fn is_invalid(&self) -> bool {
+		!self.is_valid()
+	}
+
+	/// Returns an iterator over the hard violations.
+	pub fn errors(&self) -> impl Iterator<Item = &Violation<R>> {
+		self.violations.iter().filter(|v| v.severity.is_error())
+	}
+
+	/// Returns an iterator over the advisory violations.
+	pub fn warnings(&self) -> impl Iterator<Item = &Violation<R>> {
+		self.violations.iter().filter(|v| !v.severity.is_error())
+	}
+
+	/// Turns this report into a validation status, keeping only the first
+	/// hard violation.
+	pub fn into_validation(self) -> Validation<R> {
+		self.violations
+			.into_iter()
+			.find(|v| v.severity.is_error())
+			.map(|v| Validation::Invalid(v.reason))
+			.unwrap_or(Validation::Ok)
+	}
+}
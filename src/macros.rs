@@ -134,6 +134,11 @@ macro_rules! patterns {
 	} => {
 		$crate::patterns!(@from [] $($acc)*)
 	};
+	{
+		@tokenize [$($acc:tt)*] [$($current:tt)+]
+	} => {
+		compile_error!("expected `.` at the end of the pattern")
+	};
 	{
 		@tokenize [$($acc:tt)*] [$($current:tt)*] $t:tt $($rest:tt)*
 	} => {
@@ -158,7 +163,233 @@ macro_rules! patterns {
 	};
 }
 
+/// Creates a rule hypothesis, with its list patterns.
+///
+/// The resulting value has type
+/// `(`[`Hypothesis`](crate::rule::Hypothesis)`, Vec<`[`ListPattern`](crate::rule::ListPattern)`>)`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! hypothesis {
+	// Split list patterns from triple patterns.
+	{
+		@split [$($patterns:tt)*] [$($lists:tt)*] [] ? $id:ident ( $($items:tt)* ) . $($rest:tt)*
+	} => {
+		$crate::hypothesis!(
+			@split [$($patterns)*] [$($lists)* $crate::rule::ListPattern::new(
+				$crate::pattern::ResourceOrVar::Var($id),
+				$crate::hypothesis!(@items [] $($items)*)
+			),] [] $($rest)*
+		)
+	};
+	{
+		@split [$($patterns:tt)*] [$($lists:tt)*] [$($current:tt)*] . $($rest:tt)*
+	} => {
+		$crate::hypothesis!(@split [$($patterns)* $($current)* .] [$($lists)*] [] $($rest)*)
+	};
+	{
+		@split [$($patterns:tt)*] [$($lists:tt)*] [$($current:tt)*] $t:tt $($rest:tt)*
+	} => {
+		$crate::hypothesis!(@split [$($patterns)*] [$($lists)*] [$($current)* $t] $($rest)*)
+	};
+	{
+		@split [$($patterns:tt)*] [$($lists:tt)*] [$($current:tt)*]
+	} => {
+		(
+			$crate::rule::Hypothesis::new($crate::patterns!($($patterns)* $($current)*)),
+			vec![$($lists)*]
+		)
+	};
+	// Parse list items.
+	{
+		@items [] ? $id:ident ...
+	} => {
+		$crate::rule::ListItems::Member($crate::pattern::ResourceOrVar::Var($id))
+	};
+	{
+		@items [$($acc:tt)*] ? $id:ident $($rest:tt)*
+	} => {
+		$crate::hypothesis!(@items [$($acc)* $crate::pattern::ResourceOrVar::Var($id),] $($rest)*)
+	};
+	{
+		@items [$($acc:tt)*] < $iri:literal > $($rest:tt)*
+	} => {
+		$crate::hypothesis!(@items [$($acc)* $crate::pattern::ResourceOrVar::Resource(
+			<$crate::rdf_types::Term>::iri($crate::static_iref::iri!($iri).to_owned())
+		),] $($rest)*)
+	};
+	{
+		@items [$($acc:tt)*] $value:literal ^^ $ty:literal $($rest:tt)*
+	} => {
+		$crate::hypothesis!(@items [$($acc)* $crate::pattern::ResourceOrVar::Resource(
+			<$crate::rdf_types::Term>::Literal($crate::rdf_types::Literal::new(
+				$value.to_owned(),
+				$crate::rdf_types::LiteralType::Any(
+					$crate::static_iref::iri!($ty).to_owned()
+				)
+			))
+		),] $($rest)*)
+	};
+	{
+		@items [$($acc:tt)*] $value:literal $($rest:tt)*
+	} => {
+		$crate::hypothesis!(@items [$($acc)* $crate::pattern::ResourceOrVar::Resource(
+			<$crate::rdf_types::Term>::Literal($crate::rdf_types::Literal::new(
+				$value.to_owned(),
+				$crate::rdf_types::LiteralType::Any(
+					$crate::rdf_types::XSD_STRING.to_owned()
+				)
+			))
+		),] $($rest)*)
+	};
+	{
+		@items [$($acc:tt)*]
+	} => {
+		$crate::rule::ListItems::Exact(vec![$($acc)*])
+	};
+	{
+		@items [$($acc:tt)*] $t:tt $($rest:tt)*
+	} => {
+		$crate::unexpected_token!($t)
+	};
+	// Main rule.
+	{
+		$($t:tt)*
+	} => {
+		$crate::hypothesis!(@split [] [] [] $($t)*)
+	};
+}
+
 /// Creates a deduction rule.
+///
+/// The rule can be prefixed with its [severity](crate::Severity) (`error`,
+/// `warning` or `info`), followed by its IRI identifier (`<"iri">`) and the
+/// [profiles](crate::Rule::profiles) in which it is active (`["strict"]`).
+/// By default the severity is `error`, and the rule is active in every
+/// profile.
+///
+/// ```
+/// # use inferdf::rule;
+/// let rule = rule! {
+///   warning for ?x, ?y {
+///     ?x <"https://example.org/#parent"> ?y .
+///     ! ?y <"https://example.org/#name"> "Alice" .
+///   } => for ?z {
+///     ?x <"https://example.org/#grandParent"> ?z !
+///     ! ?z <"https://example.org/#parent"> ?x .
+///     ?x = ?y .
+///     ?y != ?z .
+///     (!= ?x ?y) !
+///   }
+/// };
+/// ```
+///
+/// The inequality statement `?y != ?z` is a shorthand for `! ?y = ?z`.
+/// Declared variable names are kept in [`Rule::names`](crate::Rule::names).
+///
+/// Universal conditions follow the hypothesis, each introduced by `forall`
+/// with its own local variables. The condition holds if every match of its
+/// premise also matches the required patterns:
+///
+/// ```
+/// # use inferdf::rule;
+/// let rule = rule! {
+///   for ?l {
+///     ?l <"https://example.org/#type"> <"https://example.org/#Team"> .
+///   } forall ?m {
+///     ?l <"https://example.org/#member"> ?m .
+///   } => {
+///     ?m <"https://example.org/#certified"> "true" .
+///   } => {
+///     ?l <"https://example.org/#type"> <"https://example.org/#CertifiedTeam"> .
+///   }
+/// };
+/// ```
+///
+/// Universal conditions only filter the matches of the hypothesis: they do
+/// not trigger incremental deduction by themselves.
+///
+/// A hypothesis pattern of the form `?l ( ... ) .` is a
+/// [list pattern](crate::rule::ListPattern), matching the items of the RDF
+/// collection `?l` through its `rdf:first` and `rdf:rest` links. It matches
+/// either a list with exactly the given items, or each member `?m` of the
+/// list with `( ?m ... )`:
+///
+/// ```
+/// # use inferdf::rule;
+/// let rule = rule! {
+///   for ?x, ?l, ?a, ?b {
+///     ?x <"https://example.org/#pair"> ?l .
+///     ?l ( ?a ?b ) .
+///   } => {
+///     ?a <"https://example.org/#pairedWith"> ?b .
+///   }
+/// };
+///
+/// let rule = rule! {
+///   for ?x, ?l, ?m {
+///     ?x <"https://example.org/#members"> ?l .
+///     ?l ( ?m ... ) .
+///   } => {
+///     ?m <"https://example.org/#memberOf"> ?x .
+///   }
+/// };
+/// ```
+///
+/// The list node must be bound by the other hypothesis patterns. Like
+/// universal conditions, list patterns do not trigger incremental deduction
+/// by themselves.
+///
+/// Hypothesis patterns must end with `.`:
+///
+/// ```compile_fail
+/// # use inferdf::rule;
+/// let rule = rule! {
+///   for ?x, ?y {
+///     ?x <"https://example.org/#parent"> ?y
+///   } => {
+///     ?y <"https://example.org/#child"> ?x .
+///   }
+/// };
+/// ```
+///
+/// Conclusion statements must end with `.` (untrusted) or `!` (trusted):
+///
+/// ```compile_fail
+/// # use inferdf::rule;
+/// let rule = rule! {
+///   for ?x, ?y {
+///     ?x <"https://example.org/#parent"> ?y .
+///   } => {
+///     ?y <"https://example.org/#child"> ?x
+///   }
+/// };
+/// ```
+///
+/// Variables must be declared by a `for` clause:
+///
+/// ```compile_fail
+/// # use inferdf::rule;
+/// let rule = rule! {
+///   for ?x {
+///     ?x <"https://example.org/#parent"> ?y .
+///   } => {
+///     ?y <"https://example.org/#child"> ?x .
+///   }
+/// };
+/// ```
+///
+/// Hypothesis patterns cannot contain expressions:
+///
+/// ```compile_fail
+/// # use inferdf::rule;
+/// let rule = rule! {
+///   for ?x, ?y {
+///     ?x <"https://example.org/#age"> (>= ?y 18) .
+///   } => {
+///     ?x <"https://example.org/#adult"> ?y .
+///   }
+/// };
+/// ```
 #[macro_export]
 macro_rules! rule {
 	// Parse a conclusion.
@@ -178,6 +409,29 @@ macro_rules! rule {
 			)
 		}
 	};
+	// Name the variables of a conclusion.
+	{
+		@conclusion_names ($($offset:tt)*) { $($statements:tt)* }
+	} => {
+		$crate::rule!(@names)
+	};
+	{
+		@conclusion_names ($($offset:tt)*) for $(?$id:ident),* { $($statements:tt)* }
+	} => {
+		{
+			$crate::rule!(@bind ($($offset)*) $($id)*);
+			$crate::rule!(@names $($id)*)
+		}
+	};
+	// List the names of bound variables.
+	{
+		@names $($id:ident)*
+	} => {
+		{
+			let names: Vec<(usize, &str)> = vec![$(($id, stringify!($id))),*];
+			names
+		}
+	};
 	// Count the number of tokens.
 	{
 		@count $($t:tt)*
@@ -205,18 +459,102 @@ macro_rules! rule {
 		@bind ($($n:tt)*)
 	} => {};
 	// Main rules
+	{
+		error $($rest:tt)*
+	} => {
+		$crate::rule!($($rest)*).with_severity($crate::Severity::Error)
+	};
+	{
+		warning $($rest:tt)*
+	} => {
+		$crate::rule!($($rest)*).with_severity($crate::Severity::Warning)
+	};
+	{
+		info $($rest:tt)*
+	} => {
+		$crate::rule!($($rest)*).with_severity($crate::Severity::Info)
+	};
+	{
+		[ $($profile:literal),* ] $($rest:tt)*
+	} => {
+		$crate::rule!($($rest)*).with_profiles([$($profile),*])
+	};
+	{
+		< $iri:literal > $($rest:tt)*
+	} => {
+		$crate::rule!($($rest)*).with_id(
+			<$crate::rdf_types::Term>::iri($crate::static_iref::iri!($iri).to_owned())
+		)
+	};
+	// Parse universal conditions.
+	{
+		@forall [$($id:ident)*] [$($hypothesis:tt)*] [$($acc:tt)*]
+		forall $(?$local:ident),* { $($premise:tt)* } => { $($required:tt)* } $($rest:tt)*
+	} => {
+		$crate::rule!(
+			@forall [$($id)*] [$($hypothesis)*] [$($acc)* ($($local)*) { $($premise)* } { $($required)* }]
+			$($rest)*
+		)
+	};
+	{
+		@forall [$($id:ident)*] [$($hypothesis:tt)*]
+		[$( ($($local:ident)*) { $($premise:tt)* } { $($required:tt)* } )*]
+		=> $($conclusion:tt)*
+	} => {
+		{
+			$crate::rule!(@bind (0) $($id)*);
+			let offset = $crate::rule!(@count $($id)*);
+			let (hypothesis, lists) = $crate::hypothesis!($($hypothesis)*);
+			lists.into_iter().fold(
+				$crate::Rule::new(
+					offset,
+					hypothesis,
+					$crate::rule!(@conclusion (offset) $($conclusion)*)
+				),
+				$crate::Rule::with_list
+			)
+			.with_variable_names($crate::rule!(@names $($id)*))
+			.with_variable_names($crate::rule!(@conclusion_names (offset) $($conclusion)*))
+			$(.with_universal({
+				$crate::rule!(@bind (offset) $($local)*);
+				$crate::rule::ForAll::new(
+					$crate::rule!(@count $($local)*),
+					$crate::rule::Hypothesis::new($crate::patterns!($($premise)*)),
+					$crate::rule::Hypothesis::new($crate::patterns!($($required)*))
+				)
+			}))*
+		}
+	};
+	{
+		for $(?$id:ident),* { $($hypothesis:tt)* } forall $($rest:tt)*
+	} => {
+		$crate::rule!(@forall [$($id)*] [$($hypothesis)*] [] forall $($rest)*)
+	};
 	{
 		for $(?$id:ident),* { $($hypothesis:tt)* } => $($conclusion:tt)*
 	} => {
 		{
 			$crate::rule!(@bind (0) $($id)*);
-			$crate::Rule::new(
-				$crate::rule!(@count $($id)*),
-				$crate::rule::Hypothesis::new($crate::patterns!($($hypothesis)*)),
-				$crate::rule!(@conclusion ($crate::rule!(@count $($id)*)) $($conclusion)*)
+			let (hypothesis, lists) = $crate::hypothesis!($($hypothesis)*);
+			lists.into_iter().fold(
+				$crate::Rule::new(
+					$crate::rule!(@count $($id)*),
+					hypothesis,
+					$crate::rule!(@conclusion ($crate::rule!(@count $($id)*)) $($conclusion)*)
+				),
+				$crate::Rule::with_list
+			)
+			.with_variable_names($crate::rule!(@names $($id)*))
+			.with_variable_names(
+				$crate::rule!(@conclusion_names ($crate::rule!(@count $($id)*)) $($conclusion)*)
 			)
 		}
 	};
+	{
+		{ $($hypothesis:tt)* } forall $($rest:tt)*
+	} => {
+		$crate::rule!(for { $($hypothesis)* } forall $($rest)*)
+	};
 	{
 		{ $($hypothesis:tt)* } => $($conclusion:tt)*
 	} => {
@@ -385,6 +723,46 @@ macro_rules! expression {
 			$crate::expressions!($($args)*)
 		)
 	};
+	{
+		(+ $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Arithmetic(
+				$crate::expression::ArithmeticOperator::Add
+			),
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(- $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Arithmetic(
+				$crate::expression::ArithmeticOperator::Sub
+			),
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(* $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Arithmetic(
+				$crate::expression::ArithmeticOperator::Mul
+			),
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(/ $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Arithmetic(
+				$crate::expression::ArithmeticOperator::Div
+			),
+			$crate::expressions!($($args)*)
+		)
+	};
 	{
 		(matches $($args:tt)*)
 	} => {
@@ -393,6 +771,110 @@ macro_rules! expression {
 			$crate::expressions!($($args)*)
 		)
 	};
+	{
+		(lang $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Lang,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(langMatches $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::LangMatches,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(strLang $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::StrLang,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(concat $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Concat,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(iri $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Iri,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(str $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Str,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(isIri $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::IsIri,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(isBlank $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::IsBlank,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(isLiteral $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::IsLiteral,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(datatype $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Datatype,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(uuid $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Uuid,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(sha256 $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Sha256,
+			$crate::expressions!($($args)*)
+		)
+	};
+	{
+		(blake3 $($args:tt)*)
+	} => {
+		$crate::Expression::Call(
+			$crate::expression::BuiltInFunction::Blake3,
+			$crate::expressions!($($args)*)
+		)
+	};
 }
 
 /// Creates a triple statement.
@@ -400,60 +882,71 @@ macro_rules! expression {
 macro_rules! statement {
 	// Parse a list of expressions.
 	{
-		@from ($($acc:tt)*) ? $id:ident $($rest:tt)*
+		@from $signs:tt ($($acc:tt)*) ? $id:ident $($rest:tt)*
 	} => {
-		$crate::statement!(@from ($($acc)* ($crate::expression!(? $id)),) $($rest)*)
+		$crate::statement!(@from $signs ($($acc)* ($crate::expression!(? $id)),) $($rest)*)
 	};
 	{
-		@from ($($acc:tt)*) < $iri:literal > $($rest:tt)*
+		@from $signs:tt ($($acc:tt)*) < $iri:literal > $($rest:tt)*
 	} => {
-		$crate::statement!(@from ($($acc)* ($crate::expression!(< $iri >)),) $($rest)*)
+		$crate::statement!(@from $signs ($($acc)* ($crate::expression!(< $iri >)),) $($rest)*)
 	};
 	{
-		@from ($($acc:tt)*) _ : $id:literal $($rest:tt)*
+		@from $signs:tt ($($acc:tt)*) _ : $id:literal $($rest:tt)*
 	} => {
-		$crate::statement!(@from ($($acc)* ($crate::expression!(_ : $id)),) $($rest)*)
+		$crate::statement!(@from $signs ($($acc)* ($crate::expression!(_ : $id)),) $($rest)*)
 	};
 	{
-		@from ($($acc:tt)*) $value:literal ^^ $ty:literal $($rest:tt)*
+		@from $signs:tt ($($acc:tt)*) $value:literal ^^ $ty:literal $($rest:tt)*
 	} => {
-		$crate::statement!(@from ($($acc)* ($crate::expression!($value ^^ $ty)),) $($rest)*)
+		$crate::statement!(@from $signs ($($acc)* ($crate::expression!($value ^^ $ty)),) $($rest)*)
 	};
 	{
-		@from ($($acc:tt)*) $value:literal $($rest:tt)*
+		@from $signs:tt ($($acc:tt)*) $value:literal $($rest:tt)*
 	} => {
-		$crate::statement!(@from ($($acc)* ($crate::expression!($value)),) $($rest)*)
+		$crate::statement!(@from $signs ($($acc)* ($crate::expression!($value)),) $($rest)*)
 	};
 	{
-		@from ($($acc:tt)*) /$value:literal/ $($rest:tt)*
+		@from $signs:tt ($($acc:tt)*) /$value:literal/ $($rest:tt)*
 	} => {
-		$crate::statement!(@from ($($acc)* ($crate::expression!(/$value/)),) $($rest)*)
+		$crate::statement!(@from $signs ($($acc)* ($crate::expression!(/$value/)),) $($rest)*)
 	};
 	{
-		@from ($($acc:tt)*) ($($g:tt)*) $($rest:tt)*
+		@from $signs:tt ($($acc:tt)*) ($($g:tt)*) $($rest:tt)*
 	} => {
-		$crate::statement!(@from ($($acc)* ($crate::expression!(($($g)*))),) $($rest)*)
+		$crate::statement!(@from $signs ($($acc)* ($crate::expression!(($($g)*))),) $($rest)*)
 	};
 	{
-		@from (($($s:tt)*),) = $($rest:tt)*
+		@from ($sign:ident $opposite:ident) (($($s:tt)*),) = $($rest:tt)*
 	} => {
-		$crate::TripleStatement::Eq(
+		$crate::Signed($crate::Sign::$sign, $crate::TripleStatement::Eq(
 			$($s)*,
 			$crate::expression!($($rest)*)
-		)
+		))
+	};
+	{
+		@from ($sign:ident $opposite:ident) (($($s:tt)*),) != $($rest:tt)*
+	} => {
+		$crate::Signed($crate::Sign::$opposite, $crate::TripleStatement::Eq(
+			$($s)*,
+			$crate::expression!($($rest)*)
+		))
 	};
 	{
-		@from (($($s:tt)*), ($($p:tt)*), ($($o:tt)*),)
+		@from ($sign:ident $opposite:ident) (($($s:tt)*), ($($p:tt)*), ($($o:tt)*),)
 	} => {
-		$crate::TripleStatement::Triple($crate::rdf_types::Triple($($s)*, $($p)*, $($o)*))
+		$crate::Signed(
+			$crate::Sign::$sign,
+			$crate::TripleStatement::Triple($crate::rdf_types::Triple($($s)*, $($p)*, $($o)*))
+		)
 	};
 	{
-		@from (($($s:tt)*),)
+		@from ($sign:ident $opposite:ident) (($($s:tt)*),)
 	} => {
-		$crate::TripleStatement::True($($s)*)
+		$crate::Signed($crate::Sign::$sign, $crate::TripleStatement::True($($s)*))
 	};
 	{
-		@from $acc:tt $t:tt $($rest:tt)*
+		@from $signs:tt $acc:tt $t:tt $($rest:tt)*
 	} => {
 		$crate::unexpected_token!($t)
 	};
@@ -461,23 +954,36 @@ macro_rules! statement {
 	{
 		! $($t:tt)*
 	} => {
-		$crate::Signed($crate::Sign::Negative, $crate::statement!(@from () $($t)*))
+		$crate::statement!(@from (Negative Positive) () $($t)*)
 	};
 	{
 		$($t:tt)*
 	} => {
-		$crate::Signed($crate::Sign::Positive, $crate::statement!(@from () $($t)*))
+		$crate::statement!(@from (Positive Negative) () $($t)*)
 	};
 }
 
 /// Creates a list of triple statements.
+///
+/// Each statement ends either with `.` for an untrusted statement, or `!` for
+/// a trusted statement.
 #[macro_export]
 macro_rules! statements {
 	// Tokenize statements.
 	{
-		@tokenize [$($acc:tt)*] [$($current:tt)*] ! $($rest:tt)*
+		@tokenize [$($acc:tt)*] [$($current:tt)*] != $($rest:tt)*
 	} => {
-		$crate::statements!(@tokenize [$($acc)*] [$($current)* !] $($rest)*)
+		$crate::statements!(@tokenize [$($acc)*] [$($current)* !=] $($rest)*)
+	};
+	{
+		@tokenize [$($acc:tt)*] [] ! $($rest:tt)*
+	} => {
+		$crate::statements!(@tokenize [$($acc)*] [!] $($rest)*)
+	};
+	{
+		@tokenize [$($acc:tt)*] [$($current:tt)+] ! $($rest:tt)*
+	} => {
+		$crate::statements!(@tokenize [$($acc)* (Trusted ($($current)*))] [] $($rest)*)
 	};
 	{
 		@tokenize [$($acc:tt)*] [$($current:tt)*] = $($rest:tt)*
@@ -537,13 +1043,18 @@ macro_rules! statements {
 	{
 		@tokenize [$($acc:tt)*] [$($current:tt)*] . $($rest:tt)*
 	} => {
-		$crate::statements!(@tokenize [$($acc)* ($($current)*)] [] $($rest)*)
+		$crate::statements!(@tokenize [$($acc)* (Untrusted ($($current)*))] [] $($rest)*)
 	};
 	{
 		@tokenize [$($acc:tt)*] []
 	} => {
 		$crate::statements!(@from [] $($acc)*)
 	};
+	{
+		@tokenize [$($acc:tt)*] [$($current:tt)+]
+	} => {
+		compile_error!("expected `.` or `!` at the end of the statement")
+	};
 	{
 		@tokenize [$($acc:tt)*] [$($current:tt)*] $t:tt $($rest:tt)*
 	} => {
@@ -551,9 +1062,12 @@ macro_rules! statements {
 	};
 	// Parse a tokenized statement list.
 	{
-		@from [$($acc:tt)*] ($($statement:tt)*) $($rest:tt)*
+		@from [$($acc:tt)*] ($trust:ident ($($statement:tt)*)) $($rest:tt)*
 	} => {
-		$crate::statements!(@from [$($acc)* $crate::statement!($($statement)*),] $($rest)*)
+		$crate::statements!(@from [$($acc)* $crate::MaybeTrusted(
+			$crate::Trust::$trust,
+			$crate::statement!($($statement)*)
+		),] $($rest)*)
 	};
 	{
 		@from [$($acc:tt)*]
@@ -572,7 +1086,9 @@ macro_rules! statements {
 mod tests {
 	use rdf_types::Triple;
 
-	use crate::{pattern::ResourceOrVar, rule::TripleStatementPattern, Signed};
+	use crate::{
+		pattern::ResourceOrVar, rule::TripleStatementPattern, MaybeTrusted, Signed, Trust,
+	};
 
 	#[test]
 	fn statement_macro() {
@@ -585,11 +1101,16 @@ mod tests {
 	fn statements_macro() {
 		let x = 0;
 		let y = 1;
-		let _: Vec<Signed<TripleStatementPattern>> = statements! [
+		let statements: Vec<MaybeTrusted<Signed<TripleStatementPattern>>> = statements! [
 			?x <"http://example.org/#foo"> "hello"^^"http://example.org/#test" .
-			?x = ?y .
-			(= ?x ?y) .
+			?x = ?y !
+			! (= ?x ?y) !
 		];
+
+		assert_eq!(statements[0].trust(), Trust::Untrusted);
+		assert_eq!(statements[1].trust(), Trust::Trusted);
+		assert_eq!(statements[2].trust(), Trust::Trusted);
+		assert!(statements[2].value().is_negative());
 	}
 
 	#[test]
@@ -618,4 +1139,118 @@ mod tests {
 			}
 		};
 	}
+
+	#[test]
+	fn rule_macro_signs_and_trust() {
+		let rule = rule! {
+			for ?a, ?b {
+				?a <"http://example.org/#foo"> ?b .
+				! ?b <"http://example.org/#bar"> "hello" .
+			} => for ?c {
+				?a <"http://example.org/#knows"> ?c !
+				! ?c <"http://example.org/#bar"> ?b !
+				! ?a = ?c .
+				(!= ?a ?b) .
+			}
+		};
+
+		assert!(rule.hypothesis.patterns[0].is_positive());
+		assert!(rule.hypothesis.patterns[1].is_negative());
+
+		let statements = &rule.conclusion.statements;
+		assert_eq!(statements.len(), 4);
+		assert!(statements[0].is_trusted() && statements[0].value().is_positive());
+		assert!(statements[1].is_trusted() && statements[1].value().is_negative());
+		assert!(statements[2].is_untrusted() && statements[2].value().is_negative());
+		assert!(statements[3].is_untrusted() && statements[3].value().is_positive());
+	}
+
+	#[test]
+	fn rule_macro_inequality() {
+		let rule = rule! {
+			for ?a, ?b {
+				?a <"http://example.org/#foo"> ?b .
+			} => {
+				?a != ?b .
+				! ?a != ?b !
+			}
+		};
+
+		let statements = &rule.conclusion.statements;
+		assert_eq!(statements.len(), 2);
+		assert!(statements[0].is_untrusted() && statements[0].value().is_negative());
+		assert!(statements[1].is_trusted() && statements[1].value().is_positive());
+		assert!(matches!(
+			statements[0].value().value(),
+			crate::TripleStatement::Eq(_, _)
+		));
+	}
+
+	#[test]
+	fn rule_macro_forall() {
+		let rule = rule! {
+			for ?a {
+				?a <"http://example.org/#type"> <"http://example.org/#Team"> .
+			} forall ?b, ?c {
+				?a <"http://example.org/#member"> ?b .
+			} => {
+				?b <"http://example.org/#knows"> ?c .
+			} forall ?b {
+				! ?a <"http://example.org/#leader"> ?b .
+			} => {} => for ?c {
+				?a <"http://example.org/#team"> ?c .
+			}
+		};
+
+		assert_eq!(rule.variables, 1);
+		assert_eq!(rule.universals.len(), 2);
+		assert_eq!(rule.conclusion.variables, 1);
+
+		let forall = &rule.universals[0];
+		assert_eq!(forall.variables, 2);
+		assert_eq!(forall.premise.patterns[0].1 .2, ResourceOrVar::Var(1));
+		assert_eq!(forall.required.patterns[0].1 .0, ResourceOrVar::Var(1));
+		assert!(rule.universals[1].premise.patterns[0].is_negative());
+	}
+
+	#[test]
+	fn rule_macro_lists() {
+		let rule = rule! {
+			for ?x, ?l, ?a, ?m {
+				?x <"http://example.org/#items"> ?l .
+				?l ( ?a "b" <"http://example.org/#c"> ) .
+				?l ( ?m ... ) .
+			} => {
+				?x <"http://example.org/#first"> ?a .
+			}
+		};
+
+		assert_eq!(rule.hypothesis.patterns.len(), 1);
+		assert_eq!(rule.lists.len(), 2);
+		assert_eq!(rule.lists[0].list, ResourceOrVar::Var(1));
+		assert!(matches!(
+			&rule.lists[0].items,
+			crate::rule::ListItems::Exact(items) if items.len() == 3 && items[0] == ResourceOrVar::Var(2)
+		));
+		assert_eq!(
+			rule.lists[1].items,
+			crate::rule::ListItems::Member(ResourceOrVar::Var(3))
+		);
+	}
+
+	#[test]
+	fn rule_macro_variable_names() {
+		let rule = rule! {
+			for ?person, ?age {
+				?person <"http://example.org/#age"> ?age .
+			} => for ?record {
+				?record <"http://example.org/#about"> ?person .
+			}
+		};
+
+		assert_eq!(rule.variable(0).to_string(), "?person");
+		assert_eq!(rule.variable(1).to_string(), "?age");
+		assert_eq!(rule.variable(2).to_string(), "?record");
+		assert_eq!(rule.variable(3).to_string(), "?_3");
+	}
 }
@@ -0,0 +1,115 @@
+//! Conjunctive pattern matching.
+//!
+//! Finds the substitutions matching a set of patterns at once, without
+//! defining a rule.
+use std::hash::Hash;
+
+use rdf_types::Quad;
+
+use crate::{
+	utils::IteratorSearch, FallibleSignedPatternMatchingDataset, Signed,
+	SignedPatternMatchingDataset,
+};
+
+use super::{Pattern, PatternSubstitution, ResourceOrVar, TripleMatching};
+
+/// Lazily finds the substitutions matching every given pattern in the
+/// dataset.
+///
+/// Each returned substitution extends `initial_substitution`. Negative
+/// patterns match the negative triples of the dataset. With no pattern, the
+/// initial substitution is the only result.
+///
+/// ```
+/// # use inferdf::{patterns, pattern::{conjunctive, PatternSubstitution}};
+/// # use rdf_types::{dataset::IndexedBTreeGraph, grdf_triples};
+/// let dataset: IndexedBTreeGraph = grdf_triples![
+///   _:"alice" <"https://example.org/#parent"> _:"bob" .
+///   _:"bob" <"https://example.org/#parent"> _:"carol" .
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let (x, y, z) = (0, 1, 2);
+/// let patterns = patterns! [
+///   ?x <"https://example.org/#parent"> ?y .
+///   ?y <"https://example.org/#parent"> ?z .
+/// ];
+///
+/// let substitutions: Vec<_> =
+///   conjunctive(&dataset, &patterns, PatternSubstitution::new()).collect();
+/// assert_eq!(substitutions.len(), 1);
+/// assert_eq!(substitutions[0].get(z).unwrap().to_string(), "_:carol");
+/// ```
+pub fn conjunctive<'a, T, D>(
+	dataset: &'a D,
+	patterns: &'a [Signed<Pattern<T>>],
+	initial_substitution: PatternSubstitution<T>,
+) -> impl 'a + Iterator<Item = PatternSubstitution<T>>
+where
+	T: Clone + Eq + Hash,
+	D: SignedPatternMatchingDataset<Resource = T>,
+{
+	try_conjunctive(dataset, patterns, initial_substitution).map(Result::unwrap)
+}
+
+/// Lazily finds the substitutions matching every given pattern in the
+/// dataset.
+///
+/// See [`conjunctive`].
+pub fn try_conjunctive<'a, T, D>(
+	dataset: &'a D,
+	patterns: &'a [Signed<Pattern<T>>],
+	initial_substitution: PatternSubstitution<T>,
+) -> impl 'a + Iterator<Item = Result<PatternSubstitution<T>, D::Error>>
+where
+	T: Clone + Eq + Hash,
+	D: FallibleSignedPatternMatchingDataset<Resource = T>,
+{
+	try_conjunctive_except(dataset, patterns, initial_substitution, None)
+}
+
+/// Lazily finds the substitutions matching every given pattern in the
+/// dataset, except for the `excluded_pattern` (if any).
+pub(crate) fn try_conjunctive_except<'a, T, D>(
+	dataset: &'a D,
+	patterns: &'a [Signed<Pattern<T>>],
+	initial_substitution: PatternSubstitution<T>,
+	excluded_pattern: Option<usize>,
+) -> impl 'a + Iterator<Item = Result<PatternSubstitution<T>, D::Error>>
+where
+	T: Clone + Eq + Hash,
+	D: FallibleSignedPatternMatchingDataset<Resource = T>,
+{
+	patterns
+		.iter()
+		.enumerate()
+		.filter_map(move |(i, pattern)| {
+			if excluded_pattern == Some(i) {
+				None
+			} else {
+				let canonical_pattern = pattern
+					.as_ref()
+					.map(|t| t.as_ref().map(ResourceOrVar::as_ref))
+					.cast();
+
+				Some(dataset.try_signed_pattern_matching(canonical_pattern).map(
+					move |m: Result<Signed<Quad<&T>>, D::Error>| {
+						m.map(|Signed(_, m)| (pattern, m.into_triple().0))
+					},
+				))
+			}
+		})
+		.search(initial_substitution, |substitution, (pattern, m)| {
+			let mut substitution = substitution.clone();
+			if pattern
+				.as_ref()
+				.into_value()
+				.triple_matching(&mut substitution, m)
+			{
+				Some(substitution)
+			} else {
+				None
+			}
+		})
+}
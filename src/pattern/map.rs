@@ -19,7 +19,7 @@ impl<V: Eq + Hash, T: Eq + Hash> BipolarMap<V, T> {
 }
 
 impl<V, T: Eq + Hash> BipolarMap<V, T> {
-	pub fn get(&self, Signed(sign, triple): Signed<Triple<&T>>) -> Values<V> {
+	pub fn get(&self, Signed(sign, triple): Signed<Triple<&T>>) -> Values<'_, V> {
 		self.0.get(sign).get(triple)
 	}
 }
@@ -7,6 +7,9 @@ pub use rdf_types::pattern::CanonicalTriplePattern as Canonical;
 pub mod map;
 pub use map::BipolarMap;
 
+mod conjunctive;
+pub use conjunctive::*;
+
 /// Resource or variable, used in a [`Pattern`].
 pub type ResourceOrVar<T = Term> = rdf_types::pattern::ResourceOrVar<T, usize>;
 
@@ -174,3 +177,80 @@ impl<T, U: ApplyPartialSubstitution<T>> ApplyPartialSubstitution<T> for Triple<U
 		)
 	}
 }
+
+/// Fallible mapping of the resources of a value, leaving variables untouched.
+pub trait TryMapResources<T, U> {
+	type Output;
+
+	fn try_map_resources<E>(self, f: &mut impl FnMut(T) -> Result<U, E>)
+		-> Result<Self::Output, E>;
+}
+
+impl<T, U> TryMapResources<T, U> for ResourceOrVar<T> {
+	type Output = ResourceOrVar<U>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		match self {
+			Self::Resource(r) => Ok(ResourceOrVar::Resource(f(r)?)),
+			Self::Var(x) => Ok(ResourceOrVar::Var(x)),
+		}
+	}
+}
+
+impl<T, U, A: TryMapResources<T, U>> TryMapResources<T, U> for Triple<A, A, A> {
+	type Output = Triple<A::Output, A::Output, A::Output>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		Ok(Triple(
+			self.0.try_map_resources(f)?,
+			self.1.try_map_resources(f)?,
+			self.2.try_map_resources(f)?,
+		))
+	}
+}
+
+impl<T, U, A: TryMapResources<T, U>> TryMapResources<T, U> for Vec<A> {
+	type Output = Vec<A::Output>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		self.into_iter().map(|a| a.try_map_resources(f)).collect()
+	}
+}
+
+/// In-place renaming of the variables of a value.
+pub trait MapVariables {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize);
+}
+
+impl<T> MapVariables for ResourceOrVar<T> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		if let Self::Var(x) = self {
+			*x = f(*x)
+		}
+	}
+}
+
+impl<A: MapVariables> MapVariables for Triple<A, A, A> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		self.0.map_variables(f);
+		self.1.map_variables(f);
+		self.2.map_variables(f)
+	}
+}
+
+impl<A: MapVariables> MapVariables for Vec<A> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		for a in self {
+			a.map_variables(f)
+		}
+	}
+}
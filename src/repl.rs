@@ -0,0 +1,635 @@
+//! Interactive rule exploration.
+//!
+//! A [`Session`] holds a dataset, a deduction system and a display context,
+//! and executes commands given one line at a time, returning their
+//! pretty-printed output. The command language extends the Datalog syntax
+//! of [`System::from_datalog`]:
+//!
+//! - `triple(s, p, o).` adds a fact to the dataset;
+//! - `head :- body.` adds a rule to the system;
+//! - `@prefix ex: <...>.` and `@base <...>.` declare a prefix or base IRI,
+//!   also used to display terms;
+//! - `?- body.` lists the substitutions matching a rule body;
+//! - `:close` closes the dataset under the system, listing the entailed
+//!   facts;
+//! - `:why triple(s, p, o).` explains each fact matching the given pattern,
+//!   with the rule and substitution that entailed it;
+//! - `:validate` validates the dataset against the system, listing the
+//!   violations of each rule (`:validate json` gives a JSON report, see
+//!   [`Session::report_json`]);
+//! - `:facts` and `:rules` list the dataset and the system;
+//! - `:stats` shows the dataset [`Statistics`];
+//! - `:describe term` shows the Concise Bounded Description of a resource
+//!   (see [`describe`]);
+//! - `:load path` loads a Datalog (`.dl`) or N-Quads file;
+//! - `:help` lists the commands.
+//!
+//! The final dot of a command can be omitted.
+//!
+//! ```
+//! use inferdf::repl::Session;
+//!
+//! let mut session = Session::new();
+//! session.execute("@prefix ex: <https://example.org/#>").unwrap();
+//! session.execute("triple(ex:alice, ex:parent, ex:bob)").unwrap();
+//! session.execute("triple(X, ex:ancestor, Y) :- triple(X, ex:parent, Y)").unwrap();
+//!
+//! assert_eq!(session.execute(":close").unwrap(), "ex:alice ex:ancestor ex:bob .\n1 entailed fact");
+//! assert_eq!(
+//!   session.execute(":why triple(ex:alice, ex:ancestor, Y)").unwrap(),
+//!   "ex:alice ex:ancestor ex:bob .\n  by rule #0 with ?X = ex:alice, ?Y = ex:bob\n    ex:alice ex:parent ex:bob ."
+//! );
+//! ```
+use std::{
+	collections::{HashMap, HashSet},
+	fmt::Write,
+	io,
+	path::Path,
+	time::{Duration, Instant},
+};
+
+use rdf_types::{dataset::IndexedBTreeDataset, Term, Triple};
+use serde::{Serialize, Serializer};
+
+use crate::{
+	dataset::{stats::Statistics, subset::describe},
+	display::{DisplayContext, DisplayWithContext},
+	expression::{self, Expression},
+	pattern::{Pattern, ResourceOrVar},
+	system::{parse_query, rule_to_datalog, DatalogParseError},
+	testing, Entailment, Rule, Sign, Signed, System, TripleStatement, ValidationReport,
+};
+
+/// JSON validation report of a session dataset.
+///
+/// See [`Session::json_report`]. Terms are written using the session
+/// display context. New fields may be added, but existing fields keep their
+/// meaning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonReport {
+	/// Whether no error is reported.
+	pub valid: bool,
+
+	/// Number of facts not entailed by the session.
+	pub stated_facts: usize,
+
+	/// Number of facts entailed by the session.
+	pub derived_facts: usize,
+
+	/// Number of rules that entailed at least one fact.
+	pub rules_fired: usize,
+
+	/// Number of hard violations.
+	pub errors: usize,
+
+	/// Number of advisory violations.
+	pub warnings: usize,
+
+	/// Validation time, in microseconds.
+	pub elapsed_micros: u128,
+
+	/// Violations.
+	pub violations: Vec<JsonViolation>,
+}
+
+/// Rule violation of a [`JsonReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonViolation {
+	/// Index of the violated rule.
+	pub rule: usize,
+
+	/// Identifier of the violated rule, if any.
+	pub id: Option<String>,
+
+	/// Severity of the violated rule.
+	pub severity: &'static str,
+
+	/// Reason of the violation.
+	pub reason: String,
+
+	/// Offending terms.
+	pub terms: Vec<String>,
+
+	/// Bound variables (such as `?person`) with their value, serialized as
+	/// an object.
+	#[serde(serialize_with = "serialize_bindings")]
+	pub bindings: Vec<(String, String)>,
+}
+
+/// Serializes the given bindings as a map, in order.
+fn serialize_bindings<S: Serializer>(
+	bindings: &[(String, String)],
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	serializer.collect_map(bindings.iter().map(|(x, value)| (x, value)))
+}
+
+/// Commands summary, printed by `:help`.
+const HELP: &str = "\
+triple(s, p, o).        add a fact
+head :- body.           add a rule
+@prefix ex: <iri>.      declare a prefix
+@base <iri>.            declare the base IRI
+?- body.                list the matches of a rule body
+:close                  close the dataset under the rules
+:why triple(s, p, o).   explain the matching facts
+:validate [json]        validate the facts against the rules
+:facts                  list the facts
+:rules                  list the rules
+:stats                  show the dataset statistics
+:describe term          describe a resource
+:load path              load a Datalog (.dl) or N-Quads file
+:help                   show this help";
+
+/// REPL error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error(transparent)]
+	IO(#[from] io::Error),
+
+	#[error(transparent)]
+	Datalog(#[from] DatalogParseError),
+
+	#[error(transparent)]
+	Load(#[from] testing::Error),
+
+	#[error(transparent)]
+	Expression(#[from] expression::Error),
+
+	#[error("unknown command `{0}`")]
+	UnknownCommand(String),
+
+	#[error("missing argument: {0}")]
+	MissingArgument(&'static str),
+
+	#[error("facts must be positive triples without variables")]
+	NotAFact,
+
+	#[error("expected a single positive triple pattern")]
+	NotASinglePattern,
+
+	#[error("expected a resource")]
+	NotAResource,
+}
+
+/// Entailment recorded during a closure.
+#[derive(Debug, Clone)]
+struct Cause {
+	/// Index of the entailing rule.
+	rule: usize,
+
+	/// Rule variables substitution.
+	substitution: Vec<Option<Term>>,
+}
+
+/// Interactive exploration session.
+#[derive(Default)]
+pub struct Session {
+	dataset: IndexedBTreeDataset,
+	system: System,
+	context: DisplayContext,
+
+	/// Entailment of each fact deduced by the session.
+	causes: HashMap<Triple, Cause>,
+}
+
+impl Session {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_dataset(mut self, dataset: IndexedBTreeDataset) -> Self {
+		self.dataset = dataset;
+		self
+	}
+
+	pub fn with_system(mut self, system: System) -> Self {
+		self.system = system;
+		self
+	}
+
+	pub fn with_display_context(mut self, context: DisplayContext) -> Self {
+		self.context = context;
+		self
+	}
+
+	pub fn dataset(&self) -> &IndexedBTreeDataset {
+		&self.dataset
+	}
+
+	pub fn system(&self) -> &System {
+		&self.system
+	}
+
+	pub fn display_context(&self) -> &DisplayContext {
+		&self.context
+	}
+
+	/// Validates the dataset against every rule of the system.
+	pub fn report(&self) -> Result<ValidationReport, expression::Error> {
+		self.system.report(&self.dataset)
+	}
+
+	/// Formats the given validation report of the session dataset.
+	///
+	/// Each rule is listed with its violations, followed by a summary line
+	/// starting with `valid` or `invalid`.
+	pub fn format_report(&self, report: &ValidationReport) -> String {
+		let mut output = Vec::new();
+		for (i, rule) in self.system.iter().enumerate() {
+			let violations: Vec<_> = report.violations.iter().filter(|v| v.rule == i).collect();
+
+			let mut line = format!("#{i}");
+			if let Some(id) = &rule.id {
+				write!(line, " ({})", id.with(&self.context)).unwrap()
+			}
+			if violations.is_empty() {
+				line.push_str(" ok")
+			} else {
+				write!(
+					line,
+					" {}",
+					plural(violations.len(), "violation", "violations")
+				)
+				.unwrap()
+			}
+			output.push(line);
+
+			for v in violations {
+				output.push(format!(
+					"  {}: {}",
+					v.severity.as_str(),
+					v.reason.with(&self.context)
+				));
+
+				let bindings: Vec<_> = v
+					.bindings(rule)
+					.map(|(x, value)| format!("{x} = {}", value.with(&self.context)))
+					.collect();
+				if !bindings.is_empty() {
+					output.push(format!("    with {}", bindings.join(", ")))
+				}
+			}
+		}
+
+		let errors = report.errors().count();
+		let warnings = report.warnings().count();
+		output.push(format!(
+			"{}: {}, {}",
+			if report.is_valid() {
+				"valid"
+			} else {
+				"invalid"
+			},
+			plural(errors, "error", "errors"),
+			plural(warnings, "warning", "warnings")
+		));
+
+		output.join("\n")
+	}
+
+	/// Summarizes the given validation report of the session dataset, with
+	/// terms written using the session display context.
+	pub fn json_report(&self, report: &ValidationReport, elapsed: Duration) -> JsonReport {
+		let rules_fired: HashSet<_> = self.causes.values().map(|cause| cause.rule).collect();
+		JsonReport {
+			valid: report.is_valid(),
+			stated_facts: self.dataset.len().saturating_sub(self.causes.len()),
+			derived_facts: self.causes.len(),
+			rules_fired: rules_fired.len(),
+			errors: report.errors().count(),
+			warnings: report.warnings().count(),
+			elapsed_micros: elapsed.as_micros(),
+			violations: report
+				.violations
+				.iter()
+				.map(|v| {
+					let rule = self.system.get(v.rule);
+					JsonViolation {
+						rule: v.rule,
+						id: rule
+							.and_then(|rule| rule.id.as_ref())
+							.map(|id| id.with(&self.context).to_string()),
+						severity: v.severity.as_str(),
+						reason: v.reason.with(&self.context).to_string(),
+						terms: v
+							.reason
+							.terms()
+							.into_iter()
+							.map(|term| term.with(&self.context).to_string())
+							.collect(),
+						bindings: rule
+							.into_iter()
+							.flat_map(|rule| v.bindings(rule))
+							.map(|(x, value)| {
+								(x.to_string(), value.with(&self.context).to_string())
+							})
+							.collect(),
+					}
+				})
+				.collect(),
+		}
+	}
+
+	/// Writes the given validation report of the session dataset as a
+	/// single-line JSON object (see [`JsonReport`]).
+	pub fn report_json(&self, report: &ValidationReport, elapsed: Duration) -> String {
+		serde_json::to_string(&self.json_report(report, elapsed)).unwrap()
+	}
+
+	/// Executes the given command line, returning its output.
+	///
+	/// Blank lines and `//` comments give no output.
+	pub fn execute(&mut self, line: &str) -> Result<String, Error> {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with("//") {
+			return Ok(String::new());
+		}
+
+		if let Some(body) = line.strip_prefix("?-") {
+			return self.query(body);
+		}
+
+		if let Some(command) = line.strip_prefix(':') {
+			let (name, argument) = command
+				.split_once(char::is_whitespace)
+				.map(|(name, argument)| (name, argument.trim()))
+				.unwrap_or((command, ""));
+
+			return match name {
+				"close" => self.close(),
+				"why" => self.why(argument),
+				"validate" if argument == "json" => {
+					let start = Instant::now();
+					let report = self.report()?;
+					Ok(self.report_json(&report, start.elapsed()))
+				}
+				"validate" => Ok(self.format_report(&self.report()?)),
+				"facts" => Ok(self.facts()),
+				"rules" => Ok(self.rules()),
+				"stats" => Ok(Statistics::new(&self.dataset)
+					.with(&self.context)
+					.to_string()),
+				"describe" => self.describe(argument),
+				"load" if argument.is_empty() => Err(Error::MissingArgument("file path")),
+				"load" => self.load(argument),
+				"help" => Ok(HELP.to_owned()),
+				_ => Err(Error::UnknownCommand(name.to_owned())),
+			};
+		}
+
+		self.datalog(&terminated(line))
+	}
+
+	/// Adds the facts and rules of the given Datalog program.
+	fn datalog(&mut self, source: &str) -> Result<String, Error> {
+		let mut facts = Vec::new();
+		let mut rules = Vec::new();
+		for rule in System::from_datalog_with_context(source, &mut self.context)? {
+			if rule.variables == 0 && rule.hypothesis.patterns.is_empty() {
+				facts.push(fact_of(&rule)?)
+			} else {
+				rules.push(rule)
+			}
+		}
+
+		let mut output = Vec::new();
+		if !facts.is_empty() {
+			let len = self.dataset.len();
+			self.dataset
+				.extend(facts.into_iter().flatten().map(|t| t.into_quad(None)));
+			output.push(plural(
+				self.dataset.len() - len,
+				"fact added",
+				"facts added",
+			))
+		}
+
+		for rule in rules {
+			let len = self.system.len();
+			let i = self.system.insert(rule);
+			if i == len {
+				output.push(format!("rule #{i} added"))
+			} else {
+				output.push(format!("rule #{i} already defined"))
+			}
+		}
+
+		Ok(output.join("\n"))
+	}
+
+	fn load(&mut self, path: &str) -> Result<String, Error> {
+		let content = std::fs::read_to_string(path)?;
+		if Path::new(path).extension().is_some_and(|e| e == "dl") {
+			self.datalog(&content)
+		} else {
+			let dataset = testing::parse_nquads(&content)?;
+			let len = self.dataset.len();
+			self.dataset.extend(dataset);
+			Ok(plural(
+				self.dataset.len() - len,
+				"fact added",
+				"facts added",
+			))
+		}
+	}
+
+	/// Lists the substitutions matching the given rule body.
+	fn query(&mut self, body: &str) -> Result<String, Error> {
+		let query = parse_query(&terminated(body), &mut self.context)?;
+
+		let mut output = String::new();
+		let mut count = 0;
+		for deduction in query.deductions(&self.dataset) {
+			let bindings: Vec<_> = deduction
+				.entailment
+				.bindings()
+				.map(|(x, value)| format!("{x} = {}", value.with(&self.context)))
+				.collect();
+
+			if bindings.is_empty() {
+				writeln!(output, "yes").unwrap()
+			} else {
+				writeln!(output, "{}", bindings.join(", ")).unwrap()
+			}
+
+			count += 1
+		}
+
+		output.push_str(&plural(count, "match", "matches"));
+		Ok(output)
+	}
+
+	/// Closes the dataset, listing the entailed facts.
+	fn close(&mut self) -> Result<String, Error> {
+		let mut output = String::new();
+		let system = &self.system;
+		let context = &self.context;
+		let causes = &mut self.causes;
+		let count = system.close_observed(&mut self.dataset, |triple, entailment| {
+			writeln!(output, "{} .", triple.with(context)).unwrap();
+			let rule = system
+				.iter()
+				.position(|r| std::ptr::eq(r, entailment.rule))
+				.unwrap();
+
+			causes.insert(
+				triple.clone(),
+				Cause {
+					rule,
+					substitution: entailment.substitution.clone(),
+				},
+			);
+		})?;
+
+		output.push_str(&plural(count, "entailed fact", "entailed facts"));
+		Ok(output)
+	}
+
+	/// Explains each fact matching the given triple pattern.
+	fn why(&mut self, pattern: &str) -> Result<String, Error> {
+		let query = parse_query(&terminated(pattern), &mut self.context)?;
+		let [Signed(Sign::Positive, pattern)] = query.hypothesis.patterns.as_slice() else {
+			return Err(Error::NotASinglePattern);
+		};
+
+		let mut output = Vec::new();
+		for deduction in query.deductions(&self.dataset) {
+			let substitution = &deduction.entailment.substitution;
+			let triple = instantiate(pattern, substitution);
+			output.push(format!("{} .", triple.with(&self.context)));
+
+			match self.causes.get(&triple) {
+				Some(cause) => {
+					let rule = self.system.get(cause.rule).unwrap();
+					let entailment = Entailment::new(rule, cause.substitution.clone());
+					let bindings: Vec<_> = entailment
+						.bindings()
+						.map(|(x, value)| format!("{x} = {}", value.with(&self.context)))
+						.collect();
+
+					let mut by = format!("  by rule #{}", cause.rule);
+					if let Some(id) = &rule.id {
+						write!(by, " ({})", id.with(&self.context)).unwrap()
+					}
+					if !bindings.is_empty() {
+						write!(by, " with {}", bindings.join(", ")).unwrap()
+					}
+					output.push(by);
+
+					for premise in entailment.premises() {
+						output.push(format!("    {} .", premise.with(&self.context)))
+					}
+				}
+				None => output.push("  stated".to_owned()),
+			}
+		}
+
+		if output.is_empty() {
+			output.push("no matching fact".to_owned())
+		}
+
+		Ok(output.join("\n"))
+	}
+
+	/// Describes the given resource, written as a Datalog term.
+	fn describe(&mut self, term: &str) -> Result<String, Error> {
+		if term.is_empty() {
+			return Err(Error::MissingArgument("resource"));
+		}
+
+		let query = parse_query(&format!("triple({term}, X, Y)."), &mut self.context)?;
+		let Some(Signed(_, Triple(ResourceOrVar::Resource(resource), _, _))) =
+			query.hypothesis.patterns.first()
+		else {
+			return Err(Error::NotAResource);
+		};
+
+		let description: Vec<Triple> = describe(&self.dataset, resource);
+		if description.is_empty() {
+			return Ok("no description".to_owned());
+		}
+
+		Ok(description
+			.iter()
+			.map(|triple| format!("{} .", triple.with(&self.context)))
+			.collect::<Vec<_>>()
+			.join("\n"))
+	}
+
+	fn facts(&self) -> String {
+		let mut facts: Vec<_> = self
+			.dataset
+			.iter()
+			.map(|quad| format!("{} .", quad.into_triple().0.with(&self.context)))
+			.collect();
+		facts.sort_unstable();
+		facts.dedup();
+		facts.join("\n")
+	}
+
+	fn rules(&self) -> String {
+		self.system
+			.iter()
+			.enumerate()
+			.map(|(i, rule)| {
+				let datalog = rule_to_datalog(rule, &self.context)
+					.unwrap_or_else(|_| "(not representable in Datalog)".to_owned());
+
+				match &rule.id {
+					Some(id) => format!("#{i} ({}) {datalog}", id.with(&self.context)),
+					None => format!("#{i} {datalog}"),
+				}
+			})
+			.collect::<Vec<_>>()
+			.join("\n")
+	}
+}
+
+/// Appends the final dot of a command if it is missing.
+fn terminated(source: &str) -> String {
+	let source = source.trim();
+	if source.ends_with('.') {
+		source.to_owned()
+	} else {
+		format!("{source}.")
+	}
+}
+
+fn plural(count: usize, singular: &str, plural: &str) -> String {
+	if count == 1 {
+		format!("1 {singular}")
+	} else {
+		format!("{count} {plural}")
+	}
+}
+
+/// Returns the triples stated by a rule without hypothesis nor variables.
+fn fact_of(rule: &Rule) -> Result<Vec<Triple>, Error> {
+	let ground = |e: &Expression<ResourceOrVar<Term>>| match e {
+		Expression::Resource(ResourceOrVar::Resource(t)) => Ok(t.clone()),
+		_ => Err(Error::NotAFact),
+	};
+
+	rule.conclusion
+		.statements
+		.iter()
+		.map(|statement| match &statement.1 {
+			Signed(Sign::Positive, TripleStatement::Triple(Triple(s, p, o))) => {
+				Ok(Triple(ground(s)?, ground(p)?, ground(o)?))
+			}
+			_ => Err(Error::NotAFact),
+		})
+		.collect()
+}
+
+/// Substitutes the variables of the given matched pattern.
+fn instantiate(pattern: &Pattern<Term>, substitution: &[Option<Term>]) -> Triple {
+	let term = |r: &ResourceOrVar<Term>| match r {
+		ResourceOrVar::Resource(t) => t.clone(),
+		ResourceOrVar::Var(x) => substitution[*x].clone().unwrap(),
+	};
+
+	Triple(term(&pattern.0), term(&pattern.1), term(&pattern.2))
+}
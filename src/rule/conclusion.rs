@@ -1,7 +1,11 @@
 use rdf_types::{vocabulary::EmbedIntoVocabulary, Term, Vocabulary};
 use serde::{Deserialize, Serialize};
 
-use crate::{expression::Expression, pattern::ResourceOrVar, Signed, TripleStatement};
+use crate::{
+	expression::Expression,
+	pattern::{MapVariables, ResourceOrVar, TryMapResources},
+	MaybeTrusted, Signed, TripleStatement,
+};
 
 /// Rule conclusion.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -10,11 +14,14 @@ pub struct Conclusion<T> {
 	pub variables: usize,
 
 	/// Concluded statements.
-	pub statements: Vec<Signed<TripleStatementPattern<T>>>,
+	pub statements: Vec<MaybeTrusted<Signed<TripleStatementPattern<T>>>>,
 }
 
 impl<T> Conclusion<T> {
-	pub fn new(variables: usize, statements: Vec<Signed<TripleStatementPattern<T>>>) -> Self {
+	pub fn new(
+		variables: usize,
+		statements: Vec<MaybeTrusted<Signed<TripleStatementPattern<T>>>>,
+	) -> Self {
 		Self {
 			variables,
 			statements,
@@ -22,7 +29,7 @@ impl<T> Conclusion<T> {
 	}
 
 	pub fn visit_variables(&self, mut f: impl FnMut(usize)) {
-		for Signed(_, v) in &self.statements {
+		for MaybeTrusted(_, Signed(_, v)) in &self.statements {
 			match v {
 				TripleStatementPattern::Eq(s, o) => {
 					s.visit_variables(&mut f);
@@ -50,4 +57,24 @@ impl<V: Vocabulary, T: EmbedIntoVocabulary<V>> EmbedIntoVocabulary<V> for Conclu
 	}
 }
 
+impl<T> MapVariables for Conclusion<T> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		self.statements.map_variables(f)
+	}
+}
+
+impl<T, U> TryMapResources<T, U> for Conclusion<T> {
+	type Output = Conclusion<U>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		Ok(Conclusion {
+			variables: self.variables,
+			statements: self.statements.try_map_resources(f)?,
+		})
+	}
+}
+
 pub type TripleStatementPattern<T = Term> = TripleStatement<Expression<ResourceOrVar<T>>>;
@@ -2,7 +2,10 @@ use educe::Educe;
 use rdf_types::vocabulary::{EmbedIntoVocabulary, Vocabulary};
 use serde::{Deserialize, Serialize};
 
-use crate::{pattern::ResourceOrVar, Pattern, Signed};
+use crate::{
+	pattern::{MapVariables, ResourceOrVar, TryMapResources},
+	Pattern, Signed,
+};
 
 /// Deduction rule hypothesis.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Educe)]
@@ -48,6 +51,25 @@ impl<V: Vocabulary, T: EmbedIntoVocabulary<V>> EmbedIntoVocabulary<V> for Hypoth
 	}
 }
 
+impl<T> MapVariables for Hypothesis<T> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		self.patterns.map_variables(f)
+	}
+}
+
+impl<T, U> TryMapResources<T, U> for Hypothesis<T> {
+	type Output = Hypothesis<U>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		Ok(Hypothesis {
+			patterns: self.patterns.try_map_resources(f)?,
+		})
+	}
+}
+
 // impl<L, M, T: MapLiteral<L, M>> MapLiteral<L, M> for Hypothesis<T> {
 // 	type Output = Hypothesis<T::Output>;
 
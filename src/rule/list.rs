@@ -0,0 +1,277 @@
+use std::{collections::HashSet, hash::Hash};
+
+use iref::Iri;
+use rdf_types::{
+	vocabulary::{EmbedIntoVocabulary, Vocabulary},
+	Quad, Term, Triple,
+};
+use serde::{Deserialize, Serialize};
+use static_iref::iri;
+
+use crate::{
+	pattern::{MapVariables, Matching, PatternSubstitution, ResourceOrVar, TryMapResources},
+	FallibleSignedPatternMatchingDataset, Sign, Signed,
+};
+
+/// `rdf:first` property.
+pub const RDF_FIRST: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#first");
+
+/// `rdf:rest` property.
+pub const RDF_REST: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#rest");
+
+/// `rdf:nil` list.
+pub const RDF_NIL: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil");
+
+/// RDF collection (`rdf:List`) pattern.
+///
+/// Matches the items of the list starting at the `list` node by following
+/// its `rdf:first` and `rdf:rest` links. The list node must be bound by the
+/// hypothesis patterns of the rule.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ListPattern<T = Term> {
+	/// List node.
+	pub list: ResourceOrVar<T>,
+
+	/// Matched items.
+	pub items: ListItems<T>,
+
+	/// Resources of the list vocabulary.
+	pub vocabulary: ListVocabulary<T>,
+}
+
+impl ListPattern {
+	/// Creates a new list pattern using the `rdf:first`, `rdf:rest` and
+	/// `rdf:nil` IRIs.
+	pub fn new(list: ResourceOrVar, items: ListItems) -> Self {
+		Self::new_with(list, items, ListVocabulary::rdf())
+	}
+}
+
+impl<T> ListPattern<T> {
+	pub fn new_with(
+		list: ResourceOrVar<T>,
+		items: ListItems<T>,
+		vocabulary: ListVocabulary<T>,
+	) -> Self {
+		Self {
+			list,
+			items,
+			vocabulary,
+		}
+	}
+
+	pub fn visit_variables(&self, mut f: impl FnMut(usize)) {
+		if let ResourceOrVar::Var(x) = &self.list {
+			f(*x)
+		}
+
+		for item in self.items.iter() {
+			if let ResourceOrVar::Var(x) = item {
+				f(*x)
+			}
+		}
+	}
+}
+
+impl<T: Clone + Eq + Hash> ListPattern<T> {
+	/// Finds the extensions of the given substitution matching this list
+	/// pattern in the dataset.
+	///
+	/// Returns no substitution if the list node is not bound.
+	pub fn try_matching<D>(
+		&self,
+		dataset: &D,
+		substitution: PatternSubstitution<T>,
+	) -> Result<Vec<PatternSubstitution<T>>, D::Error>
+	where
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		let list = match &self.list {
+			ResourceOrVar::Resource(r) => r.clone(),
+			ResourceOrVar::Var(x) => match substitution.get(*x) {
+				Some(r) => r.clone(),
+				None => return Ok(Vec::new()),
+			},
+		};
+
+		let mut result = Vec::new();
+		match &self.items {
+			ListItems::Exact(items) => {
+				self.try_match_exact(dataset, &list, items, substitution, &mut result)?
+			}
+			ListItems::Member(member) => {
+				let mut visited = HashSet::new();
+				let mut stack = vec![list];
+				while let Some(node) = stack.pop() {
+					if node == self.vocabulary.nil || !visited.insert(node.clone()) {
+						continue;
+					}
+
+					for first in objects(dataset, &node, &self.vocabulary.first)? {
+						let mut s = substitution.clone();
+						if member.matching(&mut s, &first) {
+							result.push(s)
+						}
+					}
+
+					stack.extend(objects(dataset, &node, &self.vocabulary.rest)?)
+				}
+			}
+		}
+
+		Ok(result)
+	}
+
+	fn try_match_exact<D>(
+		&self,
+		dataset: &D,
+		node: &T,
+		items: &[ResourceOrVar<T>],
+		substitution: PatternSubstitution<T>,
+		result: &mut Vec<PatternSubstitution<T>>,
+	) -> Result<(), D::Error>
+	where
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		match items.split_first() {
+			None => {
+				if *node == self.vocabulary.nil {
+					result.push(substitution)
+				}
+			}
+			Some((item, rest_items)) => {
+				let rests = objects(dataset, node, &self.vocabulary.rest)?;
+				for first in objects(dataset, node, &self.vocabulary.first)? {
+					let mut s = substitution.clone();
+					if item.matching(&mut s, &first) {
+						for rest in &rests {
+							self.try_match_exact(dataset, rest, rest_items, s.clone(), result)?
+						}
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Returns the objects of the positive triples with the given subject and
+/// predicate.
+fn objects<T: Clone, D>(dataset: &D, subject: &T, predicate: &T) -> Result<Vec<T>, D::Error>
+where
+	D: FallibleSignedPatternMatchingDataset<Resource = T>,
+{
+	let pattern = Triple(
+		ResourceOrVar::Resource(subject),
+		ResourceOrVar::Resource(predicate),
+		ResourceOrVar::Var(0),
+	);
+
+	dataset
+		.try_signed_pattern_matching(Signed(Sign::Positive, pattern.into()))
+		.map(|m: Result<Signed<Quad<&T>>, D::Error>| m.map(|Signed(_, Quad(_, _, o, _))| o.clone()))
+		.collect()
+}
+
+/// Items matched by a [`ListPattern`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ListItems<T = Term> {
+	/// Matches a list with exactly the given items, in order.
+	Exact(Vec<ResourceOrVar<T>>),
+
+	/// Matches each member of the list, producing one substitution per
+	/// member.
+	Member(ResourceOrVar<T>),
+}
+
+impl<T> ListItems<T> {
+	pub fn iter(&self) -> impl Iterator<Item = &ResourceOrVar<T>> {
+		match self {
+			Self::Exact(items) => items.as_slice(),
+			Self::Member(member) => std::slice::from_ref(member),
+		}
+		.iter()
+	}
+}
+
+/// Resources of the RDF collection vocabulary.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ListVocabulary<T = Term> {
+	/// `rdf:first` property.
+	pub first: T,
+
+	/// `rdf:rest` property.
+	pub rest: T,
+
+	/// `rdf:nil` list.
+	pub nil: T,
+}
+
+impl ListVocabulary {
+	pub fn rdf() -> Self {
+		Self {
+			first: Term::iri(RDF_FIRST.to_owned()),
+			rest: Term::iri(RDF_REST.to_owned()),
+			nil: Term::iri(RDF_NIL.to_owned()),
+		}
+	}
+}
+
+impl<V: Vocabulary, T: EmbedIntoVocabulary<V>> EmbedIntoVocabulary<V> for ListPattern<T> {
+	type Embedded = ListPattern<T::Embedded>;
+
+	fn embed_into_vocabulary(self, vocabulary: &mut V) -> Self::Embedded {
+		let items = match self.items {
+			ListItems::Exact(items) => ListItems::Exact(items.embed_into_vocabulary(vocabulary)),
+			ListItems::Member(member) => {
+				ListItems::Member(member.embed_into_vocabulary(vocabulary))
+			}
+		};
+
+		ListPattern {
+			list: self.list.embed_into_vocabulary(vocabulary),
+			items,
+			vocabulary: ListVocabulary {
+				first: self.vocabulary.first.embed_into_vocabulary(vocabulary),
+				rest: self.vocabulary.rest.embed_into_vocabulary(vocabulary),
+				nil: self.vocabulary.nil.embed_into_vocabulary(vocabulary),
+			},
+		}
+	}
+}
+
+impl<T> MapVariables for ListPattern<T> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		self.list.map_variables(f);
+		match &mut self.items {
+			ListItems::Exact(items) => items.map_variables(f),
+			ListItems::Member(member) => member.map_variables(f),
+		}
+	}
+}
+
+impl<T, U> TryMapResources<T, U> for ListPattern<T> {
+	type Output = ListPattern<U>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		let items = match self.items {
+			ListItems::Exact(items) => ListItems::Exact(items.try_map_resources(f)?),
+			ListItems::Member(member) => ListItems::Member(member.try_map_resources(f)?),
+		};
+
+		Ok(ListPattern {
+			list: self.list.try_map_resources(f)?,
+			items,
+			vocabulary: ListVocabulary {
+				first: f(self.vocabulary.first)?,
+				rest: f(self.vocabulary.rest)?,
+				nil: f(self.vocabulary.nil)?,
+			},
+		})
+	}
+}
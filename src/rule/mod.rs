@@ -1,44 +1,436 @@
 //! Deduction rules.
-use std::hash::Hash;
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fmt,
+	hash::Hash,
+};
 
+use educe::Educe;
 use rdf_types::{
 	generator,
-	interpretation::{LiteralInterpretationMut, ReverseTermInterpretation},
-	InterpretationMut, Quad, Term, VocabularyMut,
+	interpretation::{
+		IriInterpretationMut, LiteralInterpretationMut, ReverseTermInterpretation,
+		TermInterpretationMut,
+	},
+	vocabulary::{EmbedIntoVocabulary, ExtractFromVocabulary},
+	InterpretationMut, Term, Vocabulary, VocabularyMut,
 };
 use serde::{Deserialize, Serialize};
 
 mod conclusion;
 mod hypothesis;
+mod list;
+mod universal;
 
 pub use conclusion::*;
 pub use hypothesis::*;
+pub use list::*;
+pub use universal::*;
 
 use crate::{
 	expression,
-	pattern::{ApplyPartialSubstitution, PatternSubstitution, ResourceOrVar, TripleMatching},
-	system::{Deduction, Deductions},
-	utils::IteratorSearch,
-	Entailment, FallibleSignedPatternMatchingDataset, Signed, SignedPatternMatchingDataset,
-	Validation, ValidationError,
+	pattern::{
+		try_conjunctive, try_conjunctive_except, ApplyPartialSubstitution, MapVariables,
+		PatternSubstitution, TryMapResources,
+	},
+	system::{Deduction, Deductions, ViolationCause},
+	Entailment, FallibleSignedPatternMatchingDataset, SignedPatternMatchingDataset, Validation,
+	ValidationError,
 };
 
 /// Deduction rule.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+///
+/// Variable names are ignored by comparison and hashing: rules differing
+/// only by the names of their variables are equal.
+#[derive(Debug, Clone, Educe, Serialize, Deserialize)]
+#[educe(
+	PartialEq(bound),
+	Eq(bound),
+	PartialOrd(bound),
+	Ord(bound),
+	Hash(bound)
+)]
 pub struct Rule<T = Term> {
+	/// Rule identifier, typically an IRI.
+	#[serde(default = "Option::default", skip_serializing_if = "Option::is_none")]
+	pub id: Option<T>,
+
 	pub variables: usize,
 
 	pub hypothesis: Hypothesis<T>,
 
+	/// List patterns of the hypothesis.
+	///
+	/// List patterns are matched after the hypothesis patterns, which must
+	/// bind their list node. They only refine the matches of the hypothesis:
+	/// they do not trigger incremental deduction by themselves.
+	#[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+	pub lists: Vec<ListPattern<T>>,
+
+	/// Universally quantified conditions on the hypothesis.
+	///
+	/// A substitution matching the hypothesis only triggers the rule if it
+	/// satisfies every condition.
+	#[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+	pub universals: Vec<ForAll<T>>,
+
 	pub conclusion: Conclusion<T>,
+
+	/// Severity of a violation of this rule during validation.
+	#[serde(default)]
+	pub severity: Severity,
+
+	/// Profiles in which this rule is active.
+	///
+	/// A rule without profile is active in every profile. See
+	/// [`System::with_profiles`](crate::System::with_profiles).
+	#[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+	pub profiles: BTreeSet<String>,
+
+	/// Priority of the rule.
+	///
+	/// When rules deduce contradictory statements in the same batch of
+	/// deductions, the statement deduced by the rule with the highest
+	/// priority wins. See
+	/// [`DeductionsInstance::resolve_conflicts`](crate::system::DeductionsInstance::resolve_conflicts).
+	#[serde(default, skip_serializing_if = "is_zero")]
+	pub priority: i32,
+
+	/// Variable names, by variable index.
+	///
+	/// Names are only used to display variables, and do not change the
+	/// meaning of the rule.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	#[educe(PartialEq(ignore), PartialOrd(ignore), Ord(ignore), Hash(ignore))]
+	pub names: BTreeMap<usize, String>,
+}
+
+fn is_zero(n: &i32) -> bool {
+	*n == 0
 }
 
 impl<T> Rule<T> {
 	pub fn new(variables: usize, hypothesis: Hypothesis<T>, conclusion: Conclusion<T>) -> Self {
 		Self {
+			id: None,
 			variables,
 			hypothesis,
+			lists: Vec::new(),
+			universals: Vec::new(),
 			conclusion,
+			severity: Severity::default(),
+			profiles: BTreeSet::new(),
+			priority: 0,
+			names: BTreeMap::new(),
+		}
+	}
+
+	/// Sets the identifier of the rule.
+	pub fn with_id(mut self, id: T) -> Self {
+		self.id = Some(id);
+		self
+	}
+
+	/// Adds the given list pattern to the hypothesis.
+	pub fn with_list(mut self, list: ListPattern<T>) -> Self {
+		self.lists.push(list);
+		self
+	}
+
+	/// Adds the given universally quantified condition to the hypothesis.
+	pub fn with_universal(mut self, condition: ForAll<T>) -> Self {
+		self.universals.push(condition);
+		self
+	}
+
+	/// Sets the severity of the rule.
+	pub fn with_severity(mut self, severity: Severity) -> Self {
+		self.severity = severity;
+		self
+	}
+
+	/// Sets the priority of the rule.
+	pub fn with_priority(mut self, priority: i32) -> Self {
+		self.priority = priority;
+		self
+	}
+
+	/// Adds the given profiles to the rule.
+	pub fn with_profiles(mut self, profiles: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		self.profiles.extend(profiles.into_iter().map(Into::into));
+		self
+	}
+
+	/// Names the given variables.
+	pub fn with_variable_names(
+		mut self,
+		names: impl IntoIterator<Item = (usize, impl Into<String>)>,
+	) -> Self {
+		self.names
+			.extend(names.into_iter().map(|(x, name)| (x, name.into())));
+		self
+	}
+
+	/// Returns the given variable, with its name if any.
+	pub fn variable(&self, x: usize) -> Variable<'_> {
+		Variable {
+			index: x,
+			name: self.names.get(&x).map(String::as_str),
+		}
+	}
+
+	/// Checks if the rule is active when any of the given profiles is
+	/// selected.
+	///
+	/// A rule without profile is always active.
+	pub fn is_active_in<P: AsRef<str>>(&self, profiles: &[P]) -> bool {
+		self.profiles.is_empty() || profiles.iter().any(|p| self.profiles.contains(p.as_ref()))
+	}
+}
+
+impl<T: Clone> Rule<T> {
+	/// Returns the canonical form of this rule, where variables are numbered
+	/// in order of first occurrence (hypothesis first, then conclusion).
+	///
+	/// Universal variables are numbered from `0`, and existential variables
+	/// (introduced by the conclusion) from `self.variables`. Two rules
+	/// differing only by the numbering of their variables have the same
+	/// canonical form.
+	pub fn canonicalize(&self) -> Self {
+		let mut map: Vec<Option<usize>> = Vec::new();
+		let mut next_universal = 0;
+		let mut next_existential = self.variables;
+
+		let mut assign = |x: usize| {
+			if x >= map.len() {
+				map.resize(x + 1, None)
+			}
+
+			if map[x].is_none() {
+				let next = if x < self.variables {
+					&mut next_universal
+				} else {
+					&mut next_existential
+				};
+
+				map[x] = Some(*next);
+				*next += 1
+			}
+		};
+
+		self.hypothesis.visit_variables(&mut assign);
+		for l in &self.lists {
+			l.visit_variables(&mut assign)
+		}
+		for u in &self.universals {
+			u.visit_variables(|x| {
+				if x < self.variables {
+					assign(x)
+				}
+			})
+		}
+		self.conclusion.visit_variables(&mut assign);
+		for x in 0..(self.variables + self.conclusion.variables) {
+			assign(x)
+		}
+
+		let mut result = self.clone();
+		result.map_variables(&mut |x| map[x].unwrap());
+		result
+	}
+
+	/// Checks that this rule and `other` are structurally equal, ignoring the
+	/// numbering and names of variables, and the rule identifiers.
+	pub fn is_equivalent(&self, other: &Self) -> bool
+	where
+		T: PartialEq,
+	{
+		let a = self.canonicalize();
+		let b = other.canonicalize();
+		a.variables == b.variables
+			&& a.hypothesis == b.hypothesis
+			&& a.lists == b.lists
+			&& a.universals == b.universals
+			&& a.conclusion == b.conclusion
+			&& a.severity == b.severity
+			&& a.profiles == b.profiles
+			&& a.priority == b.priority
+	}
+}
+
+impl<T> MapVariables for Rule<T> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		self.hypothesis.map_variables(f);
+		self.lists.map_variables(f);
+		for u in &mut self.universals {
+			u.map_rule_variables(self.variables, f)
+		}
+		self.conclusion.map_variables(f);
+		self.names = std::mem::take(&mut self.names)
+			.into_iter()
+			.map(|(x, name)| (f(x), name))
+			.collect()
+	}
+}
+
+impl<T, U> TryMapResources<T, U> for Rule<T> {
+	type Output = Rule<U>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		Ok(Rule {
+			id: self.id.map(&mut *f).transpose()?,
+			variables: self.variables,
+			hypothesis: self.hypothesis.try_map_resources(f)?,
+			lists: self.lists.try_map_resources(f)?,
+			universals: self
+				.universals
+				.into_iter()
+				.map(|u| u.try_map_resources(f))
+				.collect::<Result<_, _>>()?,
+			conclusion: self.conclusion.try_map_resources(f)?,
+			severity: self.severity,
+			profiles: self.profiles,
+			priority: self.priority,
+			names: self.names,
+		})
+	}
+}
+
+impl Rule {
+	/// Interprets the terms of this rule, turning it into a rule over the
+	/// resources of the given interpretation.
+	pub fn interpret<V, I>(self, vocabulary: &mut V, interpretation: &mut I) -> Rule<I::Resource>
+	where
+		V: VocabularyMut,
+		I: TermInterpretationMut<V::Iri, V::BlankId, V::Literal>,
+	{
+		let result: Result<_, std::convert::Infallible> =
+			self.try_map_resources(&mut |term: Term| {
+				Ok(interpretation.interpret_term(term.embed_into_vocabulary(vocabulary)))
+			});
+
+		match result {
+			Ok(rule) => rule,
+			Err(e) => match e {},
+		}
+	}
+}
+
+impl<R> Rule<R> {
+	/// Turns this rule over interpreted resources back into a rule over
+	/// terms.
+	///
+	/// Each resource is replaced by a representative term, preferring IRIs
+	/// over literals, and literals over blank node identifiers. Fails if a
+	/// resource has no term.
+	pub fn uninterpret<V, I>(
+		self,
+		vocabulary: &V,
+		interpretation: &I,
+	) -> Result<Rule, AnonymousResource<R>>
+	where
+		V: Vocabulary,
+		I: ReverseTermInterpretation<
+			Resource = R,
+			Iri = V::Iri,
+			BlankId = V::BlankId,
+			Literal = V::Literal,
+		>,
+	{
+		self.try_map_resources(&mut |r| {
+			representative_term(vocabulary, interpretation, &r).ok_or(AnonymousResource(r))
+		})
+	}
+}
+
+/// Error raised when a resource cannot be represented by a term.
+#[derive(Debug, thiserror::Error)]
+#[error("anonymous resource")]
+pub struct AnonymousResource<R>(pub R);
+
+/// Returns the representative term of the given resource, preferring IRIs
+/// over literals, and literals over blank node identifiers.
+fn representative_term<V, I>(
+	vocabulary: &V,
+	interpretation: &I,
+	resource: &I::Resource,
+) -> Option<Term>
+where
+	V: Vocabulary,
+	I: ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+{
+	if let Some(i) = interpretation.iris_of(resource).next() {
+		return Some(Term::iri(vocabulary.iri(i)?.to_owned()));
+	}
+
+	if let Some(l) = interpretation.literals_of(resource).next() {
+		return Some(Term::Literal(
+			vocabulary.literal(l)?.extract_from_vocabulary(vocabulary),
+		));
+	}
+
+	if let Some(b) = interpretation.blank_ids_of(resource).next() {
+		return Some(Term::blank(vocabulary.blank_id(b)?.to_owned()));
+	}
+
+	None
+}
+
+/// Rule variable.
+///
+/// Displayed as `?name` if the variable is named, or `?_index` otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Variable<'a> {
+	/// Variable index.
+	pub index: usize,
+
+	/// Variable name.
+	pub name: Option<&'a str>,
+}
+
+impl fmt::Display for Variable<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.name {
+			Some(name) => write!(f, "?{name}"),
+			None => write!(f, "?_{}", self.index),
+		}
+	}
+}
+
+/// Rule severity.
+///
+/// Only violations of [`Severity::Error`] rules make a dataset invalid.
+/// Other violations are advisory.
+#[derive(
+	Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+	/// Hard violation.
+	#[default]
+	Error,
+
+	/// Advisory violation.
+	Warning,
+
+	/// Informative violation.
+	Info,
+}
+
+impl Severity {
+	pub fn is_error(&self) -> bool {
+		matches!(self, Self::Error)
+	}
+
+	/// Returns the lowercase name of the severity, as serialized.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Error => "error",
+			Self::Warning => "warning",
+			Self::Info => "info",
 		}
 	}
 }
@@ -49,7 +441,7 @@ impl<T: Clone + Eq + Hash> Rule<T> {
 	/// Returns all the `Deduction` instances representing each substitutions
 	/// satisfying the rule's hypotheses. Each deduction also include the
 	/// partially substituted conclusions.
-	pub fn deduce<D>(&self, dataset: &D) -> Deductions<T>
+	pub fn deduce<D>(&self, dataset: &D) -> Deductions<'_, T>
 	where
 		D: SignedPatternMatchingDataset<Resource = T>,
 	{
@@ -62,7 +454,7 @@ impl<T: Clone + Eq + Hash> Rule<T> {
 	/// Returns all the `Deduction` instances representing each substitutions
 	/// satisfying the rule's hypotheses. Each deduction also include the
 	/// partially substituted conclusions.
-	pub fn try_deduce<D>(&self, dataset: &D) -> Result<Deductions<T>, D::Error>
+	pub fn try_deduce<D>(&self, dataset: &D) -> Result<Deductions<'_, T>, D::Error>
 	where
 		D: FallibleSignedPatternMatchingDataset<Resource = T>,
 	{
@@ -81,30 +473,74 @@ impl<T: Clone + Eq + Hash> Rule<T> {
 		dataset: &D,
 		initial_substitution: PatternSubstitution<T>,
 		excluded_hypothesis: Option<usize>,
-	) -> Result<Deductions<T>, D::Error>
+	) -> Result<Deductions<'_, T>, D::Error>
 	where
 		D: FallibleSignedPatternMatchingDataset<Resource = T>,
 	{
-		let substitutions = self.try_find_substitutions(
-			dataset,
-			&self.hypothesis,
-			initial_substitution,
-			excluded_hypothesis,
-		)?;
-
-		let mut deduction = Deductions::default();
-
-		for substitution in substitutions {
-			let mut d = Deduction::new(Entailment::new(self, substitution.to_vec()));
+		self.try_deductions_from(dataset, initial_substitution, excluded_hypothesis)
+			.collect()
+	}
 
-			for statement in &self.conclusion.statements {
-				d.insert(statement.apply_partial_substitution(&substitution))
-			}
+	/// Lazily deduces triples using this rule against the given dataset.
+	///
+	/// Returns an iterator over the `Deduction` instances representing each
+	/// substitution satisfying the rule's hypotheses. Substitutions are
+	/// searched on demand, so the caller can stop before all of them are
+	/// found.
+	pub fn deductions<'r, 'd, D>(
+		&'r self,
+		dataset: &'d D,
+	) -> impl 'd + Iterator<Item = Deduction<'r, T>>
+	where
+		'r: 'd,
+		D: SignedPatternMatchingDataset<Resource = T>,
+	{
+		self.try_deductions_from(dataset, PatternSubstitution::new(), None)
+			.map(Result::unwrap)
+	}
 
-			deduction.push(d);
-		}
+	/// Lazily deduces triples using this rule against the given dataset.
+	///
+	/// See [`Self::deductions`].
+	pub fn try_deductions<'r, 'd, D>(
+		&'r self,
+		dataset: &'d D,
+	) -> impl 'd + Iterator<Item = Result<Deduction<'r, T>, D::Error>>
+	where
+		'r: 'd,
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		self.try_deductions_from(dataset, PatternSubstitution::new(), None)
+	}
 
-		Ok(deduction)
+	/// Lazily deduces triples using this rule against the given dataset from
+	/// the given `initial_substitution`.
+	///
+	/// See [`Self::try_deduce_from`].
+	pub fn try_deductions_from<'r, 'd, D>(
+		&'r self,
+		dataset: &'d D,
+		initial_substitution: PatternSubstitution<T>,
+		excluded_hypothesis: Option<usize>,
+	) -> impl 'd + Iterator<Item = Result<Deduction<'r, T>, D::Error>>
+	where
+		'r: 'd,
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		try_conjunctive_except(
+			dataset,
+			&self.hypothesis.patterns,
+			initial_substitution,
+			excluded_hypothesis,
+		)
+		.flat_map(move |substitution| match substitution {
+			Ok(substitution) => self.try_match_lists(dataset, substitution),
+			Err(e) => vec![Err(e)],
+		})
+		.filter_map(move |substitution| match substitution {
+			Ok(substitution) => self.try_conclude(dataset, substitution),
+			Err(e) => Some(Err(e)),
+		})
 	}
 
 	/// Validates the given dataset against this rule.
@@ -121,6 +557,7 @@ impl<T: Clone + Eq + Hash> Rule<T> {
 		V: VocabularyMut,
 		V::Iri: PartialEq,
 		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
 			+ LiteralInterpretationMut<V::Literal>
 			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
 		D: SignedPatternMatchingDataset<Resource = T>,
@@ -143,67 +580,119 @@ impl<T: Clone + Eq + Hash> Rule<T> {
 		V: VocabularyMut,
 		V::Iri: PartialEq,
 		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		Ok(
+			match self.try_find_violation_with(vocabulary, interpretation, dataset)? {
+				Some((reason, _)) => Validation::Invalid(reason),
+				None => Validation::Ok,
+			},
+		)
+	}
+
+	/// Finds a violation of this rule in the given dataset.
+	///
+	/// Returns the reason of the violation, with the substitution of the
+	/// violating deduction.
+	pub(crate) fn try_find_violation_with<V, I, D>(
+		&self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		dataset: &D,
+	) -> Result<Option<ViolationCause<T>>, ValidationError<D::Error>>
+	where
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
 			+ LiteralInterpretationMut<V::Literal>
 			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
 		D: FallibleSignedPatternMatchingDataset<Resource = T>,
 	{
-		let deductions = self.try_deduce(dataset).map_err(ValidationError::Dataset)?;
-		if let Validation::Invalid(reason) =
-			deductions.try_validate(vocabulary, interpretation, dataset)?
-		{
-			return Ok(Validation::Invalid(reason));
+		self.try_deduce(dataset)
+			.map_err(ValidationError::Dataset)?
+			.try_find_violation(vocabulary, interpretation, dataset)
+	}
+
+	/// Extends the given substitution with the matches of the list patterns
+	/// of the rule.
+	pub(crate) fn try_match_lists<D>(
+		&self,
+		dataset: &D,
+		substitution: PatternSubstitution<T>,
+	) -> Vec<Result<PatternSubstitution<T>, D::Error>>
+	where
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		let mut substitutions = vec![substitution];
+		for list in &self.lists {
+			let mut next = Vec::new();
+			for s in substitutions {
+				match list.try_matching(dataset, s) {
+					Ok(matches) => next.extend(matches),
+					Err(e) => return vec![Err(e)],
+				}
+			}
+
+			substitutions = next
 		}
 
-		Ok(Validation::Ok)
+		substitutions.into_iter().map(Ok).collect()
 	}
 
-	fn try_find_substitutions<D>(
+	/// Deduces the conclusion of the rule from the given substitution, if it
+	/// satisfies the universally quantified conditions of the rule.
+	///
+	/// The substitution must match the hypothesis and list patterns.
+	pub(crate) fn try_conclude<D>(
 		&self,
 		dataset: &D,
-		hypothesis: &Hypothesis<T>,
-		initial_substitution: PatternSubstitution<T>,
-		excluded_pattern: Option<usize>,
-	) -> Result<Vec<PatternSubstitution<T>>, D::Error>
+		substitution: PatternSubstitution<T>,
+	) -> Option<Result<Deduction<'_, T>, D::Error>>
 	where
 		D: FallibleSignedPatternMatchingDataset<Resource = T>,
 	{
-		let substitutions = {
-			hypothesis
-				.patterns
-				.iter()
-				.enumerate()
-				.filter_map(|(i, pattern)| {
-					if excluded_pattern == Some(i) {
-						None
-					} else {
-						let canonical_pattern = pattern
-							.as_ref()
-							.map(|t| t.as_ref().map(ResourceOrVar::as_ref))
-							.cast();
-
-						Some(dataset.try_signed_pattern_matching(canonical_pattern).map(
-							move |m: Result<Signed<Quad<&T>>, D::Error>| {
-								m.map(|Signed(_, m)| (pattern, m.into_triple().0))
-							},
-						))
-					}
-				})
-				.search(initial_substitution, |substitution, (pattern, m)| {
-					let mut substitution = substitution.clone();
-					if pattern
-						.as_ref()
-						.into_value()
-						.triple_matching(&mut substitution, m)
-					{
-						Some(substitution)
-					} else {
-						None
-					}
-				})
-				.collect::<Result<Vec<_>, _>>()?
-		};
+		match self.try_satisfies_universals(dataset, &substitution) {
+			Ok(true) => {
+				let mut d = Deduction::new(Entailment::new(self, substitution.to_vec()));
+
+				for statement in &self.conclusion.statements {
+					d.insert(statement.apply_partial_substitution(&substitution))
+				}
+
+				Some(Ok(d))
+			}
+			Ok(false) => None,
+			Err(e) => Some(Err(e)),
+		}
+	}
+
+	/// Checks that the given substitution satisfies the universally
+	/// quantified conditions of the rule.
+	fn try_satisfies_universals<D>(
+		&self,
+		dataset: &D,
+		substitution: &PatternSubstitution<T>,
+	) -> Result<bool, D::Error>
+	where
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		for u in &self.universals {
+			for s in try_conjunctive(dataset, &u.premise.patterns, substitution.clone()) {
+				if try_conjunctive(dataset, &u.required.patterns, s?)
+					.next()
+					.transpose()?
+					.is_none()
+				{
+					return Ok(false);
+				}
+			}
+		}
 
-		Ok(substitutions)
+		Ok(true)
 	}
 }
 
@@ -224,6 +713,40 @@ impl Rule {
 	/// Returns `Validation::Ok` if and only if any triple deduced from the
 	/// dataset is already in the dataset.
 	pub fn try_validate<D>(&self, dataset: &D) -> Result<Validation, ValidationError<D::Error>>
+	where
+		D: FallibleSignedPatternMatchingDataset<Resource = Term>,
+	{
+		Ok(match self.try_find_violation(dataset)? {
+			Some((reason, _)) => Validation::Invalid(reason),
+			None => Validation::Ok,
+		})
+	}
+
+	/// Finds a violation of this rule in the given dataset.
+	///
+	/// See [`Self::try_find_violation_with`].
+	pub(crate) fn try_find_violation<D>(
+		&self,
+		dataset: &D,
+	) -> Result<Option<ViolationCause>, ValidationError<D::Error>>
+	where
+		D: FallibleSignedPatternMatchingDataset<Resource = Term>,
+	{
+		let mut interpretation = rdf_types::interpretation::WithGenerator::new(
+			(),
+			generator::Blank::new_with_prefix("inferdf:validation".to_owned()),
+		);
+
+		self.try_find_violation_with(&mut (), &mut interpretation, dataset)
+	}
+
+	/// Finds every violation of this rule in the given dataset.
+	///
+	/// See [`Self::try_find_violation`].
+	pub(crate) fn try_find_violations<D>(
+		&self,
+		dataset: &D,
+	) -> Result<Vec<ViolationCause>, ValidationError<D::Error>>
 	where
 		D: FallibleSignedPatternMatchingDataset<Resource = Term>,
 	{
@@ -232,7 +755,9 @@ impl Rule {
 			generator::Blank::new_with_prefix("inferdf:validation".to_owned()),
 		);
 
-		self.try_validate_with(&mut (), &mut interpretation, dataset)
+		self.try_deduce(dataset)
+			.map_err(ValidationError::Dataset)?
+			.try_find_violations(&mut (), &mut interpretation, dataset)
 	}
 }
 
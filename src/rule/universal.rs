@@ -0,0 +1,88 @@
+use rdf_types::{
+	vocabulary::{EmbedIntoVocabulary, Vocabulary},
+	Term,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	pattern::{MapVariables, TryMapResources},
+	Pattern, Signed,
+};
+
+use super::Hypothesis;
+
+/// Universally quantified hypothesis condition.
+///
+/// The condition holds if every substitution of its local variables matching
+/// the `premise` patterns also matches the `required` patterns. For instance,
+/// it can check that every member of a list has some property.
+///
+/// Local variables are numbered from the number of universal variables of
+/// the rule. Variables of the `required` patterns that do not appear in the
+/// premise are existentially quantified.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ForAll<T = Term> {
+	/// Number of local variables.
+	pub variables: usize,
+
+	/// Patterns selecting the values to check.
+	pub premise: Hypothesis<T>,
+
+	/// Patterns that must be matched for each value.
+	pub required: Hypothesis<T>,
+}
+
+impl<T> ForAll<T> {
+	pub fn new(variables: usize, premise: Hypothesis<T>, required: Hypothesis<T>) -> Self {
+		Self {
+			variables,
+			premise,
+			required,
+		}
+	}
+
+	pub fn visit_variables(&self, mut f: impl FnMut(usize)) {
+		self.premise.visit_variables(&mut f);
+		self.required.visit_variables(&mut f)
+	}
+
+	/// Maps the variables of the rule (lower than `offset`), leaving the local
+	/// variables untouched.
+	pub(crate) fn map_rule_variables(&mut self, offset: usize, f: &mut impl FnMut(usize) -> usize) {
+		let mut g = |x| if x < offset { f(x) } else { x };
+		self.premise.map_variables(&mut g);
+		self.required.map_variables(&mut g)
+	}
+
+	/// Returns the patterns of the condition, premise first.
+	pub(crate) fn patterns(&self) -> impl Iterator<Item = &Signed<Pattern<T>>> {
+		self.premise.patterns.iter().chain(&self.required.patterns)
+	}
+}
+
+impl<V: Vocabulary, T: EmbedIntoVocabulary<V>> EmbedIntoVocabulary<V> for ForAll<T> {
+	type Embedded = ForAll<T::Embedded>;
+
+	fn embed_into_vocabulary(self, vocabulary: &mut V) -> Self::Embedded {
+		ForAll {
+			variables: self.variables,
+			premise: self.premise.embed_into_vocabulary(vocabulary),
+			required: self.required.embed_into_vocabulary(vocabulary),
+		}
+	}
+}
+
+impl<T, U> TryMapResources<T, U> for ForAll<T> {
+	type Output = ForAll<U>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		Ok(ForAll {
+			variables: self.variables,
+			premise: self.premise.try_map_resources(f)?,
+			required: self.required.try_map_resources(f)?,
+		})
+	}
+}
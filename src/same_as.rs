@@ -0,0 +1,181 @@
+//! Resource identities as `owl:sameAs` and `owl:differentFrom` links.
+//!
+//! The [`Identities`] type records which terms denote the same resource (and
+//! which do not), and can be exported to or imported from RDF triples using
+//! the `owl:sameAs` and `owl:differentFrom` properties.
+use std::collections::BTreeMap;
+
+use iref::Iri;
+use rdf_types::{Term, Triple};
+use static_iref::iri;
+
+use crate::utils::UnionFind;
+
+/// `owl:sameAs` property.
+pub const OWL_SAME_AS: &Iri = iri!("http://www.w3.org/2002/07/owl#sameAs");
+
+/// `owl:differentFrom` property.
+pub const OWL_DIFFERENT_FROM: &Iri = iri!("http://www.w3.org/2002/07/owl#differentFrom");
+
+/// Error raised when two terms are declared both equal and different.
+#[derive(Debug, thiserror::Error)]
+#[error("contradictory identity: {0} and {1} are both equal and different")]
+pub struct Contradiction(pub Term, pub Term);
+
+/// Term identities.
+#[derive(Debug, Clone, Default)]
+pub struct Identities {
+	/// Classes of terms denoting the same resource.
+	classes: UnionFind<Term>,
+
+	/// Pairs of terms denoting different resources.
+	different: Vec<(Term, Term)>,
+}
+
+impl Identities {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the representative term of the given term.
+	///
+	/// The representative is an arbitrary, but fixed, member of the term's
+	/// class.
+	pub fn representative<'a>(&'a self, term: &'a Term) -> &'a Term {
+		self.classes.find(term)
+	}
+
+	/// Returns the number of terms merged into another one, that is the
+	/// number of terms that are not the representative of their class.
+	pub fn merged(&self) -> usize {
+		self.classes.iter().count()
+	}
+
+	/// Checks if the given terms denote the same resource.
+	pub fn are_same(&self, a: &Term, b: &Term) -> bool {
+		self.classes.are_merged(a, b)
+	}
+
+	/// Checks if the given terms are known to denote different resources.
+	pub fn are_different(&self, a: &Term, b: &Term) -> bool {
+		let a = self.representative(a);
+		let b = self.representative(b);
+		self.different.iter().any(|(x, y)| {
+			let x = self.representative(x);
+			let y = self.representative(y);
+			(x == a && y == b) || (x == b && y == a)
+		})
+	}
+
+	/// Declares that the given terms denote the same resource.
+	///
+	/// Fails, leaving the identities unchanged, if this contradicts a
+	/// previous `differentFrom` declaration.
+	pub fn merge(&mut self, a: &Term, b: &Term) -> Result<(), Contradiction> {
+		let mut classes = self.classes.clone();
+		classes.merge(a, b);
+
+		if let Some((x, y)) = self
+			.different
+			.iter()
+			.find(|(x, y)| classes.are_merged(x, y))
+		{
+			return Err(Contradiction(x.clone(), y.clone()));
+		}
+
+		self.classes = classes;
+		Ok(())
+	}
+
+	/// Declares that the given terms denote different resources.
+	///
+	/// Fails if the terms are already known to denote the same resource.
+	pub fn separate(&mut self, a: &Term, b: &Term) -> Result<(), Contradiction> {
+		if self.are_same(a, b) {
+			return Err(Contradiction(a.clone(), b.clone()));
+		}
+
+		if !self.are_different(a, b) {
+			self.different.push((a.clone(), b.clone()))
+		}
+
+		Ok(())
+	}
+
+	/// Replaces every term of the given triple by its representative.
+	pub fn canonicalize(&self, Triple(s, p, o): Triple) -> Triple {
+		Triple(
+			self.representative(&s).clone(),
+			self.representative(&p).clone(),
+			self.representative(&o).clone(),
+		)
+	}
+
+	/// Imports the given `owl:sameAs` or `owl:differentFrom` triple.
+	///
+	/// Returns `false` if the triple is not an identity link, in which case
+	/// it is ignored.
+	pub fn insert_triple(&mut self, Triple(s, p, o): &Triple) -> Result<bool, Contradiction> {
+		match p.as_iri() {
+			Some(iri) if iri == OWL_SAME_AS => self.merge(s, o).map(|_| true),
+			Some(iri) if iri == OWL_DIFFERENT_FROM => self.separate(s, o).map(|_| true),
+			_ => Ok(false),
+		}
+	}
+
+	/// Exports the identities as RDF triples.
+	///
+	/// Each class of equal terms is represented by its preferred term (IRIs
+	/// and literals over blank node identifiers, then the smallest term), to
+	/// which every other term of the class is linked with `owl:sameAs`.
+	/// Representatives of different resources are linked with
+	/// `owl:differentFrom`.
+	pub fn triples(&self) -> Vec<Triple> {
+		let same_as = Term::iri(OWL_SAME_AS.to_owned());
+		let different_from = Term::iri(OWL_DIFFERENT_FROM.to_owned());
+
+		let mut classes: BTreeMap<&Term, Vec<&Term>> = BTreeMap::new();
+		for (t, r) in self.classes.iter() {
+			classes.entry(r).or_insert_with(|| vec![r]).push(t)
+		}
+
+		let preferred: BTreeMap<&Term, &Term> = classes
+			.iter()
+			.map(|(r, members)| {
+				let p = members
+					.iter()
+					.copied()
+					.min_by_key(|t| (t.is_blank(), *t))
+					.unwrap();
+				(*r, p)
+			})
+			.collect();
+
+		let preferred_of = |t: &Term| -> Term {
+			let r = self.representative(t);
+			preferred.get(r).copied().unwrap_or(r).clone()
+		};
+
+		let mut result = Vec::new();
+		for (r, members) in &classes {
+			let p = preferred[r];
+			for t in members {
+				if *t != p {
+					result.push(Triple((*t).clone(), same_as.clone(), p.clone()))
+				}
+			}
+		}
+
+		for (a, b) in &self.different {
+			result.push(Triple(
+				preferred_of(a),
+				different_from.clone(),
+				preferred_of(b),
+			))
+		}
+
+		result.sort_unstable();
+		result.dedup();
+		result
+	}
+}
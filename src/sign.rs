@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "paged")]
 use paged::Paged;
 
-use crate::pattern::{ApplyPartialSubstitution, ApplySubstitution};
+use crate::pattern::{ApplyPartialSubstitution, ApplySubstitution, MapVariables, TryMapResources};
 
 /// Signed (positive or negative) value.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -120,6 +120,23 @@ impl<T, U: ApplyPartialSubstitution<T>> ApplyPartialSubstitution<T> for Signed<U
 	}
 }
 
+impl<T, U, A: TryMapResources<T, U>> TryMapResources<T, U> for Signed<A> {
+	type Output = Signed<A::Output>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		Ok(Signed(self.0, self.1.try_map_resources(f)?))
+	}
+}
+
+impl<A: MapVariables> MapVariables for Signed<A> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		self.1.map_variables(f)
+	}
+}
+
 /// Sign (positive or negative).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "paged", derive(Paged))]
@@ -139,6 +156,9 @@ impl Sign {
 }
 
 /// One value for each sign (positive and negative).
+///
+/// A pair of datasets is a signed dataset, storing the positive and negative
+/// quads separately.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Bipolar<T> {
 	pub positive: T,
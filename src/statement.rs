@@ -3,7 +3,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
 	expression::{Eval, Instantiate},
-	pattern::{ApplyPartialSubstitution, ApplySubstitution, PatternSubstitution},
+	pattern::{
+		ApplyPartialSubstitution, ApplySubstitution, MapVariables, PatternSubstitution,
+		TryMapResources,
+	},
 };
 
 /// Triple statement found in a deduction rule conclusion.
@@ -59,6 +62,37 @@ impl<T, U: ApplyPartialSubstitution<T>> ApplyPartialSubstitution<T> for TripleSt
 	}
 }
 
+impl<T, U, A: TryMapResources<T, U>> TryMapResources<T, U> for TripleStatement<A> {
+	type Output = TripleStatement<A::Output>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		match self {
+			Self::Triple(t) => Ok(TripleStatement::Triple(t.try_map_resources(f)?)),
+			Self::Eq(a, b) => Ok(TripleStatement::Eq(
+				a.try_map_resources(f)?,
+				b.try_map_resources(f)?,
+			)),
+			Self::True(r) => Ok(TripleStatement::True(r.try_map_resources(f)?)),
+		}
+	}
+}
+
+impl<A: MapVariables> MapVariables for TripleStatement<A> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		match self {
+			Self::Triple(t) => t.map_variables(f),
+			Self::Eq(a, b) => {
+				a.map_variables(f);
+				b.map_variables(f)
+			}
+			Self::True(r) => r.map_variables(f),
+		}
+	}
+}
+
 impl<'e, V, I, T: Eval<'e, V, I>> Eval<'e, V, I> for TripleStatement<T> {
 	type Output = TripleStatement<T::Output>;
 
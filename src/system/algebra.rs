@@ -0,0 +1,95 @@
+use std::hash::Hash;
+
+use super::{Rule, System};
+
+/// Error raised when two systems define the same rule identifier
+/// differently.
+#[derive(Debug, thiserror::Error)]
+#[error("conflicting rule definitions")]
+pub struct RuleConflict<T> {
+	/// Conflicting rule identifier.
+	pub id: T,
+
+	/// Index of the rule in the left-hand system.
+	pub left: usize,
+
+	/// Index of the rule in the right-hand system.
+	pub right: usize,
+}
+
+/// Returns the structural key of a rule: its canonical form without
+/// identifier.
+fn structural_key<T: Clone>(rule: &Rule<T>) -> Rule<T> {
+	let mut key = rule.canonicalize();
+	key.id = None;
+	key
+}
+
+impl<T: Clone + Eq + Hash> System<T> {
+	/// Returns the structural keys of the rules of this system, collected
+	/// into a system so they are looked up through its rule index.
+	fn structural_keys(&self) -> Self {
+		self.rules.iter().map(structural_key).collect()
+	}
+
+	/// Checks if the system contains a rule equivalent to the given one (see
+	/// [`Rule::is_equivalent`]).
+	pub fn contains_equivalent(&self, rule: &Rule<T>) -> bool {
+		self.rules.iter().any(|r| r.is_equivalent(rule))
+	}
+
+	/// Returns the union of this system with `other`.
+	///
+	/// Rules of `other` equivalent to a rule of `self` are not duplicated.
+	/// Fails if both systems give different definitions to the same rule
+	/// identifier.
+	pub fn union(&self, other: &Self) -> Result<Self, RuleConflict<T>> {
+		let mut result: Self = self.rules.iter().cloned().collect();
+		let mut keys = self.structural_keys();
+
+		for (j, rule) in other.rules.iter().enumerate() {
+			if let Some(id) = &rule.id {
+				if let Some(i) = self.index_of(id) {
+					if self.rules[i].is_equivalent(rule) {
+						continue;
+					}
+
+					return Err(RuleConflict {
+						id: id.clone(),
+						left: i,
+						right: j,
+					});
+				}
+			}
+
+			let len = keys.len();
+			if keys.insert(structural_key(rule)) == len {
+				result.insert(rule.clone());
+			}
+		}
+
+		Ok(result)
+	}
+
+	/// Returns the rules of this system that have an equivalent rule in
+	/// `other`.
+	pub fn intersection(&self, other: &Self) -> Self {
+		let keys = other.structural_keys();
+		self.rules
+			.iter()
+			.filter(|r| keys.index_of_rule(&structural_key(r)).is_some())
+			.cloned()
+			.collect()
+	}
+
+	/// Returns the rules of this system that have no equivalent rule in
+	/// `other`.
+	pub fn difference(&self, other: &Self) -> Self {
+		let keys = other.structural_keys();
+		self.rules
+			.iter()
+			.filter(|r| keys.index_of_rule(&structural_key(r)).is_none())
+			.cloned()
+			.collect()
+	}
+}
@@ -0,0 +1,723 @@
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+	time::{Duration, Instant},
+};
+
+use rdf_types::{
+	dataset::IndexedBTreeDataset, generator, interpretation::WithGenerator, Quad, Term, Triple,
+};
+
+use crate::{
+	expression,
+	same_as::{Contradiction, Identities},
+	Entailment, Sign, Signed, TripleStatement, GENERATED_BLANK_ID_PREFIX,
+};
+
+use super::{DeductionsInstance, Firing, System};
+
+/// Closure limits.
+///
+/// Closure may not terminate on some rule sets, for instance when
+/// existential rules generate infinite chains of fresh resources. These
+/// limits interrupt the closure before it runs away. By default, no limit is
+/// set.
+#[derive(Debug, Default, Clone)]
+pub struct ClosureLimits {
+	/// Maximum number of deduction rounds.
+	///
+	/// The first round deduces facts from the whole dataset, each following
+	/// round deduces facts from the triples inserted by the previous one.
+	pub max_iterations: Option<usize>,
+
+	/// Maximum number of inserted triples.
+	pub max_facts: Option<usize>,
+
+	/// Maximum number of fresh resources generated for existential
+	/// variables.
+	pub max_new_resources: Option<usize>,
+
+	/// Maximum duration of the closure.
+	pub timeout: Option<Duration>,
+
+	/// Cancellation token.
+	pub cancellation: Option<CancellationToken>,
+}
+
+/// Cooperative cancellation token.
+///
+/// Clones share the same state: cancelling one clone cancels them all.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Requests the cancellation.
+	pub fn cancel(&self) {
+		self.0.store(true, Ordering::Relaxed)
+	}
+
+	pub fn is_cancelled(&self) -> bool {
+		self.0.load(Ordering::Relaxed)
+	}
+}
+
+/// Reason why a closure was interrupted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interruption {
+	/// Maximum number of deduction rounds reached.
+	MaxIterations,
+
+	/// Maximum number of inserted triples reached.
+	MaxFacts,
+
+	/// Maximum number of fresh resources reached.
+	MaxNewResources,
+
+	/// Timeout reached.
+	Timeout,
+
+	/// Closure cancelled.
+	Cancelled,
+}
+
+/// Interrupted closure.
+///
+/// Triples inserted before the interruption are left in the dataset.
+#[derive(Debug, thiserror::Error)]
+#[error("closure interrupted ({reason:?}) after inserting {inserted} triples")]
+pub struct ClosureInterrupted {
+	/// Interruption reason.
+	pub reason: Interruption,
+
+	/// Number of triples inserted before the interruption.
+	pub inserted: usize,
+}
+
+/// Closure policy regarding named graphs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GraphPolicy {
+	/// Rules match the triples of every graph together, and deduced triples
+	/// are inserted in the default graph.
+	#[default]
+	Union,
+
+	/// Each graph is closed separately: rules only match triples of the same
+	/// graph, and deduced triples are inserted in that graph.
+	PerGraph,
+
+	/// Only the default graph is closed. Named graphs are neither matched
+	/// nor modified.
+	DefaultGraph,
+}
+
+/// Contradiction between a positive and a negative fact, recorded by a
+/// paraconsistent closure.
+///
+/// See [`System::close_paraconsistent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dispute<'r> {
+	/// Disputed triple.
+	pub triple: Triple,
+
+	/// Whether the triple is stated by the input dataset.
+	pub stated: bool,
+
+	/// Entailments deducing the triple.
+	pub positive: Vec<Entailment<'r, Term>>,
+
+	/// Entailments deducing the negation of the triple.
+	pub negative: Vec<Entailment<'r, Term>>,
+}
+
+/// Result of a paraconsistent closure.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ParaconsistentClosure<'r> {
+	/// Number of inserted triples.
+	pub inserted: usize,
+
+	/// Disputed triples, in order.
+	pub disputes: Vec<Dispute<'r>>,
+}
+
+impl ParaconsistentClosure<'_> {
+	/// Checks that no triple is disputed.
+	pub fn is_consistent(&self) -> bool {
+		self.disputes.is_empty()
+	}
+}
+
+/// Signed facts deduced by a closure run, used to detect disputes.
+#[derive(Default)]
+struct DisputeTracker<'a> {
+	/// Triples excluded from the closure.
+	disputed: BTreeSet<Triple>,
+
+	/// Entailments deducing disputed triples.
+	positive: BTreeMap<Triple, Vec<Entailment<'a, Term>>>,
+
+	/// Entailments deducing negative triples.
+	negative: BTreeMap<Triple, Vec<Entailment<'a, Term>>>,
+}
+
+impl<'a> DisputeTracker<'a> {
+	/// Records the deduced negative triples, and removes the disputed
+	/// positive triples from the deductions.
+	fn filter(&mut self, deductions: &mut DeductionsInstance<'a>) {
+		for deduction in &mut deductions.0 {
+			let entailment = &deduction.entailment;
+			deduction.statements.retain(|statement| match statement {
+				Signed(Sign::Negative, TripleStatement::Triple(t)) => {
+					push_cause(&mut self.negative, t, entailment);
+					true
+				}
+				Signed(Sign::Positive, TripleStatement::Triple(t)) if self.disputed.contains(t) => {
+					push_cause(&mut self.positive, t, entailment);
+					false
+				}
+				_ => true,
+			})
+		}
+	}
+}
+
+fn push_cause<'a>(
+	map: &mut BTreeMap<Triple, Vec<Entailment<'a, Term>>>,
+	triple: &Triple,
+	entailment: &Entailment<'a, Term>,
+) {
+	let causes = map.entry(triple.clone()).or_default();
+	if !causes.contains(entailment) {
+		causes.push(entailment.clone())
+	}
+}
+
+/// Closure error.
+#[derive(Debug, thiserror::Error)]
+pub enum ClosureError {
+	#[error(transparent)]
+	Expression(#[from] expression::Error),
+
+	#[error(transparent)]
+	Interrupted(#[from] ClosureInterrupted),
+
+	#[error(transparent)]
+	Contradiction(#[from] Contradiction),
+}
+
+impl System {
+	/// Computes the closure of the given dataset under this system, inserting
+	/// every deduced positive triple into the dataset.
+	///
+	/// In each deduction round, contradictory statements are settled using
+	/// rule priorities (see [`DeductionsInstance::resolve_conflicts`]): a
+	/// positive triple overridden by a negative conclusion of higher priority
+	/// is not inserted.
+	///
+	/// Returns the number of inserted triples.
+	pub fn close(&self, dataset: &mut IndexedBTreeDataset) -> Result<usize, expression::Error> {
+		self.close_observed(dataset, |_, _| ())
+	}
+
+	/// Computes the closure of the given dataset under this system, inserting
+	/// every deduced positive triple into the dataset.
+	///
+	/// The `on_new_fact` function is called once for each inserted triple,
+	/// with the entailment that first deduced it, as soon as it is inserted.
+	///
+	/// Returns the number of inserted triples.
+	pub fn close_observed(
+		&self,
+		dataset: &mut IndexedBTreeDataset,
+		on_new_fact: impl FnMut(&Triple, &Entailment<Term>),
+	) -> Result<usize, expression::Error> {
+		match self.close_bounded(dataset, &ClosureLimits::default(), on_new_fact) {
+			Ok(count) => Ok(count),
+			Err(ClosureError::Expression(e)) => Err(e),
+			Err(ClosureError::Interrupted(_) | ClosureError::Contradiction(_)) => unreachable!(),
+		}
+	}
+
+	/// Computes the closure of the given dataset under this system within
+	/// the given limits, inserting every deduced positive triple into the
+	/// dataset.
+	///
+	/// The `on_new_fact` function is called once for each inserted triple,
+	/// with the entailment that first deduced it, as soon as it is inserted.
+	///
+	/// Returns the number of inserted triples, or a
+	/// [`ClosureError::Interrupted`] error if a limit is reached.
+	pub fn close_bounded(
+		&self,
+		dataset: &mut IndexedBTreeDataset,
+		limits: &ClosureLimits,
+		on_new_fact: impl FnMut(&Triple, &Entailment<Term>),
+	) -> Result<usize, ClosureError> {
+		let mut blanks = blank_generator(dataset);
+		self.close_inner(
+			dataset,
+			limits,
+			None,
+			Observers::default(),
+			&mut blanks,
+			on_new_fact,
+		)
+	}
+
+	/// Computes the closure of the given dataset under this system within
+	/// the given limits, reporting each rule firing to `trace`.
+	///
+	/// A rule fires once for each substitution matching its hypothesis,
+	/// whether or not the produced statements are new. Combined with a
+	/// [`JsonLinesTrace`](super::JsonLinesTrace), this gives a structured log
+	/// of the closure.
+	///
+	/// Returns the number of inserted triples.
+	pub fn close_traced(
+		&self,
+		dataset: &mut IndexedBTreeDataset,
+		limits: &ClosureLimits,
+		mut trace: impl FnMut(&Firing),
+	) -> Result<usize, ClosureError> {
+		let mut blanks = blank_generator(dataset);
+		self.close_inner(
+			dataset,
+			limits,
+			None,
+			Observers {
+				trace: Some(&mut trace),
+				..Default::default()
+			},
+			&mut blanks,
+			|_, _| (),
+		)
+	}
+
+	/// Computes the closure of the given dataset under this system within
+	/// the given limits, taking deduced equalities into account.
+	///
+	/// Deduced equality and inequality statements are recorded in
+	/// `identities`. Every term of the dataset and of the deduced triples is
+	/// replaced by its representative, so that equal resources are merged.
+	///
+	/// Returns the number of inserted triples, or a
+	/// [`ClosureError::Contradiction`] error if the deduced identities are
+	/// contradictory.
+	pub fn close_with_identities(
+		&self,
+		dataset: &mut IndexedBTreeDataset,
+		identities: &mut Identities,
+		limits: &ClosureLimits,
+	) -> Result<usize, ClosureError> {
+		let mut blanks = blank_generator(dataset);
+		self.close_inner(
+			dataset,
+			limits,
+			Some(identities),
+			Observers::default(),
+			&mut blanks,
+			|_, _| (),
+		)
+	}
+
+	/// Computes the closure of the given dataset under this system within
+	/// the given limits, following the given graph policy.
+	///
+	/// With [`GraphPolicy::PerGraph`], the limits apply to the closure of
+	/// each graph separately.
+	///
+	/// Returns the total number of inserted triples.
+	pub fn close_graphs(
+		&self,
+		dataset: &mut IndexedBTreeDataset,
+		policy: GraphPolicy,
+		limits: &ClosureLimits,
+	) -> Result<usize, ClosureError> {
+		if policy == GraphPolicy::Union {
+			return self.close_bounded(dataset, limits, |_, _| ());
+		}
+
+		// Blank nodes are shared between the graphs.
+		let mut blanks = blank_generator(dataset);
+		let mut graphs: BTreeMap<Option<Term>, IndexedBTreeDataset> = BTreeMap::new();
+		for Quad(s, p, o, g) in std::mem::take(dataset) {
+			graphs.entry(g).or_default().insert(Quad(s, p, o, None));
+		}
+
+		let mut count = 0;
+		let mut result = Ok(());
+		for (g, graph) in &mut graphs {
+			if result.is_ok() && (g.is_none() || policy == GraphPolicy::PerGraph) {
+				match self.close_inner(
+					graph,
+					limits,
+					None,
+					Observers::default(),
+					&mut blanks,
+					|_, _| (),
+				) {
+					Ok(n) => count += n,
+					Err(e) => result = Err(e),
+				}
+			}
+		}
+
+		for (g, graph) in graphs {
+			for Quad(s, p, o, _) in graph {
+				dataset.insert(Quad(s, p, o, g.clone()));
+			}
+		}
+
+		result.map(|()| count)
+	}
+
+	/// Computes the closure of the given dataset under this system within
+	/// the given limits, tolerating contradictions between signed facts.
+	///
+	/// A triple is disputed when it is both in the closure (stated or
+	/// deduced) and deduced negatively. Disputed triples are removed from the
+	/// dataset and never inserted, and the closure is computed again from
+	/// the remaining facts, until no new dispute arises. The resulting
+	/// dataset is the closure of the consistent subset of the facts.
+	///
+	/// Returns the number of triples inserted by the final closure, and the
+	/// disputed triples with the entailments of the final closure deducing
+	/// them positively and negatively.
+	pub fn close_paraconsistent(
+		&self,
+		dataset: &mut IndexedBTreeDataset,
+		limits: &ClosureLimits,
+	) -> Result<ParaconsistentClosure<'_>, ClosureError> {
+		let input = std::mem::take(dataset);
+		let mut disputed: BTreeSet<Triple> = BTreeSet::new();
+
+		loop {
+			*dataset = input.clone();
+			for t in &disputed {
+				dataset.remove(t.as_ref().into_quad(None));
+			}
+
+			let mut tracker = DisputeTracker {
+				disputed: disputed.clone(),
+				..Default::default()
+			};
+
+			let mut blanks = blank_generator(dataset);
+			let inserted = self.close_inner(
+				dataset,
+				limits,
+				None,
+				Observers {
+					tracker: Some(&mut tracker),
+					..Default::default()
+				},
+				&mut blanks,
+				|_, _| (),
+			)?;
+
+			let mut changed = false;
+			for t in tracker.negative.keys() {
+				if dataset.contains(t.as_ref().into_quad(None)) {
+					disputed.insert(t.clone());
+					changed = true
+				}
+			}
+
+			if !changed {
+				let disputes = disputed
+					.into_iter()
+					.map(|triple| Dispute {
+						stated: input.contains(triple.as_ref().into_quad(None)),
+						positive: tracker.positive.remove(&triple).unwrap_or_default(),
+						negative: tracker.negative.remove(&triple).unwrap_or_default(),
+						triple,
+					})
+					.collect();
+
+				break Ok(ParaconsistentClosure { inserted, disputes });
+			}
+		}
+	}
+
+	fn close_inner<'a>(
+		&'a self,
+		dataset: &mut IndexedBTreeDataset,
+		limits: &ClosureLimits,
+		mut identities: Option<&mut Identities>,
+		observers: Observers<'a, '_>,
+		blanks: &mut generator::Blank,
+		mut on_new_fact: impl FnMut(&Triple, &Entailment<Term>),
+	) -> Result<usize, ClosureError> {
+		let Observers {
+			mut tracker,
+			mut trace,
+		} = observers;
+		if let Some(identities) = identities.as_deref() {
+			rewrite(dataset, identities, &mut []);
+		}
+
+		let start = Instant::now();
+		let offset = blanks.count();
+		let mut interpretation = WithGenerator::new((), blanks);
+
+		let mut count = 0;
+		let mut next = Vec::new();
+		check_interruption(limits, start, count)?;
+		// Duplicate deductions are dropped so that existential conclusions
+		// are only instantiated once.
+		let mut deductions = self.deduce(dataset);
+		deductions.dedup(self);
+		let mut deductions = deductions.eval_with(&mut (), &mut interpretation)?;
+		check_resources(limits, interpretation.generator().count() - offset, count)?;
+		// Contradictions between rules of different priorities are settled
+		// before being tracked or inserted.
+		deductions.resolve_conflicts();
+		if let Some(tracker) = tracker.as_deref_mut() {
+			tracker.filter(&mut deductions)
+		}
+		if let Some(trace) = trace.as_deref_mut() {
+			emit(trace, &deductions, 1, start)
+		}
+		insert(
+			dataset,
+			deductions,
+			limits,
+			identities.as_deref_mut(),
+			&mut count,
+			&mut next,
+			&mut on_new_fact,
+		)?;
+
+		let mut iterations = 1;
+		while !next.is_empty() {
+			if limits.max_iterations.is_some_and(|max| iterations >= max) {
+				return Err(interrupted(Interruption::MaxIterations, count));
+			}
+
+			iterations += 1;
+
+			for triple in std::mem::take(&mut next) {
+				check_interruption(limits, start, count)?;
+
+				let mut deductions =
+					self.deduce_from_triple(dataset, Signed(Sign::Positive, triple.as_ref()));
+				deductions.dedup(self);
+				let mut deductions = deductions.eval_with(&mut (), &mut interpretation)?;
+				check_resources(limits, interpretation.generator().count() - offset, count)?;
+				deductions.resolve_conflicts();
+				if let Some(tracker) = tracker.as_deref_mut() {
+					tracker.filter(&mut deductions)
+				}
+				if let Some(trace) = trace.as_deref_mut() {
+					emit(trace, &deductions, iterations, start)
+				}
+				insert(
+					dataset,
+					deductions,
+					limits,
+					identities.as_deref_mut(),
+					&mut count,
+					&mut next,
+					&mut on_new_fact,
+				)?;
+			}
+		}
+
+		Ok(count)
+	}
+}
+
+/// Optional observers of the deductions of a closure.
+#[derive(Default)]
+struct Observers<'a, 'b> {
+	/// Tracks and filters out disputed deductions.
+	tracker: Option<&'b mut DisputeTracker<'a>>,
+
+	/// Called on each rule firing.
+	trace: Option<&'b mut dyn FnMut(&Firing)>,
+}
+
+/// Returns a generator for the blank node identifiers of existential
+/// conclusions, numbered after the generated identifiers already used in the
+/// given dataset.
+///
+/// Closing a dataset again, or speculating on a closed dataset, hence never
+/// reuses a blank node generated by a previous closure.
+pub(crate) fn blank_generator(dataset: &IndexedBTreeDataset) -> generator::Blank {
+	let prefix = format!("_:{GENERATED_BLANK_ID_PREFIX}");
+	let offset = dataset
+		.iter()
+		.flat_map(|Quad(s, p, o, g)| [Some(s), Some(p), Some(o), g])
+		.flatten()
+		.filter_map(|term| {
+			term.as_blank()?
+				.as_str()
+				.strip_prefix(&prefix)?
+				.parse::<usize>()
+				.ok()
+		})
+		.max()
+		.map_or(0, |n| n + 1);
+
+	generator::Blank::new_full(GENERATED_BLANK_ID_PREFIX.to_owned(), offset)
+}
+
+/// Emits a firing event for each of the given deductions.
+fn emit(
+	trace: &mut dyn FnMut(&Firing),
+	deductions: &DeductionsInstance,
+	iteration: usize,
+	start: Instant,
+) {
+	let elapsed = start.elapsed();
+	for deduction in deductions.iter() {
+		trace(&Firing {
+			iteration,
+			elapsed,
+			deduction,
+		})
+	}
+}
+
+fn interrupted(reason: Interruption, inserted: usize) -> ClosureError {
+	ClosureError::Interrupted(ClosureInterrupted { reason, inserted })
+}
+
+/// Checks if the closure has been cancelled or has timed out.
+fn check_interruption(
+	limits: &ClosureLimits,
+	start: Instant,
+	inserted: usize,
+) -> Result<(), ClosureError> {
+	if limits
+		.cancellation
+		.as_ref()
+		.is_some_and(CancellationToken::is_cancelled)
+	{
+		return Err(interrupted(Interruption::Cancelled, inserted));
+	}
+
+	if limits.timeout.is_some_and(|t| start.elapsed() >= t) {
+		return Err(interrupted(Interruption::Timeout, inserted));
+	}
+
+	Ok(())
+}
+
+fn check_resources(
+	limits: &ClosureLimits,
+	resources: usize,
+	inserted: usize,
+) -> Result<(), ClosureError> {
+	if limits.max_new_resources.is_some_and(|max| resources > max) {
+		Err(interrupted(Interruption::MaxNewResources, inserted))
+	} else {
+		Ok(())
+	}
+}
+
+/// Inserts the positive triples of the given deductions into the dataset.
+///
+/// If `identities` is given, deduced equalities are recorded first, and
+/// the dataset is rewritten if they changed.
+fn insert(
+	dataset: &mut IndexedBTreeDataset,
+	deductions: DeductionsInstance,
+	limits: &ClosureLimits,
+	identities: Option<&mut Identities>,
+	count: &mut usize,
+	inserted: &mut Vec<Triple>,
+	on_new_fact: &mut impl FnMut(&Triple, &Entailment<Term>),
+) -> Result<(), ClosureError> {
+	let identities = match identities {
+		Some(identities) => {
+			if deductions.apply_equalities(identities)? {
+				// Rewritten triples are caused by the deduced equalities.
+				let cause = deductions
+					.iter()
+					.find(|d| d.equalities().next().is_some())
+					.map(|d| d.entailment.clone());
+
+				for t in rewrite(dataset, identities, inserted) {
+					if limits.max_facts.is_some_and(|max| *count >= max) {
+						return Err(interrupted(Interruption::MaxFacts, *count));
+					}
+
+					if let Some(cause) = &cause {
+						on_new_fact(&t, cause)
+					}
+
+					inserted.push(t);
+					*count += 1
+				}
+			}
+
+			Some(&*identities)
+		}
+		None => None,
+	};
+
+	for deduction in deductions {
+		for t in deduction.positive_triples() {
+			let t = match identities {
+				Some(identities) => identities.canonicalize(t.clone()),
+				None => t.clone(),
+			};
+
+			if !dataset.contains(t.as_ref().into_quad(None)) {
+				if limits.max_facts.is_some_and(|max| *count >= max) {
+					return Err(interrupted(Interruption::MaxFacts, *count));
+				}
+
+				dataset.insert(t.clone().into_quad(None));
+				on_new_fact(&t, &deduction.entailment);
+				inserted.push(t);
+				*count += 1
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Replaces every term of the dataset, and of the pending `inserted`
+/// triples, by its representative.
+///
+/// Returns the rewritten triples that are new facts of the dataset.
+fn rewrite(
+	dataset: &mut IndexedBTreeDataset,
+	identities: &Identities,
+	inserted: &mut [Triple],
+) -> Vec<Triple> {
+	let mut rewritten = Vec::new();
+	for t in inserted.iter_mut() {
+		*t = identities.canonicalize(t.clone())
+	}
+
+	let old = std::mem::take(dataset);
+	for Quad(s, p, o, g) in old.iter() {
+		let quad = Quad(
+			identities.representative(s).clone(),
+			identities.representative(p).clone(),
+			identities.representative(o).clone(),
+			g.map(|g| identities.representative(g).clone()),
+		);
+
+		let changed = quad.as_ref() != Quad(s, p, o, g);
+		if dataset.insert(quad.clone())
+			&& changed
+			&& quad.3.is_none()
+			&& !old.contains(quad.as_ref())
+		{
+			rewritten.push(quad.into_triple().0)
+		}
+	}
+
+	rewritten
+}
@@ -0,0 +1,638 @@
+use std::{
+	collections::HashMap,
+	fmt::{self, Write},
+};
+
+use iref::{IriBuf, IriRef};
+use nquads_syntax::Parse;
+use rdf_types::{Id, LiteralType, RdfDisplay, Term, Triple};
+
+use crate::{
+	display::{DisplayContext, DisplayWithContext},
+	expression::Expression,
+	pattern::{Pattern, ResourceOrVar},
+	rule::{Conclusion, Hypothesis, Rule},
+	MaybeTrusted, Sign, Signed, TripleStatement, Trust,
+};
+
+use super::System;
+
+/// Datalog export error.
+#[derive(Debug, thiserror::Error)]
+pub enum DatalogExportError {
+	#[error("rule #{0}: expressions cannot be exported to Datalog")]
+	Expression(usize),
+
+	#[error("rule #{0}: truth statements cannot be exported to Datalog")]
+	Truth(usize),
+
+	#[error("rule #{0}: trusted statements cannot be exported to Datalog")]
+	Trusted(usize),
+
+	#[error("rule #{0}: universal conditions cannot be exported to Datalog")]
+	Universal(usize),
+
+	#[error("rule #{0}: list patterns cannot be exported to Datalog")]
+	List(usize),
+
+	#[error("rule #{0}: variable `X{1}` is not bound by the hypothesis")]
+	UnboundVariable(usize, usize),
+
+	#[error(transparent)]
+	Fmt(#[from] fmt::Error),
+}
+
+/// Datalog parse error.
+///
+/// Positions are byte offsets in the parsed text.
+#[derive(Debug, thiserror::Error)]
+pub enum DatalogParseError {
+	#[error("unexpected character at {0}")]
+	Unexpected(usize),
+
+	#[error("unexpected end of input")]
+	UnexpectedEnd,
+
+	#[error("invalid term at {0}")]
+	InvalidTerm(usize),
+
+	#[error("unknown predicate `{1}` at {0}")]
+	UnknownPredicate(usize, String),
+
+	#[error("undeclared prefix `{1}` at {0}")]
+	UnknownPrefix(usize, String),
+}
+
+impl System {
+	/// Writes the system as a Datalog program.
+	///
+	/// Each rule is written on its own line, in the form `head :- body.`,
+	/// where every atom is a `triple(s, p, o)` predicate. Variables
+	/// are named `X0`, `X1`, etc. following their index in the rule, and
+	/// resources are written in the N-Triples syntax. Negative patterns and
+	/// statements are prefixed with `!`, and equality statements are written
+	/// `X = Y` or `X != Y`.
+	///
+	/// Variables only appearing in the head are existential. Rule
+	/// identifiers, severities, profiles and priorities are not exported.
+	/// Expressions, truth statements, trusted statements and universal
+	/// conditions have no Datalog equivalent and are rejected.
+	pub fn write_datalog(&self, out: &mut impl Write) -> Result<(), DatalogExportError> {
+		for (i, rule) in self.rules.iter().enumerate() {
+			write_rule(out, i, rule, None)?;
+		}
+
+		Ok(())
+	}
+
+	/// Returns the system as a Datalog program.
+	///
+	/// See [`Self::write_datalog`].
+	pub fn to_datalog(&self) -> Result<String, DatalogExportError> {
+		let mut result = String::new();
+		self.write_datalog(&mut result)?;
+		Ok(result)
+	}
+
+	/// Parses a Datalog program in the format produced by
+	/// [`Self::write_datalog`].
+	///
+	/// Variables are any identifier starting with an uppercase letter, and
+	/// line comments start with `//`. Variable names are kept in
+	/// [`Rule::names`]. Parsed statements are untrusted.
+	///
+	/// IRIs can also be written as prefixed names (`ex:name`), after a
+	/// `@prefix ex: <https://example.org/#> .` declaration, and relative to
+	/// the base IRI declared with `@base <https://example.org/> .`.
+	pub fn from_datalog(source: &str) -> Result<Self, DatalogParseError> {
+		Self::from_datalog_with_context(source, &mut DisplayContext::new())
+	}
+
+	/// Parses a Datalog program, using and updating the given display
+	/// context.
+	///
+	/// Prefixes of the context can be used in the program, and the prefix
+	/// and base declarations of the program are added to the context, so that
+	/// diagnostics can be rendered with them. The context is left unchanged
+	/// if the program is invalid.
+	///
+	/// See [`Self::from_datalog`].
+	pub fn from_datalog_with_context(
+		source: &str,
+		context: &mut DisplayContext,
+	) -> Result<Self, DatalogParseError> {
+		let mut parser = Parser {
+			source,
+			offset: 0,
+			context: context.clone(),
+		};
+		let mut result = Self::new();
+
+		while let Some(rule) = parser.next_rule()? {
+			result.insert(rule);
+		}
+
+		*context = parser.context;
+		Ok(result)
+	}
+}
+
+/// Parses a Datalog query, a comma-separated list of `triple(s, p, o)` atoms
+/// ending with a dot, using and updating the given display context.
+///
+/// The query is returned as a rule without conclusion, whose variables are
+/// numbered in order of first appearance.
+pub(crate) fn parse_query(
+	source: &str,
+	context: &mut DisplayContext,
+) -> Result<Rule, DatalogParseError> {
+	let mut parser = Parser {
+		source,
+		offset: 0,
+		context: context.clone(),
+	};
+
+	let query = parser.query()?;
+	*context = parser.context;
+	Ok(query)
+}
+
+/// Writes the given rule as a Datalog rule, using the given display context
+/// to write resources.
+///
+/// Prefix and base declarations are not written.
+pub(crate) fn rule_to_datalog(
+	rule: &Rule,
+	context: &DisplayContext,
+) -> Result<String, DatalogExportError> {
+	let mut result = String::new();
+	write_rule(&mut result, 0, rule, Some(context))?;
+	result.pop();
+	Ok(result)
+}
+
+fn write_rule(
+	out: &mut impl Write,
+	i: usize,
+	rule: &Rule,
+	context: Option<&DisplayContext>,
+) -> Result<(), DatalogExportError> {
+	if !rule.universals.is_empty() {
+		return Err(DatalogExportError::Universal(i));
+	}
+
+	if !rule.lists.is_empty() {
+		return Err(DatalogExportError::List(i));
+	}
+
+	let mut bound = vec![false; rule.variables];
+	rule.hypothesis.visit_variables(|x| bound[x] = true);
+	if let Some(x) = bound.iter().position(|b| !b) {
+		return Err(DatalogExportError::UnboundVariable(i, x));
+	}
+
+	let mut head = Vec::new();
+	for MaybeTrusted(trust, Signed(sign, statement)) in &rule.conclusion.statements {
+		if trust.is_trusted() {
+			return Err(DatalogExportError::Trusted(i));
+		}
+
+		let atom = match statement {
+			TripleStatement::Triple(Triple(s, p, o)) => format!(
+				"{}triple({}, {}, {})",
+				negation(*sign),
+				expression(i, s, context)?,
+				expression(i, p, context)?,
+				expression(i, o, context)?
+			),
+			TripleStatement::Eq(a, b) => {
+				let op = match sign {
+					Sign::Positive => "=",
+					Sign::Negative => "!=",
+				};
+
+				format!(
+					"{} {op} {}",
+					expression(i, a, context)?,
+					expression(i, b, context)?
+				)
+			}
+			TripleStatement::True(_) => return Err(DatalogExportError::Truth(i)),
+		};
+
+		head.push(atom)
+	}
+
+	let body: Vec<_> = rule
+		.hypothesis
+		.patterns
+		.iter()
+		.map(|Signed(sign, Triple(s, p, o))| {
+			format!(
+				"{}triple({}, {}, {})",
+				negation(*sign),
+				term(s, context),
+				term(p, context),
+				term(o, context)
+			)
+		})
+		.collect();
+
+	if body.is_empty() {
+		writeln!(out, "{}.", head.join(", "))?
+	} else {
+		writeln!(out, "{} :- {}.", head.join(", "), body.join(", "))?
+	}
+
+	Ok(())
+}
+
+fn negation(sign: Sign) -> &'static str {
+	match sign {
+		Sign::Positive => "",
+		Sign::Negative => "!",
+	}
+}
+
+fn term(t: &ResourceOrVar, context: Option<&DisplayContext>) -> String {
+	match (t, context) {
+		(ResourceOrVar::Resource(r), Some(context)) => r.with(context).to_string(),
+		(ResourceOrVar::Resource(r), None) => r.rdf_display().to_string(),
+		(ResourceOrVar::Var(x), _) => format!("X{x}"),
+	}
+}
+
+fn expression(
+	i: usize,
+	e: &Expression<ResourceOrVar>,
+	context: Option<&DisplayContext>,
+) -> Result<String, DatalogExportError> {
+	match e {
+		Expression::Resource(t) => Ok(term(t, context)),
+		_ => Err(DatalogExportError::Expression(i)),
+	}
+}
+
+/// Parsed term, before variable numbering.
+enum Item {
+	Resource(Term),
+	Var(String),
+}
+
+/// Parsed rule body.
+type Body = Vec<Signed<Triple<Item, Item, Item>>>;
+
+/// Parsed atom.
+enum Atom {
+	Triple(Sign, Triple<Item, Item, Item>),
+	Eq(Sign, Item, Item),
+}
+
+struct Parser<'a> {
+	source: &'a str,
+	offset: usize,
+	context: DisplayContext,
+}
+
+impl<'a> Parser<'a> {
+	fn rest(&self) -> &'a str {
+		&self.source[self.offset..]
+	}
+
+	fn skip_whitespaces(&mut self) {
+		loop {
+			let rest = self.rest();
+			let trimmed = rest.trim_start();
+			self.offset += rest.len() - trimmed.len();
+
+			if trimmed.starts_with("//") {
+				self.offset += trimmed.find('\n').unwrap_or(trimmed.len())
+			} else {
+				break;
+			}
+		}
+	}
+
+	fn peek(&mut self) -> Option<char> {
+		self.skip_whitespaces();
+		self.rest().chars().next()
+	}
+
+	/// Consumes the given token if it comes next.
+	fn accept(&mut self, token: &str) -> bool {
+		self.skip_whitespaces();
+		if self.rest().starts_with(token) {
+			self.offset += token.len();
+			true
+		} else {
+			false
+		}
+	}
+
+	fn expect(&mut self, token: &str) -> Result<(), DatalogParseError> {
+		if self.accept(token) {
+			Ok(())
+		} else {
+			Err(self.unexpected())
+		}
+	}
+
+	fn unexpected(&self) -> DatalogParseError {
+		if self.offset >= self.source.len() {
+			DatalogParseError::UnexpectedEnd
+		} else {
+			DatalogParseError::Unexpected(self.offset)
+		}
+	}
+
+	/// Consumes the characters matching the given predicate.
+	fn take_while(&mut self, mut f: impl FnMut(char) -> bool) -> &'a str {
+		let rest = self.rest();
+		let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+		self.offset += len;
+		&rest[..len]
+	}
+
+	/// Parses the prefix and base declarations coming next.
+	fn declarations(&mut self) -> Result<(), DatalogParseError> {
+		loop {
+			if self.accept("@prefix") {
+				self.skip_whitespaces();
+				let prefix = self.take_while(is_prefix_char).to_owned();
+				if !self.rest().starts_with(':') {
+					return Err(self.unexpected());
+				}
+				self.offset += 1;
+				let namespace = self.iri()?;
+				self.expect(".")?;
+				self.context.insert_prefix(prefix, namespace);
+			} else if self.accept("@base") {
+				let base = self.iri()?;
+				self.expect(".")?;
+				self.context.set_base(Some(base));
+			} else {
+				break Ok(());
+			}
+		}
+	}
+
+	fn next_rule(&mut self) -> Result<Option<Rule>, DatalogParseError> {
+		self.declarations()?;
+		if self.peek().is_none() {
+			return Ok(None);
+		}
+
+		let mut head = vec![self.atom()?];
+		while self.accept(",") {
+			head.push(self.atom()?)
+		}
+
+		let body = if self.accept(":-") {
+			self.body()?
+		} else {
+			Vec::new()
+		};
+
+		self.expect(".")?;
+
+		let mut variables = HashMap::new();
+		let patterns = patterns(body, &mut variables);
+
+		let universal = variables.len();
+		let mut statement = |item| {
+			Expression::Resource(match item {
+				Item::Resource(r) => ResourceOrVar::Resource(r),
+				Item::Var(name) => {
+					let next = variables.len();
+					ResourceOrVar::Var(*variables.entry(name).or_insert(next))
+				}
+			})
+		};
+
+		let statements = head
+			.into_iter()
+			.map(|atom| {
+				let (sign, statement) = match atom {
+					Atom::Triple(sign, Triple(s, p, o)) => (
+						sign,
+						TripleStatement::Triple(Triple(statement(s), statement(p), statement(o))),
+					),
+					Atom::Eq(sign, a, b) => (sign, TripleStatement::Eq(statement(a), statement(b))),
+				};
+
+				MaybeTrusted(Trust::Untrusted, Signed(sign, statement))
+			})
+			.collect();
+
+		let existential = variables.len() - universal;
+		Ok(Some(
+			Rule::new(
+				universal,
+				Hypothesis::new(patterns),
+				Conclusion::new(existential, statements),
+			)
+			.with_variable_names(variables.into_iter().map(|(name, x)| (x, name))),
+		))
+	}
+
+	/// Parses a query, made of a rule body without head.
+	fn query(&mut self) -> Result<Rule, DatalogParseError> {
+		self.declarations()?;
+		let body = self.body()?;
+		self.expect(".")?;
+		if self.peek().is_some() {
+			return Err(self.unexpected());
+		}
+
+		let mut variables = HashMap::new();
+		let patterns = patterns(body, &mut variables);
+		Ok(Rule::new(
+			variables.len(),
+			Hypothesis::new(patterns),
+			Conclusion::new(0, Vec::new()),
+		)
+		.with_variable_names(variables.into_iter().map(|(name, x)| (x, name))))
+	}
+
+	/// Parses a non-empty list of triple atoms, separated by commas.
+	fn body(&mut self) -> Result<Body, DatalogParseError> {
+		let mut body = Vec::new();
+		loop {
+			self.skip_whitespaces();
+			let start = self.offset;
+			match self.atom()? {
+				Atom::Triple(sign, triple) => body.push(Signed(sign, triple)),
+				Atom::Eq(_, _, _) => return Err(DatalogParseError::Unexpected(start)),
+			}
+
+			if !self.accept(",") {
+				break Ok(body);
+			}
+		}
+	}
+
+	fn atom(&mut self) -> Result<Atom, DatalogParseError> {
+		let sign = if self.rest().starts_with("!=") {
+			Sign::Positive
+		} else if self.accept("!") {
+			Sign::Negative
+		} else {
+			Sign::Positive
+		};
+
+		match self.peek() {
+			Some(c) if c.is_ascii_lowercase() && !self.is_prefixed_name() => {
+				let start = self.offset;
+				let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+				if name != "triple" {
+					return Err(DatalogParseError::UnknownPredicate(start, name.to_owned()));
+				}
+
+				self.expect("(")?;
+				let s = self.item()?;
+				self.expect(",")?;
+				let p = self.item()?;
+				self.expect(",")?;
+				let o = self.item()?;
+				self.expect(")")?;
+				Ok(Atom::Triple(sign, Triple(s, p, o)))
+			}
+			_ if sign == Sign::Positive => {
+				let a = self.item()?;
+				let sign = if self.accept("!=") {
+					Sign::Negative
+				} else {
+					self.expect("=")?;
+					Sign::Positive
+				};
+				let b = self.item()?;
+				Ok(Atom::Eq(sign, a, b))
+			}
+			_ => Err(self.unexpected()),
+		}
+	}
+
+	fn item(&mut self) -> Result<Item, DatalogParseError> {
+		self.skip_whitespaces();
+		let start = self.offset;
+		match self.peek() {
+			Some(c) if c.is_ascii_uppercase() => {
+				let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+				Ok(Item::Var(name.to_owned()))
+			}
+			Some('<') => Ok(Item::Resource(Term::Id(Id::Iri(self.iri()?)))),
+			Some(c) if c.is_ascii_lowercase() || c == ':' => {
+				Ok(Item::Resource(Term::Id(Id::Iri(self.prefixed_name()?))))
+			}
+			Some('_') => {
+				self.expect("_:")?;
+				self.take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-'));
+				self.resource(start)
+			}
+			Some('"') => {
+				self.offset += 1;
+				let mut escaped = false;
+				self.take_while(|c| {
+					let end = !escaped && c == '"';
+					escaped = !escaped && c == '\\';
+					!end
+				});
+				self.expect("\"")?;
+
+				if self.rest().starts_with("^^") && !self.rest().starts_with("^^<") {
+					let Item::Resource(Term::Literal(mut literal)) = self.resource(start)? else {
+						unreachable!()
+					};
+					self.offset += 2;
+					literal.type_ = LiteralType::Any(self.prefixed_name()?);
+					return Ok(Item::Resource(Term::Literal(literal)));
+				} else if self.rest().starts_with("^^<") {
+					self.take_while(|c| c != '>');
+					self.expect(">")?;
+				} else if self.rest().starts_with('@') {
+					self.offset += 1;
+					self.take_while(|c| c.is_ascii_alphanumeric() || c == '-');
+				}
+
+				self.resource(start)
+			}
+			_ => Err(self.unexpected()),
+		}
+	}
+
+	/// Checks that a prefixed name comes next.
+	fn is_prefixed_name(&self) -> bool {
+		let rest = self.rest();
+		let len = rest.find(|c| !is_prefix_char(c)).unwrap_or(rest.len());
+		rest[len..].starts_with(':')
+	}
+
+	/// Parses an IRI reference, resolved against the base IRI.
+	fn iri(&mut self) -> Result<IriBuf, DatalogParseError> {
+		self.expect("<")?;
+		let start = self.offset - 1;
+		let iri_ref = self.take_while(|c| c != '>');
+		self.expect(">")?;
+
+		let iri_ref = IriRef::new(iri_ref).map_err(|_| DatalogParseError::InvalidTerm(start))?;
+		match self.context.base() {
+			Some(base) => Ok(iri_ref.resolved(base)),
+			None => iri_ref
+				.as_iri()
+				.map(ToOwned::to_owned)
+				.ok_or(DatalogParseError::InvalidTerm(start)),
+		}
+	}
+
+	/// Parses a prefixed name, expanded using the declared prefixes.
+	fn prefixed_name(&mut self) -> Result<IriBuf, DatalogParseError> {
+		let start = self.offset;
+		let prefix = self.take_while(is_prefix_char);
+		if !self.rest().starts_with(':') {
+			return Err(self.unexpected());
+		}
+		self.offset += 1;
+
+		let taken = self.take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'));
+		// A trailing dot ends the rule.
+		let local = taken.trim_end_matches('.');
+		self.offset -= taken.len() - local.len();
+
+		let namespace = self
+			.context
+			.prefix(prefix)
+			.ok_or_else(|| DatalogParseError::UnknownPrefix(start, prefix.to_owned()))?;
+
+		IriBuf::new(format!("{namespace}{local}"))
+			.map_err(|_| DatalogParseError::InvalidTerm(start))
+	}
+
+	/// Parses the resource between `start` and the current offset.
+	fn resource(&self, start: usize) -> Result<Item, DatalogParseError> {
+		Term::parse_str(&self.source[start..self.offset])
+			.map(|t| Item::Resource(t.into_value()))
+			.map_err(|_| DatalogParseError::InvalidTerm(start))
+	}
+}
+
+/// Turns body atoms into patterns, numbering variables in order of first
+/// appearance.
+fn patterns(body: Body, variables: &mut HashMap<String, usize>) -> Vec<Signed<Pattern<Term>>> {
+	body.into_iter()
+		.map(|Signed(sign, Triple(s, p, o))| {
+			let mut pattern = |item| match item {
+				Item::Resource(r) => ResourceOrVar::Resource(r),
+				Item::Var(name) => {
+					let next = variables.len();
+					ResourceOrVar::Var(*variables.entry(name).or_insert(next))
+				}
+			};
+
+			Signed(sign, Triple(pattern(s), pattern(p), pattern(o)))
+		})
+		.collect()
+}
+
+fn is_prefix_char(c: char) -> bool {
+	c.is_alphanumeric() || matches!(c, '_' | '-')
+}
@@ -1,8 +1,14 @@
-use std::hash::Hash;
+use std::{
+	collections::{HashMap, HashSet},
+	hash::Hash,
+	ops::ControlFlow,
+};
 
 use educe::Educe;
 use rdf_types::{
-	interpretation::{LiteralInterpretationMut, ReverseTermInterpretation, WithGenerator},
+	interpretation::{
+		IriInterpretationMut, LiteralInterpretationMut, ReverseTermInterpretation, WithGenerator,
+	},
 	Generator, InterpretationMut, Term, VocabularyMut,
 };
 use xsd_types::{ParseXsd, XSD_BOOLEAN};
@@ -11,17 +17,21 @@ use crate::{
 	expression::{self, Eval},
 	pattern::{ApplySubstitution, PatternSubstitution},
 	rule::TripleStatementPattern,
-	Entailment, FallibleSignedPatternMatchingDataset, Reason, Sign, Signed,
-	SignedPatternMatchingDataset, TripleStatement, Validation, ValidationError,
+	Entailment, FallibleSignedPatternMatchingDataset, MaybeTrusted, Reason, Sign, Signed,
+	SignedPatternMatchingDataset, TripleStatement, Trust, Validation, ValidationError,
 };
 
-use super::{DeductionInstance, DeductionsInstance};
+use super::{DeductionInstance, DeductionsInstance, Existentials, System};
 
 #[derive(Educe)]
 #[educe(Default)]
 pub struct Deductions<'r, T = Term>(Vec<Deduction<'r, T>>);
 
 impl<'r, T> Deductions<'r, T> {
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
 	pub fn is_empty(&self) -> bool {
 		self.0.is_empty()
 	}
@@ -34,7 +44,56 @@ impl<'r, T> Deductions<'r, T> {
 		self.0.extend(other.0)
 	}
 
+	/// Removes duplicate deductions.
+	///
+	/// Two deductions are duplicates if they are deduced by the same rule,
+	/// and their substitutions agree on every variable used by the rule
+	/// conclusion: they deduce the same statements. Only the first of each
+	/// set of duplicates is kept, hence the alternative causes of a
+	/// deduction are lost. Deductions are never deduplicated implicitly.
+	///
+	/// Rules are identified by their index in the given system, which must
+	/// be the system deducing these deductions.
+	///
+	/// # Panics
+	///
+	/// Panics if a deduction rule is not in the system.
+	pub fn dedup(&mut self, system: &System<T>)
+	where
+		T: Clone + Eq + Hash,
+	{
+		let mut rules: HashMap<usize, Vec<usize>> = HashMap::new();
+		let mut seen = HashSet::new();
+
+		self.0.retain(|d| {
+			let rule = d.entailment.rule;
+			let index = system
+				.index_of_rule(rule)
+				.expect("deduction rule not in the system");
+			let used = rules.entry(index).or_insert_with(|| {
+				let mut used = Vec::new();
+				rule.conclusion.visit_variables(|x| {
+					if x < rule.variables {
+						used.push(x)
+					}
+				});
+				used.sort_unstable();
+				used.dedup();
+				used
+			});
+
+			let key: Vec<_> = used
+				.iter()
+				.map(|&x| d.entailment.substitution.get(x).cloned().flatten())
+				.collect();
+
+			seen.insert((index, key))
+		})
+	}
+
 	/// Evaluates the expressions in the deducted statements.
+	///
+	/// Untrusted statements are accepted as deduced statements.
 	pub fn eval_with<V, I>(
 		self,
 		vocabulary: &mut V,
@@ -45,6 +104,28 @@ impl<'r, T> Deductions<'r, T> {
 		V: VocabularyMut,
 		V::Iri: PartialEq,
 		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+		I::Resource: PartialEq,
+	{
+		self.eval_with_policy(vocabulary, interpretation, UntrustedPolicy::Accept)
+	}
+
+	/// Evaluates the expressions in the deducted statements, handling
+	/// untrusted statements according to the given `policy`.
+	pub fn eval_with_policy<V, I>(
+		self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		policy: UntrustedPolicy,
+	) -> Result<DeductionsInstance<'r, T>, expression::Error>
+	where
+		T: Clone + PartialEq,
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
 			+ LiteralInterpretationMut<V::Literal>
 			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
 		I::Resource: PartialEq,
@@ -52,13 +133,45 @@ impl<'r, T> Deductions<'r, T> {
 		Ok(DeductionsInstance(
 			self.0
 				.into_iter()
-				.map(|s| s.eval(vocabulary, interpretation))
+				.map(|s| s.eval_with_policy(vocabulary, interpretation, policy))
+				.collect::<Result<_, _>>()?,
+		))
+	}
+}
+
+impl<'r, T> Deductions<'r, T> {
+	/// Evaluates the expressions in the deducted statements, handling
+	/// untrusted statements according to the given `policy`, and existential
+	/// variables with the given allocator.
+	pub fn eval_with_existentials<V, I>(
+		self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		policy: UntrustedPolicy,
+		existentials: &mut Existentials<'r, T>,
+	) -> Result<DeductionsInstance<'r, T>, expression::Error>
+	where
+		T: Clone + Eq + Hash,
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+	{
+		Ok(DeductionsInstance(
+			self.0
+				.into_iter()
+				.map(|s| s.eval_with_existentials(vocabulary, interpretation, policy, existentials))
 				.collect::<Result<_, _>>()?,
 		))
 	}
 }
 
 impl<'r> Deductions<'r> {
+	/// Evaluates the expressions in the deducted statements.
+	///
+	/// Untrusted statements are accepted as deduced statements.
 	pub fn eval(
 		self,
 		generator: impl Generator,
@@ -66,6 +179,50 @@ impl<'r> Deductions<'r> {
 		let mut interpretation = WithGenerator::new((), generator);
 		self.eval_with(&mut (), &mut interpretation)
 	}
+
+	/// Evaluates the expressions in the deducted statements.
+	///
+	/// Untrusted statements are not deduced, but must be checked against the
+	/// dataset (see [`DeductionInstance::checks`]).
+	pub fn eval_checked(
+		self,
+		generator: impl Generator,
+	) -> Result<DeductionsInstance<'r>, expression::Error> {
+		let mut interpretation = WithGenerator::new((), generator);
+		self.eval_with_policy(&mut (), &mut interpretation, UntrustedPolicy::Check)
+	}
+
+	/// Evaluates the expressions in the deducted statements, generating the
+	/// resources of existential variables with the given allocator.
+	///
+	/// Untrusted statements are accepted as deduced statements. The
+	/// `generator` is used by the [`Fresh`](super::ExistentialPolicy::Fresh)
+	/// and [`Reuse`](super::ExistentialPolicy::Reuse) policies.
+	pub fn eval_existentials(
+		self,
+		generator: impl Generator,
+		existentials: &mut Existentials<'r>,
+	) -> Result<DeductionsInstance<'r>, expression::Error> {
+		let mut interpretation = WithGenerator::new((), generator);
+		self.eval_with_existentials(
+			&mut (),
+			&mut interpretation,
+			UntrustedPolicy::Accept,
+			existentials,
+		)
+	}
+}
+
+/// Policy applied to untrusted statements when evaluating deductions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UntrustedPolicy {
+	/// Untrusted statements are deduced just like trusted statements.
+	#[default]
+	Accept,
+
+	/// Untrusted statements are not deduced, but must be checked against the
+	/// dataset.
+	Check,
 }
 
 impl<'r, T: Clone + Eq + Hash> Deductions<'r, T> {
@@ -79,6 +236,7 @@ impl<'r, T: Clone + Eq + Hash> Deductions<'r, T> {
 		V: VocabularyMut,
 		V::Iri: PartialEq,
 		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
 			+ LiteralInterpretationMut<V::Literal>
 			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
 		D: SignedPatternMatchingDataset<Resource = T>,
@@ -97,6 +255,87 @@ impl<'r, T: Clone + Eq + Hash> Deductions<'r, T> {
 		V: VocabularyMut,
 		V::Iri: PartialEq,
 		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		Ok(
+			match self.try_find_violation(vocabulary, interpretation, dataset)? {
+				Some((reason, _)) => Validation::Invalid(reason),
+				None => Validation::Ok,
+			},
+		)
+	}
+
+	/// Finds a deduced statement not satisfied by the dataset.
+	///
+	/// Returns the reason of the violation, with the substitution of the
+	/// violating deduction.
+	pub(crate) fn try_find_violation<V, I, D>(
+		self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		dataset: &D,
+	) -> Result<Option<ViolationCause<T>>, ValidationError<D::Error>>
+	where
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		let mut result = None;
+		self.try_for_each_violation(vocabulary, interpretation, dataset, |v| {
+			result = Some(v);
+			ControlFlow::Break(())
+		})?;
+		Ok(result)
+	}
+
+	/// Finds every deduced statement not satisfied by the dataset.
+	///
+	/// Returns the reason of each violation, with the substitution of the
+	/// violating deduction, in order of deduction.
+	pub(crate) fn try_find_violations<V, I, D>(
+		self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		dataset: &D,
+	) -> Result<Vec<ViolationCause<T>>, ValidationError<D::Error>>
+	where
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		let mut result = Vec::new();
+		self.try_for_each_violation(vocabulary, interpretation, dataset, |v| {
+			result.push(v);
+			ControlFlow::Continue(())
+		})?;
+		Ok(result)
+	}
+
+	/// Calls `f` on each deduced statement not satisfied by the dataset,
+	/// until it breaks.
+	fn try_for_each_violation<V, I, D>(
+		self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		dataset: &D,
+		mut f: impl FnMut(ViolationCause<T>) -> ControlFlow<()>,
+	) -> Result<(), ValidationError<D::Error>>
+	where
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
 			+ LiteralInterpretationMut<V::Literal>
 			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
 		D: FallibleSignedPatternMatchingDataset<Resource = T>,
@@ -105,29 +344,22 @@ impl<'r, T: Clone + Eq + Hash> Deductions<'r, T> {
 			.eval_with(vocabulary, interpretation)
 			.map_err(ValidationError::Expression)?;
 		for group in deduction {
-			for Signed(sign, stm) in group.statements {
-				match stm {
+			for Signed(sign, stm) in group.statements.into_iter().chain(group.checks) {
+				let reason = match stm {
 					TripleStatement::Triple(triple) => {
-						if !dataset
+						if dataset
 							.try_contains_signed_triple(Signed(sign, triple.as_ref()))
 							.map_err(ValidationError::Dataset)?
 						{
-							return Ok(Validation::Invalid(Reason::MissingTriple(Signed(
-								sign, triple,
-							))));
+							continue;
 						}
+
+						Reason::MissingTriple(Signed(sign, triple))
 					}
 					TripleStatement::Eq(a, b) => match sign {
-						Sign::Positive => {
-							if a != b {
-								return Ok(Validation::Invalid(Reason::NotEq(a, b)));
-							}
-						}
-						Sign::Negative => {
-							if a == b {
-								return Ok(Validation::Invalid(Reason::NotNe(a, b)));
-							}
-						}
+						Sign::Positive if a != b => Reason::NotEq(a, b),
+						Sign::Negative if a == b => Reason::NotNe(a, b),
+						_ => continue,
 					},
 					TripleStatement::True(r) => {
 						let expected = sign.is_positive();
@@ -152,27 +384,51 @@ impl<'r, T: Clone + Eq + Hash> Deductions<'r, T> {
 							}
 						}
 
-						if !found {
-							return Ok(Validation::Invalid(if expected {
-								Reason::NotTrue(r.clone())
-							} else {
-								Reason::NotFalse(r.clone())
-							}));
+						if found {
+							continue;
+						}
+
+						if expected {
+							Reason::NotTrue(r)
+						} else {
+							Reason::NotFalse(r)
 						}
 					}
+				};
+
+				if f((reason, group.entailment.substitution.clone())).is_break() {
+					return Ok(());
 				}
 			}
 		}
 
-		Ok(Validation::Ok)
+		Ok(())
 	}
 }
 
+/// Reason of a violation, with the substitution of the violating deduction.
+pub(crate) type ViolationCause<T = Term> = (Reason<T>, Vec<Option<T>>);
+
 pub enum EvalError<I> {
 	Expression(expression::Error),
 	Interpretation(I),
 }
 
+impl<'r, T> IntoIterator for Deductions<'r, T> {
+	type Item = Deduction<'r, T>;
+	type IntoIter = std::vec::IntoIter<Deduction<'r, T>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<'r, T> FromIterator<Deduction<'r, T>> for Deductions<'r, T> {
+	fn from_iter<I: IntoIterator<Item = Deduction<'r, T>>>(iter: I) -> Self {
+		Self(Vec::from_iter(iter))
+	}
+}
+
 impl<'r, T> From<Deduction<'r, T>> for Deductions<'r, T> {
 	fn from(value: Deduction<'r, T>) -> Self {
 		Self(vec![value])
@@ -185,7 +441,7 @@ pub struct Deduction<'r, T> {
 	pub entailment: Entailment<'r, T>,
 
 	/// Deduced statements.
-	pub statements: Vec<Signed<TripleStatementPattern<T>>>,
+	pub statements: Vec<MaybeTrusted<Signed<TripleStatementPattern<T>>>>,
 }
 
 impl<'r, T> Deduction<'r, T> {
@@ -196,7 +452,7 @@ impl<'r, T> Deduction<'r, T> {
 		}
 	}
 
-	pub fn insert(&mut self, statement: Signed<TripleStatementPattern<T>>) {
+	pub fn insert(&mut self, statement: MaybeTrusted<Signed<TripleStatementPattern<T>>>) {
 		self.statements.push(statement)
 	}
 
@@ -207,6 +463,8 @@ impl<'r, T> Deduction<'r, T> {
 	}
 
 	/// Evaluates the expressions in the deducted statements.
+	///
+	/// Untrusted statements are accepted as deduced statements.
 	pub fn eval<V, I>(
 		self,
 		vocabulary: &mut V,
@@ -217,29 +475,104 @@ impl<'r, T> Deduction<'r, T> {
 		V: VocabularyMut,
 		V::Iri: PartialEq,
 		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
 			+ LiteralInterpretationMut<V::Literal>
 			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
 		I::Resource: PartialEq,
+	{
+		self.eval_with_policy(vocabulary, interpretation, UntrustedPolicy::Accept)
+	}
+
+	/// Evaluates the expressions in the deducted statements, handling
+	/// untrusted statements according to the given `policy`.
+	pub fn eval_with_policy<V, I>(
+		self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		policy: UntrustedPolicy,
+	) -> Result<DeductionInstance<'r, T>, expression::Error>
+	where
+		T: Clone + PartialEq,
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+		I::Resource: PartialEq,
+	{
+		let resources = (0..self.entailment.rule.conclusion.variables)
+			.map(|_| interpretation.new_resource(vocabulary))
+			.collect();
+
+		self.instantiate(vocabulary, interpretation, policy, resources)
+	}
+
+	/// Evaluates the expressions in the deducted statements, handling
+	/// untrusted statements according to the given `policy`, and existential
+	/// variables with the given allocator.
+	pub fn eval_with_existentials<V, I>(
+		self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		policy: UntrustedPolicy,
+		existentials: &mut Existentials<'r, T>,
+	) -> Result<DeductionInstance<'r, T>, expression::Error>
+	where
+		T: Clone + Eq + Hash,
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+	{
+		let resources = existentials.resources(vocabulary, interpretation, &self.entailment);
+		self.instantiate(vocabulary, interpretation, policy, resources)
+	}
+
+	/// Instantiates the deducted statements, binding the existential
+	/// variables to the given resources.
+	fn instantiate<V, I>(
+		self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		policy: UntrustedPolicy,
+		resources: Vec<T>,
+	) -> Result<DeductionInstance<'r, T>, expression::Error>
+	where
+		T: Clone + PartialEq,
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
 	{
 		let rule = self.entailment.rule;
 		let mut substitution = PatternSubstitution::new();
-		for i in 0..rule.conclusion.variables {
-			let x = i + rule.variables;
-			substitution.bind(x, interpretation.new_resource(vocabulary));
+		for (i, resource) in resources.into_iter().enumerate() {
+			substitution.bind(i + rule.variables, resource);
 		}
 
 		let mut statements = Vec::with_capacity(self.statements.len());
-		for stm in self.statements {
-			statements.push(
-				stm.apply_substitution(&substitution)
-					.unwrap()
-					.eval_and_instantiate(vocabulary, interpretation)?,
-			);
+		let mut checks = Vec::new();
+		for MaybeTrusted(trust, stm) in self.statements {
+			let stm = stm
+				.apply_substitution(&substitution)
+				.unwrap()
+				.eval_and_instantiate(vocabulary, interpretation)?;
+
+			match (trust, policy) {
+				(Trust::Untrusted, UntrustedPolicy::Check) => checks.push(stm),
+				_ => statements.push(stm),
+			}
 		}
 
 		Ok(DeductionInstance {
 			entailment: self.entailment,
 			statements,
+			checks,
 		})
 	}
 }
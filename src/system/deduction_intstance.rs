@@ -1,7 +1,13 @@
+use std::{
+	cmp::Ordering,
+	collections::{HashMap, HashSet},
+	hash::Hash,
+};
+
 use educe::Educe;
-use rdf_types::Term;
+use rdf_types::{Term, Triple};
 
-use crate::{Entailment, Signed, TripleStatement};
+use crate::{Bipolar, Entailment, Sign, Signed, TripleStatement};
 
 #[derive(Educe)]
 #[educe(Default)]
@@ -15,6 +21,182 @@ impl<'r, T> DeductionsInstance<'r, T> {
 	pub fn merge_with(&mut self, other: Self) {
 		self.0.extend(other.0)
 	}
+
+	pub fn iter(&self) -> std::slice::Iter<'_, DeductionInstance<'r, T>> {
+		self.0.iter()
+	}
+
+	/// Returns an iterator over all the positive deduced triples.
+	pub fn positive_triples(&self) -> impl Iterator<Item = &Triple<T>> {
+		self.0.iter().flat_map(DeductionInstance::positive_triples)
+	}
+
+	/// Returns an iterator over all the negative deduced triples.
+	pub fn negative_triples(&self) -> impl Iterator<Item = &Triple<T>> {
+		self.0.iter().flat_map(DeductionInstance::negative_triples)
+	}
+
+	/// Turns this collection into an iterator over all the positive deduced
+	/// triples.
+	pub fn into_positive_triples(self) -> impl 'r + Iterator<Item = Triple<T>>
+	where
+		T: 'r,
+	{
+		self.0
+			.into_iter()
+			.flat_map(DeductionInstance::into_positive_triples)
+	}
+}
+
+impl<'r, T: Clone + Eq + Hash> DeductionsInstance<'r, T> {
+	/// Deduplicates the deduced statements.
+	///
+	/// Each distinct statement is returned once, with every entailment
+	/// deducing it, in order of first deduction. Untrusted statements to be
+	/// checked are not included.
+	pub fn into_caused_statements(self) -> Vec<CausedStatement<'r, T>> {
+		let mut result: Vec<CausedStatement<'r, T>> = Vec::new();
+		let mut map: HashMap<Signed<TripleStatement<T>>, usize> = HashMap::new();
+
+		for deduction in self.0 {
+			for statement in deduction.statements {
+				match map.get(&statement) {
+					Some(&i) => {
+						let causes = &mut result[i].causes;
+						if !causes.contains(&deduction.entailment) {
+							causes.push(deduction.entailment.clone())
+						}
+					}
+					None => {
+						map.insert(statement.clone(), result.len());
+						result.push(CausedStatement {
+							statement,
+							causes: vec![deduction.entailment.clone()],
+						})
+					}
+				}
+			}
+		}
+
+		result
+	}
+
+	/// Resolves contradictions between deduced statements using rule
+	/// priorities.
+	///
+	/// When a statement is deduced both positively and negatively, the sign
+	/// deduced by the rule with the highest [priority](crate::Rule::priority)
+	/// wins, and the other sign is removed from the deductions. Contradictions
+	/// between rules of equal priority are left untouched and returned.
+	///
+	/// Only the statements of these deductions are compared: a statement
+	/// contradicting a fact deduced in an earlier round, or a stated fact, is
+	/// not detected, since such facts carry no priority. Resolve the conflicts
+	/// of every deduction at once (such as the deductions of a single
+	/// [`System::deduce`](super::System::deduce) call on the closed dataset)
+	/// to settle the contradictions between all the deduced facts.
+	///
+	/// Closures call this method on the deductions of each round.
+	pub fn resolve_conflicts(&mut self) -> Vec<Conflict<'r, T>> {
+		// Highest priority deducing each statement, for each sign.
+		let mut priorities: HashMap<TripleStatement<T>, Bipolar<Option<i32>>> = HashMap::new();
+		for deduction in &self.0 {
+			let priority = deduction.entailment.rule.priority;
+			for Signed(sign, statement) in &deduction.statements {
+				let p = priorities
+					.entry(statement.clone())
+					.or_default()
+					.get_mut(*sign);
+				*p = (*p).max(Some(priority))
+			}
+		}
+
+		let mut losers = HashMap::new();
+		let mut ties = HashSet::new();
+		for (statement, p) in priorities {
+			if let Bipolar {
+				positive: Some(positive),
+				negative: Some(negative),
+			} = p
+			{
+				match positive.cmp(&negative) {
+					Ordering::Greater => {
+						losers.insert(statement, Sign::Negative);
+					}
+					Ordering::Less => {
+						losers.insert(statement, Sign::Positive);
+					}
+					Ordering::Equal => {
+						ties.insert(statement);
+					}
+				}
+			}
+		}
+
+		let mut conflicts: Vec<Conflict<'r, T>> = Vec::new();
+		let mut conflict_map = HashMap::new();
+		for deduction in &mut self.0 {
+			for Signed(sign, statement) in &deduction.statements {
+				if ties.contains(statement) {
+					let i = *conflict_map.entry(statement.clone()).or_insert_with(|| {
+						conflicts.push(Conflict {
+							statement: statement.clone(),
+							positive: Vec::new(),
+							negative: Vec::new(),
+						});
+						conflicts.len() - 1
+					});
+
+					let causes = match sign {
+						Sign::Positive => &mut conflicts[i].positive,
+						Sign::Negative => &mut conflicts[i].negative,
+					};
+
+					if !causes.contains(&deduction.entailment) {
+						causes.push(deduction.entailment.clone())
+					}
+				}
+			}
+
+			deduction
+				.statements
+				.retain(|Signed(sign, statement)| losers.get(statement) != Some(sign))
+		}
+
+		conflicts
+	}
+}
+
+/// Contradiction between rules of equal priority.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict<'r, T> {
+	/// Statement deduced both positively and negatively.
+	pub statement: TripleStatement<T>,
+
+	/// Entailments deducing the statement positively.
+	pub positive: Vec<Entailment<'r, T>>,
+
+	/// Entailments deducing the statement negatively.
+	pub negative: Vec<Entailment<'r, T>>,
+}
+
+/// Deduced statement with all its causes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CausedStatement<'r, T> {
+	/// Deduced statement.
+	pub statement: Signed<TripleStatement<T>>,
+
+	/// Entailments deducing this statement.
+	pub causes: Vec<Entailment<'r, T>>,
+}
+
+impl<'a, 'r, T> IntoIterator for &'a DeductionsInstance<'r, T> {
+	type IntoIter = std::slice::Iter<'a, DeductionInstance<'r, T>>;
+	type Item = &'a DeductionInstance<'r, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
 }
 
 impl<'r, T> IntoIterator for DeductionsInstance<'r, T> {
@@ -39,6 +221,14 @@ pub struct DeductionInstance<'r, T> {
 
 	/// Deduced statements.
 	pub statements: Vec<Signed<TripleStatement<T>>>,
+
+	/// Untrusted statements that must be checked against the dataset instead
+	/// of being deduced.
+	///
+	/// Only populated when evaluating with [`UntrustedPolicy::Check`].
+	///
+	/// [`UntrustedPolicy::Check`]: super::UntrustedPolicy::Check
+	pub checks: Vec<Signed<TripleStatement<T>>>,
 }
 
 impl<'r, T> DeductionInstance<'r, T> {
@@ -46,6 +236,7 @@ impl<'r, T> DeductionInstance<'r, T> {
 		Self {
 			entailment,
 			statements: Vec::new(),
+			checks: Vec::new(),
 		}
 	}
 
@@ -55,7 +246,62 @@ impl<'r, T> DeductionInstance<'r, T> {
 
 	pub fn merge_with(&mut self, other: DeductionsInstance<'r, T>) {
 		for s in other.0 {
-			self.statements.extend(s.statements)
+			self.statements.extend(s.statements);
+			self.checks.extend(s.checks)
 		}
 	}
+
+	/// Returns an iterator over the deduced triples with the given sign.
+	pub fn triples(&self, sign: Sign) -> impl Iterator<Item = &Triple<T>> {
+		self.statements.iter().filter_map(move |s| match s {
+			Signed(s, TripleStatement::Triple(t)) if *s == sign => Some(t),
+			_ => None,
+		})
+	}
+
+	/// Returns an iterator over the positive deduced triples.
+	pub fn positive_triples(&self) -> impl Iterator<Item = &Triple<T>> {
+		self.triples(Sign::Positive)
+	}
+
+	/// Returns an iterator over the negative deduced triples.
+	pub fn negative_triples(&self) -> impl Iterator<Item = &Triple<T>> {
+		self.triples(Sign::Negative)
+	}
+
+	/// Returns an iterator over the deduced equalities (`a = b`).
+	pub fn equalities(&self) -> impl Iterator<Item = (&T, &T)> {
+		self.statements.iter().filter_map(|s| match s {
+			Signed(Sign::Positive, TripleStatement::Eq(a, b)) => Some((a, b)),
+			_ => None,
+		})
+	}
+
+	/// Returns an iterator over the deduced inequalities (`! a = b`).
+	pub fn inequalities(&self) -> impl Iterator<Item = (&T, &T)> {
+		self.statements.iter().filter_map(|s| match s {
+			Signed(Sign::Negative, TripleStatement::Eq(a, b)) => Some((a, b)),
+			_ => None,
+		})
+	}
+
+	/// Returns an iterator over the resources deduced to be the boolean value
+	/// `true`, with the sign of the statement.
+	///
+	/// A negative sign means that the resource is deduced not to be `true`.
+	pub fn truths(&self) -> impl Iterator<Item = Signed<&T>> {
+		self.statements.iter().filter_map(|s| match s {
+			Signed(sign, TripleStatement::True(r)) => Some(Signed(*sign, r)),
+			_ => None,
+		})
+	}
+
+	/// Turns this deduction into an iterator over the positive deduced
+	/// triples.
+	pub fn into_positive_triples(self) -> impl Iterator<Item = Triple<T>> {
+		self.statements.into_iter().filter_map(|s| match s {
+			Signed(Sign::Positive, TripleStatement::Triple(t)) => Some(t),
+			_ => None,
+		})
+	}
 }
@@ -0,0 +1,273 @@
+use std::{
+	collections::{BTreeSet, HashMap},
+	fmt::{self, Write},
+	hash::Hash,
+};
+
+use rdf_types::Triple;
+
+use crate::{
+	expression::Expression,
+	pattern::{Pattern, ResourceOrVar},
+	rule::{ForAll, Rule},
+	MaybeTrusted, Sign, Signed, TripleStatement,
+};
+
+use super::System;
+
+/// Rule dependency graph.
+///
+/// See [`System::dependency_graph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyGraph<'a, T> {
+	/// Rules (nodes of the graph).
+	pub rules: &'a [Rule<T>],
+
+	/// Dependencies (edges of the graph).
+	///
+	/// A dependency `(i, j)` means that the conclusion of rule `i` may feed
+	/// the hypothesis of rule `j`.
+	pub dependencies: BTreeSet<(usize, usize)>,
+}
+
+impl<'a, T> DependencyGraph<'a, T> {
+	/// Returns the rules whose conclusions may feed the hypothesis of the
+	/// given rule.
+	pub fn dependencies_of(&self, rule: usize) -> impl '_ + Iterator<Item = usize> {
+		self.dependencies
+			.iter()
+			.filter(move |(_, j)| *j == rule)
+			.map(|(i, _)| *i)
+	}
+
+	/// Returns the rules whose hypothesis may be fed by the conclusion of the
+	/// given rule.
+	pub fn dependents_of(&self, rule: usize) -> impl '_ + Iterator<Item = usize> {
+		self.dependencies
+			.iter()
+			.filter(move |(i, _)| *i == rule)
+			.map(|(_, j)| *j)
+	}
+}
+
+impl<'a, T: fmt::Display> DependencyGraph<'a, T> {
+	/// Writes the graph in the GraphViz DOT format.
+	///
+	/// Rules are labeled by their identifier if any, or their index in the
+	/// system otherwise.
+	pub fn write_dot(&self, out: &mut impl Write) -> fmt::Result {
+		writeln!(out, "digraph {{")?;
+
+		for (i, rule) in self.rules.iter().enumerate() {
+			let label = match &rule.id {
+				Some(id) => id.to_string(),
+				None => format!("#{i}"),
+			};
+
+			writeln!(out, "\tr{i} [label=\"{}\"];", escape(&label))?;
+		}
+
+		for (i, j) in &self.dependencies {
+			writeln!(out, "\tr{i} -> r{j};")?;
+		}
+
+		writeln!(out, "}}")
+	}
+
+	/// Returns the graph in the GraphViz DOT format.
+	pub fn to_dot(&self) -> String {
+		let mut result = String::new();
+		self.write_dot(&mut result).unwrap();
+		result
+	}
+}
+
+/// Escapes the given label for a DOT quoted string.
+fn escape(s: &str) -> String {
+	s.replace('\\', "\\\\")
+		.replace('"', "\\\"")
+		.replace('\r', "\\r")
+		.replace('\n', "\\n")
+}
+
+/// Checks that the given conclusion term may produce a term matched by the
+/// given hypothesis term.
+fn may_match<T: PartialEq>(
+	conclusion: &Expression<ResourceOrVar<T>>,
+	hypothesis: &ResourceOrVar<T>,
+) -> bool {
+	match (conclusion, hypothesis) {
+		(Expression::Resource(ResourceOrVar::Resource(a)), ResourceOrVar::Resource(b)) => a == b,
+		_ => true,
+	}
+}
+
+/// Checks that the given conclusion term may produce the given resource.
+fn may_be<T: PartialEq>(conclusion: &Expression<ResourceOrVar<T>>, resource: &T) -> bool {
+	match conclusion {
+		Expression::Resource(ResourceOrVar::Resource(a)) => a == resource,
+		_ => true,
+	}
+}
+
+/// Hypothesis pattern, with the index of its rule.
+type RulePattern<'a, T> = (usize, &'a Pattern<T>);
+
+/// Hypothesis patterns (including the patterns of universally quantified
+/// conditions) of a list of rules, indexed by sign and predicate.
+struct PatternIndex<'a, T> {
+	/// Patterns with a given predicate.
+	given: HashMap<(Sign, &'a T), Vec<RulePattern<'a, T>>>,
+
+	/// Patterns with a variable predicate.
+	any: HashMap<Sign, Vec<RulePattern<'a, T>>>,
+}
+
+impl<'a, T: Eq + Hash> PatternIndex<'a, T> {
+	fn new(rules: &'a [Rule<T>]) -> Self {
+		let mut given: HashMap<_, Vec<_>> = HashMap::new();
+		let mut any: HashMap<_, Vec<_>> = HashMap::new();
+
+		for (j, rule) in rules.iter().enumerate() {
+			let patterns = rule
+				.hypothesis
+				.patterns
+				.iter()
+				.chain(rule.universals.iter().flat_map(ForAll::patterns));
+
+			for Signed(sign, pattern) in patterns {
+				match &pattern.1 {
+					ResourceOrVar::Resource(p) => {
+						given.entry((*sign, p)).or_default().push((j, pattern))
+					}
+					ResourceOrVar::Var(_) => any.entry(*sign).or_default().push((j, pattern)),
+				}
+			}
+		}
+
+		Self { given, any }
+	}
+
+	/// Returns the patterns of the given sign whose predicate may match the
+	/// given conclusion predicate.
+	fn candidates<'s>(
+		&'s self,
+		sign: Sign,
+		predicate: &'s Expression<ResourceOrVar<T>>,
+	) -> impl 's + Iterator<Item = RulePattern<'a, T>> {
+		let given: Box<dyn 's + Iterator<Item = &'s Vec<RulePattern<'a, T>>>> = match predicate {
+			Expression::Resource(ResourceOrVar::Resource(p)) => {
+				Box::new(self.given.get(&(sign, p)).into_iter())
+			}
+			_ => Box::new(
+				self.given
+					.iter()
+					.filter(move |((s, _), _)| *s == sign)
+					.map(|(_, patterns)| patterns),
+			),
+		};
+
+		given.chain(self.any.get(&sign)).flatten().copied()
+	}
+}
+
+impl<T: Eq + Hash> System<T> {
+	/// Computes the rule dependency graph of the system.
+	///
+	/// There is a dependency from a rule `i` to a rule `j` if a triple
+	/// concluded by `i` may match a hypothesis pattern of `j`. The graph is
+	/// conservative: variables (including non-linear ones) and expressions
+	/// are assumed to match anything. Equality statements are ignored.
+	///
+	/// The patterns of universally quantified conditions count as hypothesis
+	/// patterns, and list patterns depend on every positive `rdf:first` and
+	/// `rdf:rest` triple.
+	///
+	/// Hypothesis patterns are indexed by predicate, so each concluded
+	/// triple is only compared with the patterns it may match.
+	pub fn dependency_graph(&self) -> DependencyGraph<'_, T> {
+		let index = PatternIndex::new(&self.rules);
+		let list_rules: Vec<_> = self
+			.rules
+			.iter()
+			.enumerate()
+			.filter(|(_, rule)| !rule.lists.is_empty())
+			.collect();
+
+		let mut dependencies = BTreeSet::new();
+
+		for (i, rule) in self.rules.iter().enumerate() {
+			for MaybeTrusted(_, Signed(sign, statement)) in &rule.conclusion.statements {
+				if let TripleStatement::Triple(Triple(s, p, o)) = statement {
+					for (j, Triple(hs, _, ho)) in index.candidates(*sign, p) {
+						if may_match(s, hs) && may_match(o, ho) {
+							dependencies.insert((i, j));
+						}
+					}
+
+					if sign.is_positive() {
+						for (j, other) in &list_rules {
+							if other.lists.iter().any(|l| {
+								may_be(p, &l.vocabulary.first) || may_be(p, &l.vocabulary.rest)
+							}) {
+								dependencies.insert((i, *j));
+							}
+						}
+					}
+				}
+			}
+		}
+
+		DependencyGraph {
+			rules: &self.rules,
+			dependencies,
+		}
+	}
+}
+
+impl<T: Clone + Eq + Hash> System<T> {
+	/// Returns the sub-system of the rules that may contribute to triples
+	/// with one of the given predicates.
+	///
+	/// A rule contributes if it may conclude (positively or negatively) a
+	/// triple with a target predicate, or if it may feed, through the
+	/// [dependency graph](Self::dependency_graph), a contributing rule.
+	/// Closing a dataset with the slice gives the same target triples as
+	/// closing it with the whole system, as long as equality statements are
+	/// not taken into account.
+	pub fn slice_for_predicates(&self, predicates: &[T]) -> Self {
+		let graph = self.dependency_graph();
+
+		let mut stack: Vec<usize> =
+			self.rules
+				.iter()
+				.enumerate()
+				.filter(|(_, rule)| {
+					rule.conclusion.statements.iter().any(
+						|MaybeTrusted(_, Signed(_, statement))| match statement {
+							TripleStatement::Triple(Triple(_, p, _)) => match p {
+								Expression::Resource(ResourceOrVar::Resource(p)) => {
+									predicates.contains(p)
+								}
+								_ => true,
+							},
+							_ => false,
+						},
+					)
+				})
+				.map(|(i, _)| i)
+				.collect();
+
+		let mut selected = BTreeSet::new();
+		while let Some(i) = stack.pop() {
+			if selected.insert(i) {
+				stack.extend(graph.dependencies_of(i))
+			}
+		}
+
+		selected
+			.into_iter()
+			.map(|i| self.rules[i].clone())
+			.collect()
+	}
+}
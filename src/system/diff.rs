@@ -0,0 +1,121 @@
+use std::hash::Hash;
+
+use super::{Rule, System};
+
+/// Difference between two deduction systems.
+///
+/// See [`System::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemDiff<'a, T> {
+	/// Rules of the new system absent from the old one.
+	pub added: Vec<&'a Rule<T>>,
+
+	/// Rules of the old system absent from the new one.
+	pub removed: Vec<&'a Rule<T>>,
+
+	/// Rules sharing the same identifier in both systems, but with
+	/// different definitions (old rule first).
+	pub modified: Vec<(&'a Rule<T>, &'a Rule<T>)>,
+}
+
+impl<'a, T: PartialEq> SystemDiff<'a, T> {
+	/// Checks that both systems have the same rules.
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+	}
+
+	/// Checks the compatibility of the new system with the old one.
+	///
+	/// The check is syntactic, and hence conservative: a change may be
+	/// reported as incompatible even though it is semantically harmless.
+	pub fn compatibility(&self) -> Compatibility {
+		Compatibility {
+			monotonic: self.removed.is_empty()
+				&& self
+					.modified
+					.iter()
+					.all(|(old, new)| same_hypothesis(old, new) && concludes_more(new, old)),
+			preserves_validity: self.added.iter().all(|r| !r.severity.is_error())
+				&& self.modified.iter().all(|(old, new)| {
+					!new.severity.is_error()
+						|| (old.severity.is_error()
+							&& same_hypothesis(old, new)
+							&& concludes_more(old, new))
+				}),
+		}
+	}
+}
+
+/// Compatibility of a new deduction system with an old one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Compatibility {
+	/// The new system can only add entailments: whatever was deduced by the
+	/// old system is still deduced.
+	pub monotonic: bool,
+
+	/// Datasets valid according to the old system are still valid according
+	/// to the new one.
+	pub preserves_validity: bool,
+}
+
+/// Checks that `a` and `b` are triggered by the same facts, in the same
+/// profiles.
+fn same_hypothesis<T: PartialEq>(a: &Rule<T>, b: &Rule<T>) -> bool {
+	a.variables == b.variables
+		&& a.hypothesis == b.hypothesis
+		&& a.lists == b.lists
+		&& a.universals == b.universals
+		&& a.profiles == b.profiles
+}
+
+/// Checks that `a` concludes at least every statement concluded by `b`.
+fn concludes_more<T: PartialEq>(a: &Rule<T>, b: &Rule<T>) -> bool {
+	a.conclusion.variables == b.conclusion.variables
+		&& b.conclusion
+			.statements
+			.iter()
+			.all(|s| a.conclusion.statements.contains(s))
+}
+
+impl<T: Clone + Eq + Hash> System<T> {
+	/// Computes the difference between this system and the `new` one.
+	///
+	/// Rules are matched by identifier. Rules without identifier are only
+	/// matched by equality, and hence can only be added or removed.
+	pub fn diff<'a>(&'a self, new: &'a Self) -> SystemDiff<'a, T> {
+		let mut diff = SystemDiff {
+			added: Vec::new(),
+			removed: Vec::new(),
+			modified: Vec::new(),
+		};
+
+		for old_rule in &self.rules {
+			let new_rule = match &old_rule.id {
+				Some(id) => new.get_by_id(id),
+				None => new.map.get(old_rule).map(|&i| &new.rules[i]),
+			};
+
+			match new_rule {
+				Some(new_rule) => {
+					if new_rule != old_rule {
+						diff.modified.push((old_rule, new_rule))
+					}
+				}
+				None => diff.removed.push(old_rule),
+			}
+		}
+
+		for new_rule in &new.rules {
+			let matched = match &new_rule.id {
+				Some(id) => self.get_by_id(id).is_some(),
+				None => self.map.contains_key(new_rule),
+			};
+
+			if !matched {
+				diff.added.push(new_rule)
+			}
+		}
+
+		diff
+	}
+}
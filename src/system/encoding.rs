@@ -0,0 +1,105 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::hash::Hash;
+
+use super::System;
+
+/// Magic bytes starting every encoded system.
+const MAGIC: [u8; 4] = *b"IRDF";
+
+/// Current system encoding version.
+///
+/// Must be incremented every time the binary representation of rules
+/// changes. Version 2 added the universal conditions and list patterns of
+/// rule hypotheses.
+pub const SYSTEM_ENCODING_VERSION: u16 = 2;
+
+/// Magic bytes starting every encoded network.
+const NETWORK_MAGIC: [u8; 4] = *b"IRDN";
+
+/// Current network encoding version.
+///
+/// Must be incremented every time the binary representation of rules or
+/// of the network state changes.
+pub const NETWORK_ENCODING_VERSION: u16 = 1;
+
+/// System encoding error.
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct EncodeError(#[from] ciborium::ser::Error<std::io::Error>);
+
+/// System or network decoding error.
+#[derive(Debug, thiserror::Error)]
+pub enum DecodeError {
+	#[error("truncated envelope header")]
+	Truncated,
+
+	#[error("invalid magic bytes")]
+	InvalidMagic,
+
+	#[error("unsupported encoding version {0}")]
+	UnsupportedVersion(u16),
+
+	#[error("network compiled from another system")]
+	SystemMismatch,
+
+	#[error(transparent)]
+	Cbor(#[from] ciborium::de::Error<std::io::Error>),
+}
+
+impl<T: Serialize> System<T> {
+	/// Encodes the system into bytes.
+	///
+	/// The rules are encoded in CBOR, prefixed with an envelope header made of
+	/// magic bytes and the [encoding version](SYSTEM_ENCODING_VERSION).
+	pub fn to_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+		encode(MAGIC, SYSTEM_ENCODING_VERSION, self)
+	}
+}
+
+impl<T: Clone + Eq + Hash + DeserializeOwned> System<T> {
+	/// Decodes a system previously encoded with [`Self::to_bytes`].
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+		decode(MAGIC, SYSTEM_ENCODING_VERSION, bytes)
+	}
+}
+
+/// Encodes the given network state (see [`Network::to_bytes`](super::Network::to_bytes)).
+pub(crate) fn encode_network(value: &impl Serialize) -> Result<Vec<u8>, EncodeError> {
+	encode(NETWORK_MAGIC, NETWORK_ENCODING_VERSION, value)
+}
+
+/// Decodes a network state (see [`Network::from_bytes`](super::Network::from_bytes)).
+pub(crate) fn decode_network<S: DeserializeOwned>(bytes: &[u8]) -> Result<S, DecodeError> {
+	decode(NETWORK_MAGIC, NETWORK_ENCODING_VERSION, bytes)
+}
+
+/// Encodes the given value in CBOR, prefixed with the envelope header.
+fn encode(magic: [u8; 4], version: u16, value: &impl Serialize) -> Result<Vec<u8>, EncodeError> {
+	let mut bytes = magic.to_vec();
+	bytes.extend(version.to_le_bytes());
+	ciborium::into_writer(value, &mut bytes)?;
+	Ok(bytes)
+}
+
+/// Decodes a value encoded with [`encode`].
+fn decode<S: DeserializeOwned>(
+	magic: [u8; 4],
+	version: u16,
+	bytes: &[u8],
+) -> Result<S, DecodeError> {
+	if bytes.len() < magic.len() + 2 {
+		return Err(DecodeError::Truncated);
+	}
+
+	let (found_magic, rest) = bytes.split_at(magic.len());
+	if found_magic != magic {
+		return Err(DecodeError::InvalidMagic);
+	}
+
+	let found = u16::from_le_bytes([rest[0], rest[1]]);
+	if found != version {
+		return Err(DecodeError::UnsupportedVersion(found));
+	}
+
+	Ok(ciborium::from_reader(&rest[2..])?)
+}
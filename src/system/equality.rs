@@ -0,0 +1,72 @@
+use rdf_types::Term;
+
+use crate::same_as::{Contradiction, Identities};
+
+use super::DeductionsInstance;
+
+/// Equality handler.
+///
+/// Receives the equality (`a = b`) and inequality (`! a = b`) statements
+/// deduced by a system, typically to merge the equal resources.
+pub trait EqualityHandler<T> {
+	type Error;
+
+	/// Handles the deduced equality `a = b`.
+	///
+	/// Returns `true` if it was not already known.
+	fn insert_eq(&mut self, a: &T, b: &T) -> Result<bool, Self::Error>;
+
+	/// Handles the deduced inequality `! a = b`.
+	///
+	/// Returns `true` if it was not already known.
+	fn insert_ne(&mut self, a: &T, b: &T) -> Result<bool, Self::Error>;
+}
+
+impl EqualityHandler<Term> for Identities {
+	type Error = Contradiction;
+
+	fn insert_eq(&mut self, a: &Term, b: &Term) -> Result<bool, Contradiction> {
+		if self.are_same(a, b) {
+			Ok(false)
+		} else {
+			self.merge(a, b).map(|_| true)
+		}
+	}
+
+	fn insert_ne(&mut self, a: &Term, b: &Term) -> Result<bool, Contradiction> {
+		if self.are_different(a, b) {
+			Ok(false)
+		} else {
+			self.separate(a, b).map(|_| true)
+		}
+	}
+}
+
+impl<'r, T> DeductionsInstance<'r, T> {
+	/// Passes every deduced equality and inequality statement to the given
+	/// handler.
+	///
+	/// Returns `true` if any of them was not already known by the handler.
+	/// The statements are applied to a copy of the handler, so that on error
+	/// the handler is left unchanged.
+	pub fn apply_equalities<H: EqualityHandler<T> + Clone>(
+		&self,
+		handler: &mut H,
+	) -> Result<bool, H::Error> {
+		let mut staged = handler.clone();
+		let mut changed = false;
+
+		for deduction in self {
+			for (a, b) in deduction.equalities() {
+				changed |= staged.insert_eq(a, b)?
+			}
+
+			for (a, b) in deduction.inequalities() {
+				changed |= staged.insert_ne(a, b)?
+			}
+		}
+
+		*handler = staged;
+		Ok(changed)
+	}
+}
@@ -0,0 +1,107 @@
+use std::{
+	collections::HashMap,
+	hash::{DefaultHasher, Hash, Hasher},
+};
+
+use iref::IriBuf;
+use rdf_types::{interpretation::IriInterpretationMut, InterpretationMut, Term, VocabularyMut};
+
+use crate::Entailment;
+
+/// Policy applied to the existential variables of rule conclusions when
+/// evaluating deductions.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub enum ExistentialPolicy {
+	/// Each evaluation generates fresh resources.
+	#[default]
+	Fresh,
+
+	/// Existential variables are replaced by skolem IRIs, made of the given
+	/// prefix followed by a hash of the entailment and the variable index.
+	///
+	/// The same entailment always gives the same IRIs for a given build of
+	/// this library.
+	Skolem(IriBuf),
+
+	/// Fresh resources are generated the first time an entailment is
+	/// evaluated, and reused when the same rule fires again with the same
+	/// substitution.
+	Reuse,
+}
+
+/// Existential resources allocator.
+///
+/// Generates the resources bound to the existential variables of rule
+/// conclusions according to an [`ExistentialPolicy`]. The allocator keeps
+/// track of the resources generated for each entailment, so it must be kept
+/// across evaluations for [`ExistentialPolicy::Reuse`] to be effective.
+pub struct Existentials<'r, T = Term> {
+	policy: ExistentialPolicy,
+	cache: HashMap<Entailment<'r, T>, Vec<T>>,
+}
+
+impl<'r, T> Existentials<'r, T> {
+	pub fn new(policy: ExistentialPolicy) -> Self {
+		Self {
+			policy,
+			cache: HashMap::new(),
+		}
+	}
+
+	pub fn policy(&self) -> &ExistentialPolicy {
+		&self.policy
+	}
+}
+
+impl<'r, T> Default for Existentials<'r, T> {
+	fn default() -> Self {
+		Self::new(ExistentialPolicy::default())
+	}
+}
+
+impl<'r, T: Clone + Eq + Hash> Existentials<'r, T> {
+	/// Returns the resources bound to the existential variables of the given
+	/// entailment's rule conclusion, in order.
+	pub fn resources<V, I>(
+		&mut self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		entailment: &Entailment<'r, T>,
+	) -> Vec<T>
+	where
+		V: VocabularyMut,
+		I: InterpretationMut<V, Resource = T> + IriInterpretationMut<V::Iri>,
+	{
+		let n = entailment.rule.conclusion.variables;
+		if n == 0 {
+			return Vec::new();
+		}
+
+		match &self.policy {
+			ExistentialPolicy::Fresh => (0..n)
+				.map(|_| interpretation.new_resource(vocabulary))
+				.collect(),
+			ExistentialPolicy::Skolem(prefix) => {
+				let mut hasher = DefaultHasher::new();
+				entailment.hash(&mut hasher);
+				let hash = hasher.finish();
+
+				(0..n)
+					.map(|i| {
+						let iri = IriBuf::new(format!("{prefix}{hash:016x}-{i}")).unwrap();
+						interpretation.interpret_iri(vocabulary.insert(&iri))
+					})
+					.collect()
+			}
+			ExistentialPolicy::Reuse => self
+				.cache
+				.entry(entailment.clone())
+				.or_insert_with(|| {
+					(0..n)
+						.map(|_| interpretation.new_resource(vocabulary))
+						.collect()
+				})
+				.clone(),
+		}
+	}
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeSet;
+
+use rdf_types::{dataset::IndexedBTreeDataset, interpretation::WithGenerator, Triple};
+
+use crate::{expression, Reason, Sign, Signed, TripleStatement, Violation};
+
+use super::{closure::blank_generator, System};
+
+/// Speculative impact of new facts on a dataset.
+///
+/// See [`System::what_if`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Impact {
+	/// Positive triples that would be deduced, not already in the dataset.
+	pub conclusions: BTreeSet<Triple>,
+
+	/// Negative triples that would be deduced.
+	pub negative_conclusions: BTreeSet<Triple>,
+
+	/// Hard violations that would arise, not already present before.
+	///
+	/// This includes the negative conclusions contradicting a positive fact
+	/// of the speculative dataset.
+	pub contradictions: Vec<Violation>,
+
+	/// Whether the deduction stopped because the depth limit was reached
+	/// while new conclusions were still being deduced.
+	pub truncated: bool,
+}
+
+impl Impact {
+	/// Checks if the added facts would not introduce any contradiction.
+	pub fn is_consistent(&self) -> bool {
+		self.contradictions.is_empty()
+	}
+}
+
+impl System {
+	/// Speculatively inserts the given facts into the dataset, and reports
+	/// which new conclusions and contradictions would arise, without
+	/// modifying the dataset.
+	///
+	/// Deduction is performed up to `max_depth` rounds, each round deducing
+	/// facts from the conclusions of the previous one (starting with the
+	/// added facts). Every violation of the speculative dataset is compared
+	/// with the violations of the original dataset, not only the first
+	/// violation of each rule.
+	pub fn what_if(
+		&self,
+		dataset: &IndexedBTreeDataset,
+		added_facts: impl IntoIterator<Item = Triple>,
+		max_depth: usize,
+	) -> Result<Impact, expression::Error> {
+		let mut impact = Impact::default();
+		let mut speculative = dataset.clone();
+		let mut interpretation = WithGenerator::new((), blank_generator(dataset));
+		let mut negatives = Vec::new();
+
+		let mut frontier = Vec::new();
+		for triple in added_facts {
+			if speculative.insert(triple.clone().into_quad(None)) {
+				frontier.push(triple)
+			}
+		}
+
+		for _ in 0..max_depth {
+			if frontier.is_empty() {
+				break;
+			}
+
+			let mut next = Vec::new();
+			for triple in &frontier {
+				let deductions = self
+					.deduce_from_triple(&speculative, Signed(Sign::Positive, triple.as_ref()))
+					.eval_with(&mut (), &mut interpretation)?;
+
+				for deduction in deductions {
+					for Signed(sign, statement) in deduction.statements {
+						if let TripleStatement::Triple(t) = statement {
+							match sign {
+								Sign::Positive => {
+									if !speculative.contains(t.as_ref().into_quad(None)) {
+										impact.conclusions.insert(t.clone());
+										next.push(t)
+									}
+								}
+								Sign::Negative => {
+									impact.negative_conclusions.insert(t.clone());
+									if deduction.entailment.rule.severity.is_error() {
+										negatives.push((
+											t,
+											deduction.entailment.rule,
+											deduction.entailment.substitution.clone(),
+										))
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+
+			for t in &next {
+				speculative.insert(t.clone().into_quad(None));
+			}
+
+			frontier = next;
+		}
+
+		impact.truncated = !frontier.is_empty();
+
+		// Violations already present, or already reported.
+		let mut known: BTreeSet<Violation> =
+			self.full_report(dataset)?.violations.into_iter().collect();
+		let after = self.full_report(&speculative)?;
+		let mut contradictions: Vec<Violation> = after.errors().cloned().collect();
+
+		// Negative conclusions contradicting a stated or deduced fact.
+		for (t, rule, substitution) in negatives {
+			if speculative.contains(t.as_ref().into_quad(None)) {
+				contradictions.push(Violation {
+					rule: self.index_of_rule(rule).unwrap(),
+					severity: rule.severity,
+					reason: Reason::MissingTriple(Signed(Sign::Negative, t)),
+					substitution,
+				})
+			}
+		}
+
+		for v in contradictions {
+			if known.insert(v.clone()) {
+				impact.contradictions.push(v)
+			}
+		}
+
+		Ok(impact)
+	}
+}
@@ -1,25 +1,73 @@
 //! Deduction systems.
 use crate::{
-	expression, pattern::TripleMatching, FallibleSignedPatternMatchingDataset, Signed,
-	SignedPatternMatchingDataset, Validation, ValidationError,
+	dataset::cache::CachedDataset,
+	expression,
+	pattern::{TripleMatching, TryMapResources},
+	Entailment, FallibleSignedPatternMatchingDataset, Signed, SignedPatternMatchingDataset,
+	Validation, ValidationError, ValidationReport, Violation,
 };
 pub use crate::{
 	pattern,
-	rule::{Path, Rule},
+	rule::{AnonymousResource, Path, Rule, Severity},
 };
 use educe::Educe;
 use rdf_types::{
-	interpretation::{LiteralInterpretationMut, ReverseTermInterpretation},
-	InterpretationMut, Term, Triple, VocabularyMut,
+	interpretation::{
+		IriInterpretationMut, LiteralInterpretationMut, ReverseTermInterpretation,
+		TermInterpretationMut,
+	},
+	InterpretationMut, Term, Triple, Vocabulary, VocabularyMut,
 };
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, hash::Hash};
 
+mod algebra;
+pub use algebra::*;
+
+mod closure;
+pub use closure::*;
+
+mod datalog;
+pub use datalog::*;
+
 mod deduction;
 pub use deduction::*;
 
 mod deduction_intstance;
 pub use deduction_intstance::*;
 
+mod dependency;
+pub use dependency::*;
+
+mod diff;
+pub use diff::*;
+
+mod encoding;
+pub use encoding::*;
+
+mod equality;
+pub use equality::*;
+
+mod existential;
+pub use existential::*;
+
+mod impact;
+pub use impact::*;
+
+mod network;
+pub use network::*;
+
+mod ontology;
+
+mod shared;
+pub use shared::*;
+
+mod termination;
+pub use termination::*;
+
+mod trace;
+pub use trace::*;
+
 /// Deduction system (collection of rules).
 #[derive(Debug, Educe)]
 #[educe(Default)]
@@ -54,6 +102,30 @@ impl<T> System<T> {
 		self.rules.get(i)
 	}
 
+	/// Returns the index of the rule with the given identifier, if any.
+	pub fn index_of(&self, id: &T) -> Option<usize>
+	where
+		T: PartialEq,
+	{
+		self.rules.iter().position(|r| r.id.as_ref() == Some(id))
+	}
+
+	/// Returns the index of the given rule in the system, if any.
+	pub fn index_of_rule(&self, rule: &Rule<T>) -> Option<usize>
+	where
+		T: Eq + Hash,
+	{
+		self.map.get(rule).copied()
+	}
+
+	/// Returns the rule with the given identifier, if any.
+	pub fn get_by_id(&self, id: &T) -> Option<&Rule<T>>
+	where
+		T: PartialEq,
+	{
+		self.index_of(id).map(|i| &self.rules[i])
+	}
+
 	/// Inserts the given rule in the system.
 	pub fn insert(&mut self, rule: Rule<T>) -> usize
 	where
@@ -71,8 +143,24 @@ impl<T> System<T> {
 		})
 	}
 
+	/// Returns the sub-system of rules active when any of the given profiles
+	/// is selected.
+	///
+	/// Rules without profile are always included. Rules are renumbered in
+	/// the returned system.
+	pub fn with_profiles<P: AsRef<str>>(&self, profiles: &[P]) -> Self
+	where
+		T: Clone + Eq + Hash,
+	{
+		self.rules
+			.iter()
+			.filter(|r| r.is_active_in(profiles))
+			.cloned()
+			.collect()
+	}
+
 	/// Returns an iterator over the rules of the system.
-	pub fn iter(&self) -> std::slice::Iter<Rule<T>> {
+	pub fn iter(&self) -> std::slice::Iter<'_, Rule<T>> {
 		self.rules.iter()
 	}
 
@@ -105,23 +193,136 @@ impl<T> IntoIterator for System<T> {
 	}
 }
 
+impl<T: Clone + Eq + Hash> FromIterator<Rule<T>> for System<T> {
+	fn from_iter<I: IntoIterator<Item = Rule<T>>>(iter: I) -> Self {
+		let mut result = Self::new();
+
+		for rule in iter {
+			result.insert(rule);
+		}
+
+		result
+	}
+}
+
+impl<T: Clone + Eq + Hash> Extend<Rule<T>> for System<T> {
+	fn extend<I: IntoIterator<Item = Rule<T>>>(&mut self, iter: I) {
+		for rule in iter {
+			self.insert(rule);
+		}
+	}
+}
+
+/// Serializes the system as its list of rules.
+impl<T: Serialize> Serialize for System<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		self.rules.serialize(serializer)
+	}
+}
+
+/// Deserializes a list of rules, rebuilding the system indexes.
+impl<'de, T: Clone + Eq + Hash + Deserialize<'de>> Deserialize<'de> for System<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		Ok(Vec::<Rule<T>>::deserialize(deserializer)?
+			.into_iter()
+			.collect())
+	}
+}
+
+impl<T, U: Clone + Eq + Hash> TryMapResources<T, U> for System<T> {
+	type Output = System<U>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		let mut result = System::new();
+
+		for rule in self {
+			result.insert(rule.try_map_resources(f)?);
+		}
+
+		Ok(result)
+	}
+}
+
+impl System {
+	/// Interprets the terms of this system, turning it into a system over the
+	/// resources of the given interpretation.
+	pub fn interpret<V, I>(self, vocabulary: &mut V, interpretation: &mut I) -> System<I::Resource>
+	where
+		V: VocabularyMut,
+		I: TermInterpretationMut<V::Iri, V::BlankId, V::Literal>,
+		I::Resource: Clone + Eq + Hash,
+	{
+		let mut result = System::new();
+
+		for rule in self {
+			result.insert(rule.interpret(vocabulary, interpretation));
+		}
+
+		result
+	}
+}
+
+impl<R> System<R> {
+	/// Turns this system over interpreted resources back into a system over
+	/// terms.
+	///
+	/// See [`Rule::uninterpret`] for how terms are chosen.
+	pub fn uninterpret<V, I>(
+		self,
+		vocabulary: &V,
+		interpretation: &I,
+	) -> Result<System, AnonymousResource<R>>
+	where
+		V: Vocabulary,
+		I: ReverseTermInterpretation<
+			Resource = R,
+			Iri = V::Iri,
+			BlankId = V::BlankId,
+			Literal = V::Literal,
+		>,
+	{
+		let mut result = System::new();
+
+		for rule in self {
+			result.insert(rule.uninterpret(vocabulary, interpretation)?);
+		}
+
+		Ok(result)
+	}
+}
+
 impl<T: Clone + Eq + Hash> System<T> {
 	/// Deduce new facts form the give dataset.
-	pub fn deduce<D>(&self, dataset: &D) -> Deductions<T>
+	///
+	/// Each cause is returned: deductions deducing the same statements are
+	/// not merged (see [`Deductions::dedup`]).
+	pub fn deduce<D>(&self, dataset: &D) -> Deductions<'_, T>
 	where
 		D: SignedPatternMatchingDataset<Resource = T>,
 	{
-		let mut deductions = Deductions::default();
-		for rule in &self.rules {
-			deductions.merge_with(rule.deduce(dataset))
-		}
-		deductions
+		self.try_deduce(dataset).unwrap()
 	}
 
 	/// Deduce new facts from the given triple.
 	///
-	/// This function only uses existential rules to deduce facts.
-	pub fn deduce_from_triple<D>(&self, dataset: &D, triple: Signed<Triple<&T>>) -> Deductions<T>
+	/// Only the rules with a hypothesis pattern of the same sign as the
+	/// triple are triggered: a negative triple triggers the rules through
+	/// their negative patterns. Use a signed dataset such as
+	/// [`Bipolar`](crate::Bipolar) to store negative facts.
+	pub fn deduce_from_triple<D>(
+		&self,
+		dataset: &D,
+		triple: Signed<Triple<&T>>,
+	) -> Deductions<'_, T>
 	where
 		D: SignedPatternMatchingDataset<Resource = T>,
 	{
@@ -130,12 +331,14 @@ impl<T: Clone + Eq + Hash> System<T> {
 
 	/// Deduce new facts from the given triple.
 	///
-	/// This function only uses existential rules to deduce facts.
+	/// Only the rules with a hypothesis pattern of the same sign as the
+	/// triple are triggered. Each cause is returned: deductions deducing the
+	/// same statements are not merged (see [`Deductions::dedup`]).
 	pub fn try_deduce_from_triple<D>(
 		&self,
 		dataset: &D,
 		triple: Signed<Triple<&T>>,
-	) -> Result<Deductions<T>, D::Error>
+	) -> Result<Deductions<'_, T>, D::Error>
 	where
 		D: FallibleSignedPatternMatchingDataset<Resource = T>,
 	{
@@ -149,24 +352,77 @@ impl<T: Clone + Eq + Hash> System<T> {
 	}
 
 	/// Deduce new facts form the give dataset.
-	pub fn try_deduce<D>(&self, dataset: &D) -> Result<Deductions<T>, D::Error>
+	///
+	/// Each cause is returned: deductions deducing the same statements are
+	/// not merged (see [`Deductions::dedup`]). Rules sharing hypothesis
+	/// patterns share the pattern matching results (see [`CachedDataset`]).
+	pub fn try_deduce<D>(&self, dataset: &D) -> Result<Deductions<'_, T>, D::Error>
 	where
 		D: FallibleSignedPatternMatchingDataset<Resource = T>,
 	{
+		let dataset = CachedDataset::new(dataset);
 		let mut deductions = Deductions::default();
 		for rule in &self.rules {
-			deductions.merge_with(rule.try_deduce(dataset)?)
+			deductions.merge_with(rule.try_deduce(&dataset)?)
 		}
 		Ok(deductions)
 	}
 
+	/// Deduce new facts from the given dataset using only the rule with the
+	/// given identifier.
+	///
+	/// Returns `None` if no rule has this identifier. For a system over
+	/// interpreted resources, the identifier is the interpretation of the
+	/// rule IRI.
+	pub fn deduce_rule<D>(&self, id: &T, dataset: &D) -> Option<Deductions<'_, T>>
+	where
+		D: SignedPatternMatchingDataset<Resource = T>,
+	{
+		self.get_by_id(id).map(|rule| rule.deduce(dataset))
+	}
+
+	/// Deduce new facts from the given dataset using only the rule with the
+	/// given identifier.
+	///
+	/// Returns `None` if no rule has this identifier.
+	pub fn try_deduce_rule<D>(
+		&self,
+		id: &T,
+		dataset: &D,
+	) -> Result<Option<Deductions<'_, T>>, D::Error>
+	where
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		self.get_by_id(id)
+			.map(|rule| rule.try_deduce(dataset))
+			.transpose()
+	}
+
+	/// Returns the entailments using the given fact of the dataset, that is,
+	/// whose hypothesis matches the fact.
+	///
+	/// These are the deductions supported by the fact, that may no longer
+	/// hold if it is retracted from the dataset. Entailments deducing the
+	/// same statements through different hypothesis matches are all
+	/// returned.
+	pub fn dependents<D>(&self, dataset: &D, fact: Signed<Triple<&T>>) -> Vec<Entailment<'_, T>>
+	where
+		D: SignedPatternMatchingDataset<Resource = T>,
+	{
+		self.paths
+			.get(fact)
+			.flat_map(|&path| self.try_deduce_from_path(dataset, fact, path).unwrap())
+			.map(|d| d.entailment)
+			.collect()
+	}
+
 	/// Deduce facts from the given rule path.
 	fn try_deduce_from_path<D>(
 		&self,
 		dataset: &D,
 		triple: Signed<Triple<&T>>,
 		path: Path,
-	) -> Result<Deductions<T>, D::Error>
+	) -> Result<Deductions<'_, T>, D::Error>
 	where
 		D: FallibleSignedPatternMatchingDataset<Resource = T>,
 	{
@@ -181,85 +437,201 @@ impl<T: Clone + Eq + Hash> System<T> {
 		rule.try_deduce_from(dataset, substitution, Some(path.pattern))
 	}
 
-	/// Validates the given dataset against this system
+	/// Validates the given dataset against this system.
 	///
 	/// Returns `Validation::Ok` if and only if any triple deduced from the
-	/// dataset is already in the dataset.
+	/// dataset by a [`Severity::Error`] rule is already in the dataset.
+	/// Use [`Self::report_with`] to also collect advisory violations.
 	pub fn validate_with<V, I, D>(
 		&self,
 		vocabulary: &mut V,
 		interpretation: &mut I,
 		dataset: &D,
-	) -> Result<Validation, expression::Error>
+	) -> Result<Validation<T>, expression::Error>
 	where
 		V: VocabularyMut,
 		V::Iri: PartialEq,
 		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
 			+ LiteralInterpretationMut<V::Literal>
 			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
 		D: SignedPatternMatchingDataset<Resource = T>,
 	{
-		for rule in &self.rules {
-			rule.validate_with(vocabulary, interpretation, dataset)?;
-		}
-
-		Ok(Validation::Ok)
+		self.try_validate_with(vocabulary, interpretation, dataset)
+			.map_err(Into::into)
 	}
 
-	/// Validates the given dataset against this system
+	/// Validates the given dataset against this system.
 	///
 	/// Returns `Validation::Ok` if and only if any triple deduced from the
-	/// dataset is already in the dataset.
+	/// dataset by a [`Severity::Error`] rule is already in the dataset.
+	/// Use [`Self::try_report_with`] to also collect advisory violations.
 	pub fn try_validate_with<V, I, D>(
 		&self,
 		vocabulary: &mut V,
 		interpretation: &mut I,
 		dataset: &D,
-	) -> Result<Validation, ValidationError<D::Error>>
+	) -> Result<Validation<T>, ValidationError<D::Error>>
 	where
 		V: VocabularyMut,
 		V::Iri: PartialEq,
 		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
 			+ LiteralInterpretationMut<V::Literal>
 			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
 		D: FallibleSignedPatternMatchingDataset<Resource = T>,
 	{
 		for rule in &self.rules {
-			rule.try_validate_with(vocabulary, interpretation, dataset)?;
+			if rule.severity.is_error() {
+				if let Validation::Invalid(reason) =
+					rule.try_validate_with(vocabulary, interpretation, dataset)?
+				{
+					return Ok(Validation::Invalid(reason));
+				}
+			}
 		}
 
 		Ok(Validation::Ok)
 	}
+
+	/// Validates the given dataset against every rule of this system,
+	/// whatever its severity.
+	pub fn report_with<V, I, D>(
+		&self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		dataset: &D,
+	) -> Result<ValidationReport<T>, expression::Error>
+	where
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+		D: SignedPatternMatchingDataset<Resource = T>,
+	{
+		self.try_report_with(vocabulary, interpretation, dataset)
+			.map_err(Into::into)
+	}
+
+	/// Validates the given dataset against every rule of this system,
+	/// whatever its severity.
+	pub fn try_report_with<V, I, D>(
+		&self,
+		vocabulary: &mut V,
+		interpretation: &mut I,
+		dataset: &D,
+	) -> Result<ValidationReport<T>, ValidationError<D::Error>>
+	where
+		V: VocabularyMut,
+		V::Iri: PartialEq,
+		I: InterpretationMut<V, Resource = T>
+			+ IriInterpretationMut<V::Iri>
+			+ LiteralInterpretationMut<V::Literal>
+			+ ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		let mut report = ValidationReport::default();
+
+		for (i, rule) in self.rules.iter().enumerate() {
+			if let Some((reason, substitution)) =
+				rule.try_find_violation_with(vocabulary, interpretation, dataset)?
+			{
+				report.violations.push(Violation {
+					rule: i,
+					severity: rule.severity,
+					reason,
+					substitution,
+				})
+			}
+		}
+
+		Ok(report)
+	}
 }
 
 impl System {
-	/// Validates the given dataset against this system
+	/// Validates the given dataset against this system.
 	///
 	/// Returns `Validation::Ok` if and only if any triple deduced from the
-	/// dataset is already in the dataset.
+	/// dataset by a [`Severity::Error`] rule is already in the dataset.
 	pub fn validate<D>(&self, dataset: &D) -> Result<Validation, expression::Error>
 	where
 		D: SignedPatternMatchingDataset<Resource = Term>,
 	{
-		for rule in &self.rules {
-			rule.validate(dataset)?;
-		}
-
-		Ok(Validation::Ok)
+		self.try_validate(dataset).map_err(Into::into)
 	}
 
-	/// Validates the given dataset against this system
+	/// Validates the given dataset against this system.
 	///
 	/// Returns `Validation::Ok` if and only if any triple deduced from the
-	/// dataset is already in the dataset.
+	/// dataset by a [`Severity::Error`] rule is already in the dataset.
 	pub fn try_validate<D>(&self, dataset: &D) -> Result<Validation, ValidationError<D::Error>>
 	where
 		D: FallibleSignedPatternMatchingDataset<Resource = Term>,
 	{
 		for rule in &self.rules {
-			rule.try_validate(dataset)?;
+			if rule.severity.is_error() {
+				if let Validation::Invalid(reason) = rule.try_validate(dataset)? {
+					return Ok(Validation::Invalid(reason));
+				}
+			}
 		}
 
 		Ok(Validation::Ok)
 	}
+
+	/// Validates the given dataset against every rule of this system,
+	/// whatever its severity.
+	pub fn report<D>(&self, dataset: &D) -> Result<ValidationReport, expression::Error>
+	where
+		D: SignedPatternMatchingDataset<Resource = Term>,
+	{
+		self.try_report(dataset).map_err(Into::into)
+	}
+
+	/// Validates the given dataset against every rule of this system,
+	/// whatever its severity.
+	pub fn try_report<D>(&self, dataset: &D) -> Result<ValidationReport, ValidationError<D::Error>>
+	where
+		D: FallibleSignedPatternMatchingDataset<Resource = Term>,
+	{
+		let mut report = ValidationReport::default();
+
+		for (i, rule) in self.rules.iter().enumerate() {
+			if let Some((reason, substitution)) = rule.try_find_violation(dataset)? {
+				report.violations.push(Violation {
+					rule: i,
+					severity: rule.severity,
+					reason,
+					substitution,
+				})
+			}
+		}
+
+		Ok(report)
+	}
+
+	/// Validates the given dataset against every rule of this system,
+	/// reporting every violation of each rule instead of only the first one.
+	pub(crate) fn full_report<D>(&self, dataset: &D) -> Result<ValidationReport, expression::Error>
+	where
+		D: SignedPatternMatchingDataset<Resource = Term>,
+	{
+		let mut report = ValidationReport::default();
+
+		for (i, rule) in self.rules.iter().enumerate() {
+			for (reason, substitution) in rule.try_find_violations(dataset)? {
+				report.violations.push(Violation {
+					rule: i,
+					severity: rule.severity,
+					reason,
+					substitution,
+				})
+			}
+		}
+
+		Ok(report)
+	}
 }
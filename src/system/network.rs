@@ -0,0 +1,418 @@
+//! Incremental matching network.
+use std::{
+	collections::{HashMap, HashSet},
+	hash::Hash,
+};
+
+use rdf_types::{Term, Triple};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+	dataset::cache::pattern_key,
+	pattern::{Pattern, PatternSubstitution, ResourceOrVar, TripleMatching},
+	FallibleSignedPatternMatchingDataset, Rule, Signed, SignedPatternMatchingDataset,
+};
+
+use super::{
+	encoding::{decode_network, encode_network},
+	DecodeError, Deductions, EncodeError, Path, System,
+};
+
+/// Partial matches of a rule hypothesis.
+type Tokens<T> = Vec<PatternSubstitution<T>>;
+
+/// Incremental matching network compiled from a [`System`].
+///
+/// Rete-style network for long-running processes receiving a stream of
+/// facts. Each distinct hypothesis pattern of the system has an alpha memory
+/// retaining the triples it matches, shared between the rules. Each rule has
+/// beta memories retaining the substitutions matching the first patterns of
+/// its hypothesis. Inserting a triple only joins it with the retained
+/// substitutions and triples, instead of searching the whole dataset again
+/// like [`System::deduce_from_triple`].
+///
+/// The network trades memory for time: partial matches are never
+/// forgotten. Rules with an empty hypothesis are never triggered.
+pub struct Network<'r, T = Term> {
+	system: &'r System<T>,
+
+	/// Triples inserted in the network.
+	triples: HashSet<Signed<Triple<T>>>,
+
+	/// Alpha memories.
+	alphas: Vec<Vec<Triple<T>>>,
+
+	/// Network node of each rule, in system order.
+	nodes: Vec<Node<T>>,
+}
+
+/// Network node of a rule.
+struct Node<T> {
+	/// Alpha memory of each hypothesis pattern.
+	alphas: Vec<usize>,
+
+	/// Beta memories: the `i`-th memory stores the substitutions matching
+	/// the patterns `0..=i`. There is no memory for complete matches.
+	betas: Vec<Tokens<T>>,
+}
+
+impl<'r, T: Clone + Eq + Hash> Network<'r, T> {
+	/// Compiles the network of the given system.
+	///
+	/// The network is initially empty: insert the facts of an existing
+	/// dataset first to match new facts against it.
+	pub fn new(system: &'r System<T>) -> Self {
+		let mut keys = HashMap::new();
+		let mut alphas = Vec::new();
+
+		let nodes = system
+			.iter()
+			.map(|rule| {
+				let patterns = &rule.hypothesis.patterns;
+				Node {
+					alphas: patterns
+						.iter()
+						.map(|pattern| {
+							let canonical = pattern
+								.as_ref()
+								.map(|t| t.as_ref().map(ResourceOrVar::as_ref))
+								.cast();
+
+							*keys.entry(pattern_key(canonical)).or_insert_with(|| {
+								alphas.push(Vec::new());
+								alphas.len() - 1
+							})
+						})
+						.collect(),
+					betas: vec![Vec::new(); patterns.len().saturating_sub(1)],
+				}
+			})
+			.collect();
+
+		Self {
+			system,
+			triples: HashSet::new(),
+			alphas,
+			nodes,
+		}
+	}
+
+	/// Returns the system this network is compiled from.
+	pub fn system(&self) -> &'r System<T> {
+		self.system
+	}
+
+	/// Returns the number of triples inserted in the network.
+	pub fn len(&self) -> usize {
+		self.triples.len()
+	}
+
+	/// Checks if no triple has been inserted in the network.
+	pub fn is_empty(&self) -> bool {
+		self.triples.is_empty()
+	}
+
+	/// Returns the number of partial matches retained by the network.
+	pub fn partial_matches(&self) -> usize {
+		self.nodes
+			.iter()
+			.flat_map(|node| &node.betas)
+			.map(Vec::len)
+			.sum()
+	}
+
+	/// Inserts the given triple in the network, and deduces new facts from
+	/// the hypothesis matches it completes.
+	///
+	/// See [`Self::try_insert`].
+	pub fn insert<D>(&mut self, dataset: &D, triple: Signed<Triple<T>>) -> Deductions<'r, T>
+	where
+		D: SignedPatternMatchingDataset<Resource = T>,
+	{
+		self.try_insert(dataset, triple).unwrap()
+	}
+
+	/// Inserts the given triple in the network, and deduces new facts from
+	/// the hypothesis matches it completes.
+	///
+	/// Only the matches using the triple are returned: the other ones have
+	/// been returned when inserting their last triple. Inserting a triple
+	/// again returns no deduction. The dataset is only used to match the
+	/// list patterns and check the universal conditions of the completed
+	/// matches, and should contain the inserted triples. On error, the
+	/// network is left unchanged and the triple can be inserted again.
+	pub fn try_insert<D>(
+		&mut self,
+		dataset: &D,
+		triple: Signed<Triple<T>>,
+	) -> Result<Deductions<'r, T>, D::Error>
+	where
+		D: FallibleSignedPatternMatchingDataset<Resource = T>,
+	{
+		if self.triples.contains(&triple) {
+			return Ok(Deductions::default());
+		}
+
+		let system = self.system;
+		let t = triple.value().as_ref();
+		let activations = self.activations(&triple);
+
+		// Memories lengths before the insertion, restored if the deduction
+		// fails. Memories only grow, and each alpha memory receives the
+		// triple at most once.
+		let alphas = self.alphas_of(&activations);
+		let mut betas: Vec<(usize, Vec<usize>)> = Vec::new();
+		for path in &activations {
+			if betas.last().map(|(rule, _)| *rule) != Some(path.rule) {
+				let lengths = self.nodes[path.rule].betas.iter().map(Vec::len).collect();
+				betas.push((path.rule, lengths))
+			}
+		}
+
+		for &i in &alphas {
+			self.alphas[i].push(triple.value().clone())
+		}
+
+		let mut matches = Vec::new();
+		for path in activations {
+			let rule = system.get(path.rule).unwrap();
+			let patterns = &rule.hypothesis.patterns;
+			let node = &mut self.nodes[path.rule];
+
+			// Join the triple with the partial matches of the previous
+			// patterns.
+			let pattern = patterns[path.pattern].value();
+			let mut tokens: Tokens<T> = match path.pattern.checked_sub(1) {
+				Some(i) => node.betas[i]
+					.iter()
+					.filter_map(|s| join(pattern, s, t))
+					.collect(),
+				None => join(pattern, &PatternSubstitution::new(), t)
+					.into_iter()
+					.collect(),
+			};
+
+			// Join the new partial matches with the triples of the next
+			// patterns. The inserted triple is skipped there, since matching
+			// it again is done by the activation of the next pattern.
+			for (i, pattern) in patterns.iter().enumerate().skip(path.pattern) {
+				if i > path.pattern {
+					let pattern = pattern.value();
+					let alpha = &self.alphas[node.alphas[i]];
+					tokens = tokens
+						.iter()
+						.flat_map(|s| {
+							alpha
+								.iter()
+								.filter(|u| *u != triple.value())
+								.filter_map(move |u| join(pattern, s, u.as_ref()))
+						})
+						.collect();
+				}
+
+				if tokens.is_empty() {
+					break;
+				}
+
+				if let Some(beta) = node.betas.get_mut(i) {
+					beta.extend(tokens.iter().cloned())
+				}
+			}
+
+			matches.extend(tokens.into_iter().map(|s| (rule, s)))
+		}
+
+		match try_complete(dataset, matches) {
+			Ok(deductions) => {
+				self.triples.insert(triple);
+				Ok(deductions)
+			}
+			Err(e) => {
+				// Forget the triple, so that it can be inserted again.
+				for i in alphas {
+					self.alphas[i].pop();
+				}
+
+				for (rule, lengths) in betas {
+					for (beta, len) in self.nodes[rule].betas.iter_mut().zip(lengths) {
+						beta.truncate(len)
+					}
+				}
+
+				Err(e)
+			}
+		}
+	}
+
+	/// Returns the paths of the hypothesis patterns matching the given
+	/// triple.
+	///
+	/// Paths are sorted: activations of the same rule must be processed in
+	/// pattern order, so that each combination of patterns matched by the
+	/// triple is found exactly once, by the activation of its last pattern.
+	fn activations(&self, triple: &Signed<Triple<T>>) -> Vec<Path> {
+		let system = self.system;
+		let signed_triple = triple.as_ref().map(Triple::as_ref);
+		let mut activations: Vec<Path> = system
+			.paths
+			.get(signed_triple)
+			.copied()
+			.filter(|path| {
+				let pattern = &system.get(path.rule).unwrap().hypothesis.patterns[path.pattern];
+				pattern
+					.value()
+					.triple_matching(&mut PatternSubstitution::new(), signed_triple.into_value())
+			})
+			.collect();
+		activations.sort_unstable();
+		activations
+	}
+
+	/// Returns the distinct alpha memories of the given paths.
+	fn alphas_of(&self, activations: &[Path]) -> Vec<usize> {
+		let mut alphas: Vec<usize> = activations
+			.iter()
+			.map(|path| self.nodes[path.rule].alphas[path.pattern])
+			.collect();
+		alphas.sort_unstable();
+		alphas.dedup();
+		alphas
+	}
+
+	/// Stores the given triple in the alpha memories of the patterns it
+	/// matches.
+	fn remember(&mut self, triple: &Signed<Triple<T>>) {
+		let activations = self.activations(triple);
+		for i in self.alphas_of(&activations) {
+			self.alphas[i].push(triple.value().clone())
+		}
+	}
+}
+
+/// Matches the list patterns and checks the universal conditions of the
+/// given complete hypothesis matches, and deduces their conclusions.
+fn try_complete<'r, T, D>(
+	dataset: &D,
+	matches: Vec<(&'r Rule<T>, PatternSubstitution<T>)>,
+) -> Result<Deductions<'r, T>, D::Error>
+where
+	T: Clone + Eq + Hash,
+	D: FallibleSignedPatternMatchingDataset<Resource = T>,
+{
+	let mut deductions = Deductions::default();
+	for (rule, substitution) in matches {
+		for substitution in rule.try_match_lists(dataset, substitution) {
+			if let Some(deduction) = rule.try_conclude(dataset, substitution?) {
+				deductions.push(deduction?)
+			}
+		}
+	}
+
+	Ok(deductions)
+}
+
+impl<T: Clone + Serialize> Network<'_, T> {
+	/// Encodes the state of the network into bytes.
+	///
+	/// The rules of the system, the inserted triples and the partial matches
+	/// (beta memories) are encoded in CBOR, prefixed with an envelope header
+	/// made of magic bytes and the
+	/// [encoding version](super::NETWORK_ENCODING_VERSION). Alpha memories
+	/// are recomputed when decoding. Storing the network after a closure
+	/// allows continuing it later, with new facts, without joining the
+	/// stored facts again.
+	pub fn to_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+		encode_network(&StateRef {
+			system: self.system,
+			triples: &self.triples,
+			betas: self
+				.nodes
+				.iter()
+				.map(|node| {
+					node.betas
+						.iter()
+						.map(|tokens| tokens.iter().map(PatternSubstitution::to_vec).collect())
+						.collect()
+				})
+				.collect(),
+		})
+	}
+}
+
+impl<'r, T: Clone + Eq + Hash + DeserializeOwned> Network<'r, T> {
+	/// Decodes the network state previously encoded with
+	/// [`Self::to_bytes`], for the given system.
+	///
+	/// Fails with [`DecodeError::SystemMismatch`] if the network was not
+	/// compiled from a system with the same rules, in the same order.
+	pub fn from_bytes(system: &'r System<T>, bytes: &[u8]) -> Result<Self, DecodeError> {
+		let state: State<T> = decode_network(bytes)?;
+		let mut result = Self::new(system);
+
+		let same_shape = state.betas.len() == result.nodes.len()
+			&& state
+				.betas
+				.iter()
+				.zip(&result.nodes)
+				.all(|(betas, node)| betas.len() == node.betas.len());
+		if !same_shape || !system.iter().eq(state.system.iter()) {
+			return Err(DecodeError::SystemMismatch);
+		}
+
+		for triple in state.triples {
+			result.remember(&triple);
+			result.triples.insert(triple);
+		}
+
+		for (node, betas) in result.nodes.iter_mut().zip(state.betas) {
+			for (beta, tokens) in node.betas.iter_mut().zip(betas) {
+				*beta = tokens
+					.into_iter()
+					.map(|values| {
+						let mut substitution = PatternSubstitution::new();
+						for (x, value) in values.into_iter().enumerate() {
+							if let Some(value) = value {
+								substitution.bind(x, value);
+							}
+						}
+						substitution
+					})
+					.collect()
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+/// Partial matches of a rule hypothesis, as stored.
+type StoredTokens<T> = Vec<Vec<Option<T>>>;
+
+/// Network state to encode.
+#[derive(Serialize)]
+struct StateRef<'a, T> {
+	system: &'a System<T>,
+	triples: &'a HashSet<Signed<Triple<T>>>,
+	betas: Vec<Vec<StoredTokens<T>>>,
+}
+
+/// Decoded network state.
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "T: Clone + Eq + Hash + DeserializeOwned"))]
+struct State<T> {
+	system: System<T>,
+	triples: Vec<Signed<Triple<T>>>,
+	betas: Vec<Vec<StoredTokens<T>>>,
+}
+
+/// Extends the given substitution so that the pattern matches the triple.
+fn join<T: Clone + Eq + Hash>(
+	pattern: &Pattern<T>,
+	substitution: &PatternSubstitution<T>,
+	triple: Triple<&T>,
+) -> Option<PatternSubstitution<T>> {
+	let mut substitution = substitution.clone();
+	pattern
+		.triple_matching(&mut substitution, triple)
+		.then_some(substitution)
+}
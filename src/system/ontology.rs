@@ -0,0 +1,184 @@
+use iref::Iri;
+use rdf_types::{dataset::IndexedBTreeDataset, Quad, Term, Triple};
+use static_iref::iri;
+
+use crate::{
+	expression::{self, Expression},
+	pattern::ResourceOrVar,
+	rule::{Conclusion, Hypothesis, TripleStatementPattern},
+	MaybeTrusted, Rule, Sign, Signed, SignedPatternMatchingDataset, TripleStatement, Trust,
+};
+
+use super::System;
+
+const RDF_TYPE: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+const RDFS_SUB_CLASS_OF: &Iri = iri!("http://www.w3.org/2000/01/rdf-schema#subClassOf");
+const RDFS_SUB_PROPERTY_OF: &Iri = iri!("http://www.w3.org/2000/01/rdf-schema#subPropertyOf");
+const RDFS_DOMAIN: &Iri = iri!("http://www.w3.org/2000/01/rdf-schema#domain");
+const RDFS_RANGE: &Iri = iri!("http://www.w3.org/2000/01/rdf-schema#range");
+const OWL_INVERSE_OF: &Iri = iri!("http://www.w3.org/2002/07/owl#inverseOf");
+const OWL_TRANSITIVE_PROPERTY: &Iri = iri!("http://www.w3.org/2002/07/owl#TransitiveProperty");
+const OWL_SYMMETRIC_PROPERTY: &Iri = iri!("http://www.w3.org/2002/07/owl#SymmetricProperty");
+
+impl System {
+	/// Compiles the ontology axioms of the given dataset into rules.
+	///
+	/// The following axioms are compiled, each into one rule (two for
+	/// `owl:inverseOf`):
+	/// - `C rdfs:subClassOf D`: instances of `C` are instances of `D`;
+	/// - `P rdfs:subPropertyOf Q`: `?x P ?y` implies `?x Q ?y`;
+	/// - `P rdfs:domain C` and `P rdfs:range C`: subjects (resp. objects) of
+	///   `P` are instances of `C`;
+	/// - `P owl:inverseOf Q`: `?x P ?y` implies `?y Q ?x`, and conversely;
+	/// - `P rdf:type owl:TransitiveProperty` and
+	///   `P rdf:type owl:SymmetricProperty`.
+	///
+	/// Axioms whose subject or object is a literal are ignored.
+	pub fn from_ontology<D>(dataset: &D) -> Self
+	where
+		D: SignedPatternMatchingDataset<Resource = Term>,
+	{
+		let mut result = Self::new();
+		let ty = <Term>::iri(RDF_TYPE.to_owned());
+
+		for (c, d) in axioms(dataset, RDFS_SUB_CLASS_OF) {
+			result.insert(rule(
+				1,
+				[(var(0), res(&ty), res(&c))],
+				[(var(0), res(&ty), res(&d))],
+			));
+		}
+
+		for (p, q) in axioms(dataset, RDFS_SUB_PROPERTY_OF) {
+			result.insert(rule(
+				2,
+				[(var(0), res(&p), var(1))],
+				[(var(0), res(&q), var(1))],
+			));
+		}
+
+		for (p, c) in axioms(dataset, RDFS_DOMAIN) {
+			result.insert(rule(
+				2,
+				[(var(0), res(&p), var(1))],
+				[(var(0), res(&ty), res(&c))],
+			));
+		}
+
+		for (p, c) in axioms(dataset, RDFS_RANGE) {
+			result.insert(rule(
+				2,
+				[(var(0), res(&p), var(1))],
+				[(var(1), res(&ty), res(&c))],
+			));
+		}
+
+		for (p, q) in axioms(dataset, OWL_INVERSE_OF) {
+			result.insert(rule(
+				2,
+				[(var(0), res(&p), var(1))],
+				[(var(1), res(&q), var(0))],
+			));
+			result.insert(rule(
+				2,
+				[(var(0), res(&q), var(1))],
+				[(var(1), res(&p), var(0))],
+			));
+		}
+
+		let transitive = <Term>::iri(OWL_TRANSITIVE_PROPERTY.to_owned());
+		let symmetric = <Term>::iri(OWL_SYMMETRIC_PROPERTY.to_owned());
+		for (p, class) in axioms(dataset, RDF_TYPE) {
+			if class == transitive {
+				result.insert(rule(
+					3,
+					[(var(0), res(&p), var(1)), (var(1), res(&p), var(2))],
+					[(var(0), res(&p), var(2))],
+				));
+			} else if class == symmetric {
+				result.insert(rule(
+					2,
+					[(var(0), res(&p), var(1))],
+					[(var(1), res(&p), var(0))],
+				));
+			}
+		}
+
+		result
+	}
+
+	/// Computes the closure of the given dataset under this system, extended
+	/// with the rules compiled from the ontology axioms of the dataset.
+	///
+	/// Axioms are collected before the closure: axioms deduced during the
+	/// closure do not produce new rules. See [`Self::from_ontology`].
+	///
+	/// Returns the number of inserted triples.
+	pub fn close_with_ontology(
+		&self,
+		dataset: &mut IndexedBTreeDataset,
+	) -> Result<usize, expression::Error> {
+		let mut system: Self = self.iter().cloned().collect();
+		system.append(Self::from_ontology(&*dataset));
+		system.close(dataset)
+	}
+}
+
+/// Returns the subject and object of every positive triple with the given
+/// predicate, provided neither is a literal.
+fn axioms<D>(dataset: &D, predicate: &Iri) -> Vec<(Term, Term)>
+where
+	D: SignedPatternMatchingDataset<Resource = Term>,
+{
+	let predicate = <Term>::iri(predicate.to_owned());
+	let pattern = Triple(
+		ResourceOrVar::Var(0),
+		ResourceOrVar::Resource(&predicate),
+		ResourceOrVar::Var(1),
+	);
+
+	dataset
+		.signed_pattern_matching(Signed(Sign::Positive, pattern.into()))
+		.map(|Signed(_, Quad(s, _, o, _))| (s, o))
+		.filter(|(s, o)| !s.is_literal() && !o.is_literal())
+		.map(|(s, o)| (s.clone(), o.clone()))
+		.collect()
+}
+
+fn var(x: usize) -> ResourceOrVar {
+	ResourceOrVar::Var(x)
+}
+
+fn res(term: &Term) -> ResourceOrVar {
+	ResourceOrVar::Resource(term.clone())
+}
+
+/// Builds a rule with the given hypothesis and conclusion triples.
+fn rule<const N: usize, const M: usize>(
+	variables: usize,
+	hypothesis: [(ResourceOrVar, ResourceOrVar, ResourceOrVar); N],
+	conclusion: [(ResourceOrVar, ResourceOrVar, ResourceOrVar); M],
+) -> Rule {
+	let patterns = hypothesis
+		.into_iter()
+		.map(|(s, p, o)| Signed(Sign::Positive, Triple(s, p, o)))
+		.collect();
+
+	let statements = conclusion
+		.into_iter()
+		.map(|(s, p, o)| {
+			let statement: TripleStatementPattern = TripleStatement::Triple(Triple(
+				Expression::Resource(s),
+				Expression::Resource(p),
+				Expression::Resource(o),
+			));
+			MaybeTrusted(Trust::Untrusted, Signed(Sign::Positive, statement))
+		})
+		.collect();
+
+	Rule::new(
+		variables,
+		Hypothesis::new(patterns),
+		Conclusion::new(0, statements),
+	)
+}
@@ -0,0 +1,104 @@
+use std::{
+	hash::Hash,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex, RwLock},
+};
+
+use rdf_types::Term;
+
+use super::{DatalogParseError, System, SystemDiff};
+
+/// Reload hook, called with the difference between the old and new system.
+type Hook<T> = Box<dyn Fn(&SystemDiff<T>) + Send + Sync>;
+
+/// Deduction system shared between threads, that can be replaced while in
+/// use.
+///
+/// Readers get a snapshot of the current system, that stays valid until it
+/// is dropped, even if the system is replaced in the meantime.
+pub struct SharedSystem<T = Term> {
+	current: RwLock<Arc<System<T>>>,
+	hooks: Mutex<Vec<Hook<T>>>,
+}
+
+impl<T> SharedSystem<T> {
+	pub fn new(system: System<T>) -> Self {
+		Self {
+			current: RwLock::new(Arc::new(system)),
+			hooks: Mutex::new(Vec::new()),
+		}
+	}
+
+	/// Returns a snapshot of the current system.
+	pub fn get(&self) -> Arc<System<T>> {
+		self.current.read().unwrap().clone()
+	}
+
+	/// Registers a hook called each time the system is replaced.
+	///
+	/// The hook receives the difference between the old and the new system.
+	/// Cached deductions of removed or modified rules are affected by the
+	/// change, as well as any cached closure if the change is not
+	/// [monotonic](super::Compatibility::monotonic).
+	pub fn on_reload(&self, hook: impl Fn(&SystemDiff<T>) + Send + Sync + 'static) {
+		self.hooks.lock().unwrap().push(Box::new(hook))
+	}
+}
+
+impl<T: Clone + Eq + Hash> SharedSystem<T> {
+	/// Atomically replaces the current system, and calls the reload hooks.
+	///
+	/// Returns the old system.
+	pub fn replace(&self, system: System<T>) -> Arc<System<T>> {
+		let new = Arc::new(system);
+		let old = std::mem::replace(&mut *self.current.write().unwrap(), new.clone());
+
+		let diff = old.diff(&new);
+		for hook in self.hooks.lock().unwrap().iter() {
+			hook(&diff)
+		}
+
+		old
+	}
+}
+
+impl<T> From<System<T>> for SharedSystem<T> {
+	fn from(value: System<T>) -> Self {
+		Self::new(value)
+	}
+}
+
+/// Reload error.
+#[derive(Debug, thiserror::Error)]
+pub enum ReloadError {
+	#[error("unable to read `{0}`: {1}")]
+	Io(PathBuf, std::io::Error),
+
+	#[error("invalid rules in `{0}`: {1}")]
+	Parse(PathBuf, DatalogParseError),
+}
+
+impl SharedSystem {
+	/// Reloads the system from the given Datalog programs (see
+	/// [`System::from_datalog`]).
+	///
+	/// The rules of every program are loaded in order. The current system is
+	/// only replaced if every program is successfully loaded.
+	pub fn reload_from_paths(
+		&self,
+		paths: impl IntoIterator<Item = impl AsRef<Path>>,
+	) -> Result<Arc<System>, ReloadError> {
+		let mut system = System::new();
+
+		for path in paths {
+			let path = path.as_ref();
+			let source =
+				std::fs::read_to_string(path).map_err(|e| ReloadError::Io(path.to_owned(), e))?;
+			let rules = System::from_datalog(&source)
+				.map_err(|e| ReloadError::Parse(path.to_owned(), e))?;
+			system.extend(rules)
+		}
+
+		Ok(self.replace(system))
+	}
+}
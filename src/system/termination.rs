@@ -0,0 +1,284 @@
+use std::{
+	collections::{BTreeSet, HashMap},
+	hash::Hash,
+};
+
+use educe::Educe;
+use rdf_types::Triple;
+
+use crate::{
+	expression::Expression, pattern::ResourceOrVar, MaybeTrusted, Sign, Signed, TripleStatement,
+};
+
+use super::System;
+
+/// Termination analysis result.
+///
+/// See [`System::check_termination`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Termination {
+	/// Rules whose existential variables may be instantiated infinitely many
+	/// times.
+	pub rules: BTreeSet<usize>,
+
+	/// Rule dependencies along the problematic cycles.
+	///
+	/// A dependency `(i, j)` means that the conclusion of rule `i` may feed
+	/// the hypothesis of rule `j`.
+	pub dependencies: BTreeSet<(usize, usize)>,
+}
+
+impl Termination {
+	/// Checks that the system is weakly acyclic, in which case the closure is
+	/// guaranteed to terminate.
+	pub fn is_weakly_acyclic(&self) -> bool {
+		self.rules.is_empty()
+	}
+}
+
+/// Triple position.
+///
+/// The predicate is `None` when it is a variable.
+#[derive(Educe)]
+#[educe(
+	Clone,
+	Copy,
+	PartialEq(bound = "T: PartialEq"),
+	Eq(bound = "T: Eq"),
+	Hash(bound = "T: Hash")
+)]
+struct Position<'a, T> {
+	sign: Sign,
+	predicate: Option<&'a T>,
+	slot: u8,
+}
+
+impl<T: PartialEq> Position<'_, T> {
+	/// Checks if a value at this position may flow into the `other` position.
+	fn flows_into(&self, other: &Self) -> bool {
+		self.sign == other.sign
+			&& self.slot == other.slot
+			&& match (self.predicate, other.predicate) {
+				(Some(a), Some(b)) => a == b,
+				_ => true,
+			}
+	}
+}
+
+struct Edge {
+	from: usize,
+	to: usize,
+	rule: usize,
+	special: bool,
+}
+
+#[derive(Educe)]
+#[educe(Default)]
+struct PositionGraph<'a, T> {
+	positions: Vec<Position<'a, T>>,
+	map: HashMap<Position<'a, T>, usize>,
+	edges: Vec<Edge>,
+	body: Vec<(usize, usize)>,
+	head: Vec<(usize, usize)>,
+}
+
+impl<'a, T: Eq + Hash> PositionGraph<'a, T> {
+	fn insert(&mut self, position: Position<'a, T>) -> usize {
+		*self.map.entry(position).or_insert_with(|| {
+			self.positions.push(position);
+			self.positions.len() - 1
+		})
+	}
+
+	/// Returns the successors of each position, including the flows from
+	/// conclusion positions to hypothesis positions.
+	fn successors(&self) -> Vec<Vec<usize>> {
+		let mut result = vec![Vec::new(); self.positions.len()];
+
+		for e in &self.edges {
+			result[e.from].push(e.to)
+		}
+
+		for &(_, h) in &self.head {
+			for &(_, b) in &self.body {
+				if h != b && self.positions[h].flows_into(&self.positions[b]) {
+					result[h].push(b)
+				}
+			}
+		}
+
+		result
+	}
+}
+
+fn reachable(successors: &[Vec<usize>], start: usize) -> Vec<bool> {
+	let mut result = vec![false; successors.len()];
+	let mut stack = vec![start];
+	result[start] = true;
+
+	while let Some(i) = stack.pop() {
+		for &j in &successors[i] {
+			if !result[j] {
+				result[j] = true;
+				stack.push(j)
+			}
+		}
+	}
+
+	result
+}
+
+impl<T: Eq + Hash> System<T> {
+	/// Checks the weak acyclicity of the system.
+	///
+	/// A system is weakly acyclic if no existential variable can be
+	/// instantiated from a value that was itself introduced by an
+	/// existential variable through a cycle of rules. In this case the
+	/// closure of any dataset is guaranteed to terminate. Otherwise, the
+	/// returned report lists the rules and rule dependencies forming the
+	/// problematic cycles, on which the closure may not terminate.
+	///
+	/// Positions are distinguished by sign, constant predicate and slot in
+	/// the triple. Expression results are not considered, and neither are
+	/// equality statements. The analysis is conservative.
+	pub fn check_termination(&self) -> Termination {
+		let mut graph = PositionGraph::default();
+
+		for (r, rule) in self.rules.iter().enumerate() {
+			let mut body: HashMap<usize, Vec<usize>> = HashMap::new();
+			for Signed(sign, Triple(s, p, o)) in &rule.hypothesis.patterns {
+				let predicate = match p {
+					ResourceOrVar::Resource(p) => Some(p),
+					ResourceOrVar::Var(_) => None,
+				};
+				for (slot, term) in [s, p, o].into_iter().enumerate() {
+					if let ResourceOrVar::Var(x) = term {
+						let i = graph.insert(Position {
+							sign: *sign,
+							predicate,
+							slot: slot as u8,
+						});
+						graph.body.push((r, i));
+						body.entry(*x).or_default().push(i)
+					}
+				}
+			}
+
+			for list in &rule.lists {
+				let first = &list.vocabulary.first;
+				let terms =
+					std::iter::once((&list.list, 0)).chain(list.items.iter().map(|t| (t, 2)));
+				for (term, slot) in terms {
+					if let ResourceOrVar::Var(x) = term {
+						let i = graph.insert(Position {
+							sign: Sign::Positive,
+							predicate: Some(first),
+							slot,
+						});
+						graph.body.push((r, i));
+						body.entry(*x).or_default().push(i)
+					}
+				}
+			}
+
+			let mut frontier = BTreeSet::new();
+			let mut head = Vec::new();
+			for MaybeTrusted(_, Signed(sign, statement)) in &rule.conclusion.statements {
+				if let TripleStatement::Triple(Triple(s, p, o)) = statement {
+					let predicate = match p {
+						Expression::Resource(ResourceOrVar::Resource(p)) => Some(p),
+						_ => None,
+					};
+
+					for (slot, term) in [s, p, o].into_iter().enumerate() {
+						if let Expression::Resource(ResourceOrVar::Var(x)) = term {
+							let i = graph.insert(Position {
+								sign: *sign,
+								predicate,
+								slot: slot as u8,
+							});
+							graph.head.push((r, i));
+							head.push((*x, i));
+							if *x < rule.variables {
+								frontier.insert(*x);
+							}
+						}
+					}
+				}
+			}
+
+			for (x, i) in head {
+				if x < rule.variables {
+					for &j in body.get(&x).into_iter().flatten() {
+						graph.edges.push(Edge {
+							from: j,
+							to: i,
+							rule: r,
+							special: false,
+						})
+					}
+				} else {
+					for y in &frontier {
+						for &j in body.get(y).into_iter().flatten() {
+							graph.edges.push(Edge {
+								from: j,
+								to: i,
+								rule: r,
+								special: true,
+							})
+						}
+					}
+				}
+			}
+		}
+
+		let successors = graph.successors();
+		let mut predecessors = vec![Vec::new(); successors.len()];
+		for (i, s) in successors.iter().enumerate() {
+			for &j in s {
+				predecessors[j].push(i)
+			}
+		}
+
+		let mut result = Termination::default();
+		let mut cycle = vec![false; successors.len()];
+		for e in graph.edges.iter().filter(|e| e.special) {
+			let forward = reachable(&successors, e.to);
+			if forward[e.from] {
+				result.rules.insert(e.rule);
+				let backward = reachable(&predecessors, e.from);
+				for (i, c) in cycle.iter_mut().enumerate() {
+					*c |= forward[i] && backward[i]
+				}
+			}
+		}
+
+		let mut rules = BTreeSet::new();
+		for e in &graph.edges {
+			if cycle[e.from] && cycle[e.to] {
+				rules.insert(e.rule);
+			}
+		}
+
+		for &(i, h) in &graph.head {
+			for &(j, b) in &graph.body {
+				if cycle[h]
+					&& cycle[b] && rules.contains(&i)
+					&& rules.contains(&j)
+					&& graph.positions[h].flows_into(&graph.positions[b])
+				{
+					result.dependencies.insert((i, j));
+				}
+			}
+		}
+
+		if !result.is_weakly_acyclic() {
+			log::warn!(
+				"deduction system is not weakly acyclic, closure may not terminate (rules {:?})",
+				result.rules
+			)
+		}
+
+		result
+	}
+}
@@ -0,0 +1,148 @@
+use std::{io, time::Duration};
+
+use rdf_types::Term;
+
+use crate::{
+	display::{DisplayContext, DisplayWithContext},
+	utils::write_json_string,
+};
+
+use super::DeductionInstance;
+
+/// Rule firing event, emitted by [`System::close_traced`](super::System::close_traced).
+#[derive(Clone, Copy)]
+pub struct Firing<'a, 'r> {
+	/// Deduction round in which the rule fired, starting from `1`.
+	pub iteration: usize,
+
+	/// Time elapsed since the start of the closure.
+	pub elapsed: Duration,
+
+	/// Deduction produced by the rule.
+	pub deduction: &'a DeductionInstance<'r, Term>,
+}
+
+impl Firing<'_, '_> {
+	/// Writes this event as a single-line JSON object.
+	///
+	/// The object has the following fields:
+	/// - `iteration`: deduction round;
+	/// - `elapsedMicros`: time elapsed since the start of the closure, in
+	///   microseconds;
+	/// - `rule`: rule identifier, or `null`;
+	/// - `bindings`: object mapping each bound variable to its value;
+	/// - `statements`: produced statements.
+	///
+	/// Terms are written using the given display context.
+	pub fn to_json(&self, context: &DisplayContext) -> String {
+		let entailment = &self.deduction.entailment;
+		let mut json = format!(
+			"{{\"iteration\":{},\"elapsedMicros\":{},\"rule\":",
+			self.iteration,
+			self.elapsed.as_micros()
+		);
+
+		match &entailment.rule.id {
+			Some(id) => write_json_string(&mut json, &id.with(context).to_string()),
+			None => json.push_str("null"),
+		}
+
+		json.push_str(",\"bindings\":{");
+		for (i, (x, value)) in entailment.bindings().enumerate() {
+			if i > 0 {
+				json.push(',')
+			}
+
+			write_json_string(&mut json, &x.to_string());
+			json.push(':');
+			write_json_string(&mut json, &value.with(context).to_string());
+		}
+
+		json.push_str("},\"statements\":[");
+		for (i, statement) in self.deduction.statements.iter().enumerate() {
+			if i > 0 {
+				json.push(',')
+			}
+
+			write_json_string(&mut json, &statement.with(context).to_string());
+		}
+
+		json.push_str("]}");
+		json
+	}
+}
+
+/// JSON-lines trace sink.
+///
+/// Writes each recorded [`Firing`] as a line of JSON (see
+/// [`Firing::to_json`]). Writing stops at the first I/O error, which is
+/// returned by [`Self::finish`].
+///
+/// ```
+/// # use inferdf::{rule, System, system::{ClosureLimits, JsonLinesTrace}};
+/// # use rdf_types::{dataset::IndexedBTreeDataset, grdf_triples};
+/// let mut system = System::new();
+/// system.insert(rule! {
+///   <"https://example.org/#ancestorRule"> for ?x, ?y {
+///     ?x <"https://example.org/#parent"> ?y .
+///   } => {
+///     ?x <"https://example.org/#ancestor"> ?y .
+///   }
+/// });
+///
+/// let mut dataset: IndexedBTreeDataset = grdf_triples![
+///   _:"a" <"https://example.org/#parent"> _:"b" .
+/// ]
+/// .into_iter()
+/// .map(|t| t.into_quad(None))
+/// .collect();
+///
+/// let mut trace = JsonLinesTrace::new(Vec::new());
+/// system
+///   .close_traced(&mut dataset, &ClosureLimits::default(), |f| trace.record(f))
+///   .unwrap();
+///
+/// let output = String::from_utf8(trace.finish().unwrap()).unwrap();
+/// assert_eq!(output.lines().count(), 1);
+/// assert!(output.contains("\"rule\":\"<https://example.org/#ancestorRule>\""));
+/// ```
+pub struct JsonLinesTrace<W> {
+	writer: W,
+	context: DisplayContext,
+	error: Option<io::Error>,
+}
+
+impl<W: io::Write> JsonLinesTrace<W> {
+	pub fn new(writer: W) -> Self {
+		Self {
+			writer,
+			context: DisplayContext::new(),
+			error: None,
+		}
+	}
+
+	/// Sets the display context used to write terms.
+	pub fn with_display_context(mut self, context: DisplayContext) -> Self {
+		self.context = context;
+		self
+	}
+
+	/// Writes the given event.
+	pub fn record(&mut self, firing: &Firing) {
+		if self.error.is_none() {
+			if let Err(e) = writeln!(self.writer, "{}", firing.to_json(&self.context)) {
+				self.error = Some(e)
+			}
+		}
+	}
+
+	/// Flushes the sink and returns the underlying writer.
+	pub fn finish(mut self) -> io::Result<W> {
+		if let Some(e) = self.error {
+			return Err(e);
+		}
+
+		self.writer.flush()?;
+		Ok(self.writer)
+	}
+}
@@ -0,0 +1,151 @@
+use std::{
+	fmt,
+	path::{Path, PathBuf},
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Mutex,
+	},
+	thread,
+};
+
+use crate::{System, ValidationReport};
+
+use super::{Error, Fixture};
+
+/// Batch of dataset files, validated against a single system.
+#[derive(Debug, Default, Clone)]
+pub struct Batch {
+	/// N-Quads files to validate.
+	pub paths: Vec<PathBuf>,
+}
+
+impl Batch {
+	pub fn new(paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+		Self {
+			paths: paths.into_iter().map(Into::into).collect(),
+		}
+	}
+
+	/// Lists the N-Quads (`.nq`) and N-Triples (`.nt`) files of the given
+	/// directory, in path order.
+	pub fn from_dir(path: impl AsRef<Path>) -> Result<Self, Error> {
+		let mut paths = Vec::new();
+		for entry in std::fs::read_dir(path)? {
+			let path = entry?.path();
+			if path.is_file() && path.extension().is_some_and(|e| e == "nq" || e == "nt") {
+				paths.push(path)
+			}
+		}
+
+		paths.sort_unstable();
+		Ok(Self { paths })
+	}
+
+	/// Validates every file against the given system, using up to `threads`
+	/// threads.
+	///
+	/// If `threads` is `0`, the available parallelism is used. Each file is
+	/// loaded and validated independently.
+	pub fn run(&self, system: &System, threads: usize) -> BatchReport {
+		let threads = match threads {
+			0 => thread::available_parallelism().map_or(1, usize::from),
+			n => n,
+		}
+		.min(self.paths.len());
+
+		let next = AtomicUsize::new(0);
+		let results = Mutex::new(Vec::with_capacity(self.paths.len()));
+
+		thread::scope(|scope| {
+			for _ in 0..threads {
+				let (next, results) = (&next, &results);
+				scope.spawn(move || loop {
+					let i = next.fetch_add(1, Ordering::Relaxed);
+					let Some(path) = self.paths.get(i) else {
+						break;
+					};
+
+					let result = Fixture::load(path)
+						.and_then(|fixture| fixture.validate(system).map_err(Error::Expression));
+
+					results.lock().unwrap().push((i, result))
+				});
+			}
+		});
+
+		let mut results = results.into_inner().unwrap();
+		results.sort_unstable_by_key(|(i, _)| *i);
+
+		BatchReport {
+			results: results
+				.into_iter()
+				.map(|(i, result)| (self.paths[i].clone(), result))
+				.collect(),
+		}
+	}
+}
+
+/// Aggregated validation report of a [`Batch`].
+#[derive(Debug, Default)]
+pub struct BatchReport {
+	/// Validation report of each file, in batch order.
+	pub results: Vec<(PathBuf, Result<ValidationReport, Error>)>,
+}
+
+impl BatchReport {
+	/// Returns the number of valid files.
+	pub fn valid(&self) -> usize {
+		self.results
+			.iter()
+			.filter(|(_, r)| r.as_ref().is_ok_and(ValidationReport::is_valid))
+			.count()
+	}
+
+	/// Returns the paths of the invalid files.
+	pub fn invalid(&self) -> impl Iterator<Item = &Path> {
+		self.results
+			.iter()
+			.filter(|(_, r)| r.as_ref().is_ok_and(ValidationReport::is_invalid))
+			.map(|(path, _)| path.as_path())
+	}
+
+	/// Returns the paths of the files that could not be validated, with the
+	/// error.
+	pub fn errors(&self) -> impl Iterator<Item = (&Path, &Error)> {
+		self.results
+			.iter()
+			.filter_map(|(path, r)| Some((path.as_path(), r.as_ref().err()?)))
+	}
+
+	/// Checks that every file is valid.
+	pub fn is_valid(&self) -> bool {
+		self.valid() == self.results.len()
+	}
+}
+
+impl fmt::Display for BatchReport {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (path, result) in &self.results {
+			let path = path.display();
+			match result {
+				Ok(report) if report.is_valid() => writeln!(f, "VALID {path}")?,
+				Ok(report) => writeln!(
+					f,
+					"INVALID {path}: {} error(s), {} warning(s)",
+					report.errors().count(),
+					report.warnings().count()
+				)?,
+				Err(e) => writeln!(f, "ERROR {path}: {e}")?,
+			}
+		}
+
+		write!(
+			f,
+			"{}/{} valid, {} invalid, {} error(s)",
+			self.valid(),
+			self.results.len(),
+			self.invalid().count(),
+			self.errors().count()
+		)
+	}
+}
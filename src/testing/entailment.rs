@@ -0,0 +1,301 @@
+use std::{
+	collections::{BTreeSet, HashMap},
+	fmt,
+	path::{Path, PathBuf},
+};
+
+use iref::Iri;
+use rdf_types::{dataset::IndexedBTreeDataset, Id, Term, Triple};
+use static_iref::iri;
+
+use crate::{
+	pattern::ResourceOrVar,
+	rule::{Conclusion, Hypothesis, Rule},
+	system::ClosureLimits,
+	Sign, Signed, System,
+};
+
+use super::{parse_nquads, parse_triples, Error};
+
+const RDF_TYPE: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+const RDF_FIRST: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#first");
+const RDF_REST: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#rest");
+const RDF_NIL: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil");
+const MF_ENTRIES: &Iri = iri!("http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#entries");
+const MF_NAME: &Iri = iri!("http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#name");
+const MF_ACTION: &Iri = iri!("http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#action");
+const MF_RESULT: &Iri = iri!("http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#result");
+const MF_ENTAILMENT_REGIME: &Iri =
+	iri!("http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#entailmentRegime");
+const MF_POSITIVE_ENTAILMENT_TEST: &Iri =
+	iri!("http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#PositiveEntailmentTest");
+const MF_NEGATIVE_ENTAILMENT_TEST: &Iri =
+	iri!("http://www.w3.org/2001/sw/DataAccess/tests/test-manifest#NegativeEntailmentTest");
+
+/// Entailment test kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntailmentTestKind {
+	/// The premise must entail the conclusion.
+	Positive,
+
+	/// The premise must not entail the conclusion.
+	Negative,
+}
+
+/// Entailment test.
+#[derive(Debug, Clone)]
+pub struct EntailmentTest {
+	/// Test name.
+	pub name: String,
+
+	/// Entailment regime tested, such as `RDFS`.
+	pub regime: Option<String>,
+
+	pub kind: EntailmentTestKind,
+
+	/// Premise dataset.
+	pub premise: IndexedBTreeDataset,
+
+	/// Conclusion graph.
+	///
+	/// Blank nodes are existentially quantified.
+	pub conclusion: BTreeSet<Triple>,
+}
+
+impl EntailmentTest {
+	/// Runs the test against the given system.
+	///
+	/// The closure of the premise is computed within the given limits, then
+	/// searched for an instance of the conclusion.
+	pub fn run(&self, system: &System, limits: &ClosureLimits) -> TestOutcome {
+		let mut closure = self.premise.clone();
+		if let Err(e) = system.close_bounded(&mut closure, limits, |_, _| ()) {
+			return TestOutcome::Error(e.to_string());
+		}
+
+		let expected = self.kind == EntailmentTestKind::Positive;
+		if entails(&closure, &self.conclusion) == expected {
+			TestOutcome::Passed
+		} else {
+			TestOutcome::Failed
+		}
+	}
+}
+
+/// Checks that the given dataset contains an instance of the given graph,
+/// where blank nodes are replaced by any resource.
+fn entails(dataset: &IndexedBTreeDataset, graph: &BTreeSet<Triple>) -> bool {
+	let mut variables = HashMap::new();
+	let mut pattern = |t: &Term| match t {
+		Term::Id(Id::Blank(b)) => {
+			let next = variables.len();
+			ResourceOrVar::Var(*variables.entry(b.clone()).or_insert(next))
+		}
+		t => ResourceOrVar::Resource(t.clone()),
+	};
+
+	let patterns = graph
+		.iter()
+		.map(|Triple(s, p, o)| Signed(Sign::Positive, Triple(pattern(s), pattern(p), pattern(o))))
+		.collect();
+
+	let rule = Rule::new(
+		variables.len(),
+		Hypothesis::new(patterns),
+		Conclusion::new(0, Vec::new()),
+	);
+
+	!rule.deduce(dataset).is_empty()
+}
+
+/// Entailment test outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestOutcome {
+	Passed,
+	Failed,
+
+	/// The closure of the premise failed.
+	Error(String),
+}
+
+impl TestOutcome {
+	pub fn is_passed(&self) -> bool {
+		matches!(self, Self::Passed)
+	}
+}
+
+/// Entailment test manifest.
+#[derive(Debug, Default, Clone)]
+pub struct Manifest {
+	/// Supported tests.
+	pub tests: Vec<EntailmentTest>,
+
+	/// Names of the entries that are not positive or negative entailment
+	/// tests, or whose result is not a graph (such as inconsistency tests).
+	pub skipped: Vec<String>,
+}
+
+impl Manifest {
+	/// Loads a W3C RDF test manifest, such as the ones of the RDF 1.1
+	/// Semantics or OWL 2 RL test suites.
+	///
+	/// The manifest, premises and conclusions must be N-Triples documents.
+	/// Turtle documents of the test suites must be converted beforehand.
+	/// Document IRIs starting with `base` are resolved against the directory
+	/// of the manifest.
+	pub fn load(path: impl AsRef<Path>, base: &Iri) -> Result<Self, Error> {
+		let path = path.as_ref();
+		let directory = path.parent().unwrap_or(Path::new(""));
+		let triples = parse_triples(&std::fs::read_to_string(path)?)?;
+		let graph = Graph(&triples);
+
+		let mut result = Self::default();
+		let mut entries = graph
+			.object(None, MF_ENTRIES)
+			.ok_or_else(|| invalid("no entries"))?;
+
+		while !is_iri(entries, RDF_NIL) {
+			let entry = graph
+				.object(Some(entries), RDF_FIRST)
+				.ok_or_else(|| invalid("invalid entry list"))?;
+			entries = graph
+				.object(Some(entries), RDF_REST)
+				.ok_or_else(|| invalid("invalid entry list"))?;
+
+			let name = match graph.object(Some(entry), MF_NAME) {
+				Some(Term::Literal(name)) => name.value.to_string(),
+				_ => entry.to_string(),
+			};
+
+			let kind = match graph.object(Some(entry), RDF_TYPE) {
+				Some(ty) if is_iri(ty, MF_POSITIVE_ENTAILMENT_TEST) => EntailmentTestKind::Positive,
+				Some(ty) if is_iri(ty, MF_NEGATIVE_ENTAILMENT_TEST) => EntailmentTestKind::Negative,
+				_ => {
+					result.skipped.push(name);
+					continue;
+				}
+			};
+
+			let conclusion = match graph.object(Some(entry), MF_RESULT) {
+				Some(Term::Id(Id::Iri(iri))) => resolve(directory, base, iri)?,
+				_ => {
+					result.skipped.push(name);
+					continue;
+				}
+			};
+
+			let premise = match graph.object(Some(entry), MF_ACTION) {
+				Some(Term::Id(Id::Iri(iri))) => resolve(directory, base, iri)?,
+				_ => return Err(invalid(format!("missing action for test `{name}`"))),
+			};
+
+			let regime = match graph.object(Some(entry), MF_ENTAILMENT_REGIME) {
+				Some(Term::Literal(regime)) => Some(regime.value.to_string()),
+				_ => None,
+			};
+
+			result.tests.push(EntailmentTest {
+				name,
+				regime,
+				kind,
+				premise: parse_nquads(&std::fs::read_to_string(premise)?)?,
+				conclusion: parse_triples(&std::fs::read_to_string(conclusion)?)?,
+			})
+		}
+
+		Ok(result)
+	}
+
+	/// Runs every test of the manifest against the given system.
+	pub fn run(&self, system: &System, limits: &ClosureLimits) -> ConformanceReport {
+		ConformanceReport {
+			results: self
+				.tests
+				.iter()
+				.map(|test| (test.name.clone(), test.run(system, limits)))
+				.collect(),
+			skipped: self.skipped.clone(),
+		}
+	}
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+	Error::Manifest(message.into())
+}
+
+fn is_iri(term: &Term, iri: &Iri) -> bool {
+	term.as_iri().is_some_and(|i| i == iri)
+}
+
+fn resolve(directory: &Path, base: &Iri, iri: &Iri) -> Result<PathBuf, Error> {
+	iri.as_str()
+		.strip_prefix(base.as_str())
+		.map(|suffix| directory.join(suffix))
+		.ok_or_else(|| invalid(format!("cannot resolve <{iri}>")))
+}
+
+/// Manifest graph.
+struct Graph<'a>(&'a BTreeSet<Triple>);
+
+impl<'a> Graph<'a> {
+	/// Returns the object of the first triple matching the given subject (if
+	/// any) and predicate.
+	fn object(&self, subject: Option<&Term>, predicate: &Iri) -> Option<&'a Term> {
+		self.0
+			.iter()
+			.find(|Triple(s, p, _)| {
+				subject.is_none_or(|subject| s == subject) && is_iri(p, predicate)
+			})
+			.map(|Triple(_, _, o)| o)
+	}
+}
+
+/// Conformance report.
+#[derive(Debug, Default, Clone)]
+pub struct ConformanceReport {
+	/// Outcome of each test, by test name.
+	pub results: Vec<(String, TestOutcome)>,
+
+	/// Names of the skipped manifest entries.
+	pub skipped: Vec<String>,
+}
+
+impl ConformanceReport {
+	/// Returns the number of passed tests.
+	pub fn passed(&self) -> usize {
+		self.results.iter().filter(|(_, o)| o.is_passed()).count()
+	}
+
+	/// Returns the names of the tests that did not pass.
+	pub fn failures(&self) -> impl Iterator<Item = &str> {
+		self.results
+			.iter()
+			.filter(|(_, o)| !o.is_passed())
+			.map(|(name, _)| name.as_str())
+	}
+
+	/// Checks that every test passed.
+	pub fn is_conformant(&self) -> bool {
+		self.failures().next().is_none()
+	}
+}
+
+impl fmt::Display for ConformanceReport {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (name, outcome) in &self.results {
+			match outcome {
+				TestOutcome::Passed => writeln!(f, "PASS {name}")?,
+				TestOutcome::Failed => writeln!(f, "FAIL {name}")?,
+				TestOutcome::Error(e) => writeln!(f, "FAIL {name}: {e}")?,
+			}
+		}
+
+		write!(
+			f,
+			"{}/{} passed, {} skipped",
+			self.passed(),
+			self.results.len(),
+			self.skipped.len()
+		)
+	}
+}
@@ -0,0 +1,448 @@
+//! Rule testing utilities.
+//!
+//! This module provides the [`Fixture`] type, holding a dataset loaded from
+//! N-Quads on which deduction systems can be run, with assertion methods
+//! reporting a readable diff on failure.
+//!
+//! It also provides a runner for the W3C entailment test suites: a
+//! [`Manifest`] of positive and negative entailment tests can be run against
+//! a deduction system to produce a [`ConformanceReport`].
+//!
+//! Many dataset files can be validated against the same system with a
+//! [`Batch`], producing a [`BatchReport`]. Command line tools report the
+//! outcome of a validation with an [`ExitCode`].
+//!
+//! ```
+//! use inferdf::{rule, testing::Fixture, System};
+//!
+//! let mut system = System::new();
+//! system.insert(rule! {
+//!   for ?person, ?country {
+//!     ?person <"https://example.org/#citizenOf"> ?country .
+//!   } => {
+//!     ?person <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+//!   }
+//! });
+//!
+//! let fixture = Fixture::parse(r#"
+//!   _:FrançoisDupont <https://example.org/#citizenOf> _:France .
+//! "#).unwrap();
+//!
+//! fixture.deduce(&system).unwrap().assert_eq(r#"
+//!   _:FrançoisDupont <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/#Human> .
+//! "#);
+//!
+//! fixture.assert_invalid(&system);
+//! ```
+use std::{
+	collections::{BTreeSet, HashMap},
+	fmt::Write,
+	path::Path,
+};
+
+use nquads_syntax::Parse;
+use rdf_types::{dataset::IndexedBTreeDataset, generator, Quad, Triple};
+
+use crate::{
+	display::{DisplayContext, DisplayWithContext},
+	expression,
+	pattern::ResourceOrVar,
+	Sign, Signed, Source, System, TripleStatement, ValidationReport, Violation,
+};
+
+mod batch;
+mod entailment;
+pub use batch::*;
+pub use entailment::*;
+
+pub use crate::GENERATED_BLANK_ID_PREFIX;
+
+/// Testing error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error(transparent)]
+	IO(#[from] std::io::Error),
+
+	#[error("invalid N-Quads: {0}")]
+	Parse(String),
+
+	#[error(transparent)]
+	Expression(#[from] expression::Error),
+
+	#[error("invalid manifest: {0}")]
+	Manifest(String),
+}
+
+/// Exit status of a validation, for command line tools and CI pipelines.
+///
+/// The numeric codes are stable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExitCode {
+	/// Every validated dataset is valid (code `0`).
+	Valid = 0,
+
+	/// A validated dataset is invalid (code `1`).
+	Invalid = 1,
+
+	/// A dataset could not be loaded or validated (code `2`).
+	Error = 2,
+}
+
+impl ExitCode {
+	/// Returns the numeric exit code.
+	pub fn code(self) -> i32 {
+		self as i32
+	}
+}
+
+impl<R> From<&ValidationReport<R>> for ExitCode {
+	fn from(value: &ValidationReport<R>) -> Self {
+		if value.is_valid() {
+			Self::Valid
+		} else {
+			Self::Invalid
+		}
+	}
+}
+
+impl From<&BatchReport> for ExitCode {
+	/// Errors take precedence over invalid files.
+	fn from(value: &BatchReport) -> Self {
+		if value.errors().next().is_some() {
+			Self::Error
+		} else if value.is_valid() {
+			Self::Valid
+		} else {
+			Self::Invalid
+		}
+	}
+}
+
+impl From<ExitCode> for std::process::ExitCode {
+	fn from(value: ExitCode) -> Self {
+		Self::from(value as u8)
+	}
+}
+
+/// Parses the given N-Quads document.
+pub fn parse_nquads(content: &str) -> Result<IndexedBTreeDataset, Error> {
+	Ok(parse_located_nquads(content)?
+		.into_iter()
+		.map(|(quad, _)| quad)
+		.collect())
+}
+
+/// Parses the given N-Quads document, returning each quad with its line
+/// number.
+fn parse_located_nquads(content: &str) -> Result<Vec<(Quad, u32)>, Error> {
+	let document = nquads_syntax::GrdfDocument::parse_str(content).map_err(|e| {
+		let span = e.metadata();
+		Error::Parse(format!("{} at {}..{}", e.value(), span.start(), span.end()))
+	})?;
+
+	Ok(document
+		.into_value()
+		.into_iter()
+		.map(|quad| {
+			let line = content[..quad.metadata().start()].matches('\n').count() + 1;
+			(nquads_syntax::strip_quad(quad.into_value()), line as u32)
+		})
+		.collect())
+}
+
+/// Parses the given N-Quads document as a set of triples, ignoring graphs.
+pub fn parse_triples(content: &str) -> Result<BTreeSet<Triple>, Error> {
+	Ok(parse_nquads(content)?
+		.into_iter()
+		.map(|quad| quad.into_triple().0)
+		.collect())
+}
+
+/// Test fixture.
+///
+/// Fixtures parsed from N-Quads documents remember the source location of
+/// each stated triple, reported when the validation of the fixture fails.
+#[derive(Debug, Clone)]
+pub struct Fixture {
+	dataset: IndexedBTreeDataset,
+	documents: Vec<String>,
+	sources: HashMap<Triple, Source>,
+	context: DisplayContext,
+}
+
+impl Fixture {
+	pub fn new(dataset: IndexedBTreeDataset) -> Self {
+		Self {
+			dataset,
+			documents: Vec::new(),
+			sources: HashMap::new(),
+			context: DisplayContext::new(),
+		}
+	}
+
+	/// Sets the context used to compact IRIs in failure reports.
+	pub fn with_display_context(mut self, context: DisplayContext) -> Self {
+		self.context = context;
+		self
+	}
+
+	/// Creates a fixture from the given N-Quads document.
+	pub fn parse(content: &str) -> Result<Self, Error> {
+		let mut result = Self::new(IndexedBTreeDataset::default());
+		result.add_document("<input>".to_owned(), content)?;
+		Ok(result)
+	}
+
+	/// Loads a fixture from the given N-Quads file.
+	pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+		Self::load_all([path])
+	}
+
+	/// Loads a fixture from the given N-Quads files.
+	///
+	/// Files are numbered in order, starting from `0`.
+	pub fn load_all(paths: impl IntoIterator<Item = impl AsRef<Path>>) -> Result<Self, Error> {
+		let mut result = Self::new(IndexedBTreeDataset::default());
+
+		for path in paths {
+			let path = path.as_ref();
+			result.add_document(path.display().to_string(), &std::fs::read_to_string(path)?)?;
+		}
+
+		Ok(result)
+	}
+
+	fn add_document(&mut self, name: String, content: &str) -> Result<(), Error> {
+		let document = self.documents.len() as u32;
+		self.documents.push(name);
+
+		for (quad, line) in parse_located_nquads(content)? {
+			self.sources
+				.entry(quad.clone().into_triple().0)
+				.or_insert(Source::new(document, line));
+			self.dataset.insert(quad);
+		}
+
+		Ok(())
+	}
+
+	pub fn dataset(&self) -> &IndexedBTreeDataset {
+		&self.dataset
+	}
+
+	/// Returns the name of the given source document.
+	pub fn document(&self, i: u32) -> Option<&str> {
+		self.documents.get(i as usize).map(String::as_str)
+	}
+
+	/// Returns the source location of the given stated triple, in any graph.
+	pub fn source_of(&self, triple: &Triple) -> Option<Source> {
+		self.sources.get(triple).copied()
+	}
+
+	/// Deduces and evaluates the facts derived from the fixture by the given
+	/// system.
+	///
+	/// Blank node identifiers generated for existential variables are
+	/// prefixed with [`GENERATED_BLANK_ID_PREFIX`].
+	pub fn deduce(&self, system: &System) -> Result<Deduced, Error> {
+		let deductions = system
+			.deduce(&self.dataset)
+			.eval(generator::Blank::new_with_prefix(
+				GENERATED_BLANK_ID_PREFIX.to_owned(),
+			))?;
+
+		let mut result = Deduced::default();
+		for deduction in deductions {
+			for Signed(sign, statement) in deduction.statements {
+				if let TripleStatement::Triple(triple) = statement {
+					match sign {
+						Sign::Positive => result.positive.insert(triple),
+						Sign::Negative => result.negative.insert(triple),
+					};
+				}
+			}
+		}
+
+		Ok(result)
+	}
+
+	/// Validates the fixture against the given system.
+	pub fn validate(&self, system: &System) -> Result<ValidationReport, expression::Error> {
+		system.report(&self.dataset)
+	}
+
+	/// Asserts that the fixture is valid according to the given system.
+	#[track_caller]
+	pub fn assert_valid(&self, system: &System) {
+		let report = self.validate(system).unwrap();
+		if report.is_invalid() {
+			panic!(
+				"expected a valid dataset:\n{}",
+				self.format_report(system, &report)
+			)
+		}
+	}
+
+	/// Asserts that the fixture is invalid according to the given system.
+	#[track_caller]
+	pub fn assert_invalid(&self, system: &System) {
+		let report = self.validate(system).unwrap();
+		if report.is_valid() {
+			panic!("expected an invalid dataset")
+		}
+	}
+
+	/// Returns the variable bindings of the violating deduction, and the
+	/// source locations of the facts matched by the hypothesis of the
+	/// violated rule.
+	fn matched_facts(&self, system: &System, violation: &Violation) -> (String, Vec<Source>) {
+		let Some(rule) = system.get(violation.rule) else {
+			return Default::default();
+		};
+
+		let bindings = violation
+			.bindings(rule)
+			.map(|(x, value)| format!("{x} = {}", value.with(&self.context)))
+			.collect::<Vec<_>>()
+			.join(", ");
+
+		let substitution = &violation.substitution;
+		let resolve = |t: &ResourceOrVar| match t {
+			ResourceOrVar::Resource(r) => Some(r.clone()),
+			ResourceOrVar::Var(x) => substitution.get(*x).cloned().flatten(),
+		};
+
+		let sources = rule
+			.hypothesis
+			.patterns
+			.iter()
+			.filter(|Signed(sign, _)| sign.is_positive())
+			.filter_map(|Signed(_, Triple(s, p, o))| {
+				self.source_of(&Triple(resolve(s)?, resolve(p)?, resolve(o)?))
+			})
+			.collect();
+
+		(bindings, sources)
+	}
+
+	fn format_report(&self, system: &System, report: &ValidationReport) -> String {
+		let mut result = String::new();
+
+		for v in &report.violations {
+			writeln!(
+				result,
+				"  rule {} ({:?}): {}",
+				v.rule,
+				v.severity,
+				v.reason.with(&self.context)
+			)
+			.unwrap();
+
+			let (bindings, sources) = self.matched_facts(system, v);
+			if !bindings.is_empty() {
+				writeln!(result, "    with {bindings}").unwrap();
+			}
+
+			let sources: Vec<_> = sources
+				.into_iter()
+				.map(|source| {
+					let document = self.document(source.document).unwrap_or("?");
+					format!("{document}:{}", source.line)
+				})
+				.collect();
+
+			if !sources.is_empty() {
+				writeln!(result, "    matched facts at {}", sources.join(", ")).unwrap();
+			}
+		}
+
+		result
+	}
+}
+
+/// Facts deduced from a fixture.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Deduced {
+	/// Positive deduced triples.
+	pub positive: BTreeSet<Triple>,
+
+	/// Negative deduced triples.
+	pub negative: BTreeSet<Triple>,
+}
+
+impl Deduced {
+	/// Asserts that the positive deduced triples are exactly the triples of
+	/// the given N-Quads document.
+	#[track_caller]
+	pub fn assert_eq(&self, expected: &str) {
+		let expected = parse_triples(expected).unwrap();
+		if self.positive != expected {
+			panic!(
+				"deduced triples mismatch (-expected +deduced):\n{}",
+				diff(&expected, &self.positive)
+			)
+		}
+	}
+
+	/// Asserts that every triple of the given N-Quads document is deduced.
+	#[track_caller]
+	pub fn assert_contains(&self, expected: &str) {
+		assert_included(&parse_triples(expected).unwrap(), &self.positive, "deduced")
+	}
+
+	/// Asserts that every triple of the given N-Quads document is deduced as
+	/// a negative fact.
+	#[track_caller]
+	pub fn assert_contains_negative(&self, expected: &str) {
+		assert_included(
+			&parse_triples(expected).unwrap(),
+			&self.negative,
+			"negatively deduced",
+		)
+	}
+
+	/// Asserts that no triple of the given N-Quads document is deduced.
+	#[track_caller]
+	pub fn assert_not_contains(&self, forbidden: &str) {
+		let forbidden = parse_triples(forbidden).unwrap();
+		let found: Vec<_> = forbidden.intersection(&self.positive).collect();
+		if !found.is_empty() {
+			let mut message = String::new();
+			for t in found {
+				writeln!(message, "  {t} .").unwrap();
+			}
+
+			panic!("forbidden triples are deduced:\n{message}")
+		}
+	}
+}
+
+#[track_caller]
+fn assert_included(expected: &BTreeSet<Triple>, found: &BTreeSet<Triple>, what: &str) {
+	let missing: Vec<_> = expected.difference(found).collect();
+	if !missing.is_empty() {
+		let mut message = String::new();
+		for t in missing {
+			writeln!(message, "  {t} .").unwrap();
+		}
+
+		panic!("expected triples are not {what}:\n{message}")
+	}
+}
+
+fn diff(expected: &BTreeSet<Triple>, found: &BTreeSet<Triple>) -> String {
+	let mut result = String::new();
+
+	for t in expected.union(found) {
+		let marker = match (expected.contains(t), found.contains(t)) {
+			(true, true) => ' ',
+			(true, false) => '-',
+			_ => '+',
+		};
+
+		writeln!(result, "{marker} {t} .").unwrap();
+	}
+
+	result
+}
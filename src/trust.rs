@@ -0,0 +1,153 @@
+use rdf_types::vocabulary::EmbedIntoVocabulary;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "paged")]
+use paged::Paged;
+
+use crate::{
+	expression::{self, Eval, Instantiate},
+	pattern::{
+		ApplyPartialSubstitution, ApplySubstitution, MapVariables, PatternSubstitution,
+		TryMapResources,
+	},
+};
+
+/// Value with a trust level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "paged", derive(Paged), paged(
+	context(C),
+	bounds(T: paged::EncodeSized),
+	encode_bounds(T: paged::Encode<C> + paged::EncodeOnHeap<C>),
+	decode_bounds(T: paged::Decode<C> + paged::DecodeFromHeap<C>)
+))]
+pub struct MaybeTrusted<T>(pub Trust, pub T);
+
+impl<T> MaybeTrusted<T> {
+	pub fn trusted(t: T) -> Self {
+		Self(Trust::Trusted, t)
+	}
+
+	pub fn untrusted(t: T) -> Self {
+		Self(Trust::Untrusted, t)
+	}
+
+	pub fn is_trusted(&self) -> bool {
+		self.0.is_trusted()
+	}
+
+	pub fn is_untrusted(&self) -> bool {
+		self.0.is_untrusted()
+	}
+
+	pub fn trust(&self) -> Trust {
+		self.0
+	}
+
+	pub fn value(&self) -> &T {
+		&self.1
+	}
+
+	pub fn value_mut(&mut self) -> &mut T {
+		&mut self.1
+	}
+
+	pub fn into_value(self) -> T {
+		self.1
+	}
+
+	pub fn map<U>(self, f: impl FnOnce(T) -> U) -> MaybeTrusted<U> {
+		MaybeTrusted(self.0, f(self.1))
+	}
+
+	pub fn as_ref(&self) -> MaybeTrusted<&T> {
+		MaybeTrusted(self.0, &self.1)
+	}
+}
+
+impl<V, T: EmbedIntoVocabulary<V>> EmbedIntoVocabulary<V> for MaybeTrusted<T> {
+	type Embedded = MaybeTrusted<T::Embedded>;
+
+	fn embed_into_vocabulary(self, vocabulary: &mut V) -> Self::Embedded {
+		MaybeTrusted(self.0, self.1.embed_into_vocabulary(vocabulary))
+	}
+}
+
+impl<T, U: ApplySubstitution<T>> ApplySubstitution<T> for MaybeTrusted<U> {
+	type Output = MaybeTrusted<U::Output>;
+
+	fn apply_substitution(&self, substitution: &PatternSubstitution<T>) -> Option<Self::Output> {
+		Some(MaybeTrusted(
+			self.0,
+			self.1.apply_substitution(substitution)?,
+		))
+	}
+}
+
+impl<T, U: ApplyPartialSubstitution<T>> ApplyPartialSubstitution<T> for MaybeTrusted<U> {
+	fn apply_partial_substitution(&self, substitution: &PatternSubstitution<T>) -> Self {
+		MaybeTrusted(self.0, self.1.apply_partial_substitution(substitution))
+	}
+}
+
+impl<'e, V, I, T: Eval<'e, V, I>> Eval<'e, V, I> for MaybeTrusted<T> {
+	type Output = MaybeTrusted<T::Output>;
+
+	fn eval(
+		&'e self,
+		vocabulary: &V,
+		interpretation: &I,
+	) -> Result<Self::Output, expression::Error> {
+		Ok(MaybeTrusted(
+			self.0,
+			self.1.eval(vocabulary, interpretation)?,
+		))
+	}
+}
+
+impl<V, I, T: Instantiate<V, I>> Instantiate<V, I> for MaybeTrusted<T> {
+	type Instantiated = MaybeTrusted<T::Instantiated>;
+
+	fn instantiate(self, vocabulary: &mut V, interpretation: &mut I) -> Self::Instantiated {
+		MaybeTrusted(self.0, self.1.instantiate(vocabulary, interpretation))
+	}
+}
+
+impl<T, U, A: TryMapResources<T, U>> TryMapResources<T, U> for MaybeTrusted<A> {
+	type Output = MaybeTrusted<A::Output>;
+
+	fn try_map_resources<E>(
+		self,
+		f: &mut impl FnMut(T) -> Result<U, E>,
+	) -> Result<Self::Output, E> {
+		Ok(MaybeTrusted(self.0, self.1.try_map_resources(f)?))
+	}
+}
+
+impl<A: MapVariables> MapVariables for MaybeTrusted<A> {
+	fn map_variables(&mut self, f: &mut impl FnMut(usize) -> usize) {
+		self.1.map_variables(f)
+	}
+}
+
+/// Trust level of a concluded statement.
+///
+/// Trusted statements (ending with `!` in the rule syntax) can be added to the
+/// dataset as is. Untrusted statements (ending with `.`) may be required to
+/// be checked against the dataset instead, according to the chosen
+/// [`UntrustedPolicy`](crate::system::UntrustedPolicy).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "paged", derive(Paged))]
+pub enum Trust {
+	Trusted,
+	Untrusted,
+}
+
+impl Trust {
+	pub fn is_trusted(&self) -> bool {
+		matches!(self, Self::Trusted)
+	}
+
+	pub fn is_untrusted(&self) -> bool {
+		matches!(self, Self::Untrusted)
+	}
+}
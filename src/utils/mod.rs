@@ -1,6 +1,27 @@
 //! Utility types and traits.
+use std::fmt::Write;
+
 mod search;
+mod union_find;
 pub use search::*;
+pub use union_find::*;
+
+/// Writes the given string as a JSON string literal.
+pub(crate) fn write_json_string(json: &mut String, value: &str) {
+	json.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => json.push_str("\\\""),
+			'\\' => json.push_str("\\\\"),
+			'\n' => json.push_str("\\n"),
+			'\r' => json.push_str("\\r"),
+			'\t' => json.push_str("\\t"),
+			c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+			c => json.push(c),
+		}
+	}
+	json.push('"');
+}
 
 pub struct InfallibleIterator<I>(pub I);
 
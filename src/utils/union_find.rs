@@ -0,0 +1,91 @@
+use std::hash::Hash;
+
+/// Persistent union-find structure.
+///
+/// Backed by immutable maps, so cloning a union-find is cheap (`O(1)`).
+/// Resources can be merged tentatively on a clone and reverted by simply
+/// dropping it, without affecting the original structure.
+///
+/// Uses union by rank (without path compression, which would require
+/// mutating the structure on lookup), so `find` is in `O(log n)`.
+#[derive(Debug, Clone)]
+pub struct UnionFind<T: Clone + Eq + Hash> {
+	/// Parent of each merged, non-representative element.
+	parents: im::HashMap<T, T>,
+
+	/// Rank of each representative element, when not zero.
+	ranks: im::HashMap<T, u32>,
+}
+
+impl<T: Clone + Eq + Hash> Default for UnionFind<T> {
+	fn default() -> Self {
+		Self {
+			parents: im::HashMap::new(),
+			ranks: im::HashMap::new(),
+		}
+	}
+}
+
+impl<T: Clone + Eq + Hash> UnionFind<T> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Checks that no element has been merged.
+	pub fn is_empty(&self) -> bool {
+		self.parents.is_empty()
+	}
+
+	/// Returns the representative of the given element.
+	pub fn find<'a>(&'a self, t: &'a T) -> &'a T {
+		let mut current = t;
+
+		while let Some(parent) = self.parents.get(current) {
+			current = parent
+		}
+
+		current
+	}
+
+	/// Checks if the given two elements are merged.
+	pub fn are_merged(&self, a: &T, b: &T) -> bool {
+		self.find(a) == self.find(b)
+	}
+
+	/// Merges the classes of the given two elements, and returns the new
+	/// representative.
+	///
+	/// When both classes have the same rank, the representative of `a` is
+	/// kept.
+	pub fn merge(&mut self, a: &T, b: &T) -> T {
+		let a = self.find(a).clone();
+		let b = self.find(b).clone();
+
+		if a == b {
+			return a;
+		}
+
+		let a_rank = self.rank(&a);
+		let b_rank = self.rank(&b);
+
+		let (repr, other) = if a_rank >= b_rank { (a, b) } else { (b, a) };
+
+		if a_rank == b_rank {
+			self.ranks.insert(repr.clone(), a_rank + 1);
+		}
+
+		self.ranks.remove(&other);
+		self.parents.insert(other, repr.clone());
+		repr
+	}
+
+	fn rank(&self, t: &T) -> u32 {
+		self.ranks.get(t).copied().unwrap_or(0)
+	}
+
+	/// Returns an iterator over the merged elements (excluding
+	/// representatives) with their representative.
+	pub fn iter(&self) -> impl '_ + Iterator<Item = (&T, &T)> {
+		self.parents.keys().map(|t| (t, self.find(t)))
+	}
+}
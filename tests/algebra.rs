@@ -0,0 +1,103 @@
+use inferdf::{rule, System};
+
+#[test]
+fn rule_equivalence() {
+	let a = rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => for ?z {
+			?z <"https://example.org/#child"> ?x .
+		}
+	};
+
+	let b = rule! {
+		for ?y, ?x {
+			?x <"https://example.org/#parent"> ?y .
+		} => for ?z {
+			?z <"https://example.org/#child"> ?x .
+		}
+	};
+
+	assert_ne!(a, b);
+	assert!(a.is_equivalent(&b));
+	assert_eq!(a.canonicalize(), b.canonicalize());
+}
+
+fn parent_rule() -> inferdf::Rule {
+	rule! {
+		<"https://example.org/#parent"> for ?y, ?x {
+			?x <"https://example.org/#child"> ?y .
+		} => {
+			?y <"https://example.org/#parent"> ?x .
+		}
+	}
+}
+
+fn ancestor_rule() -> inferdf::Rule {
+	rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?x <"https://example.org/#ancestor"> ?y .
+		}
+	}
+}
+
+#[test]
+fn system_algebra() {
+	let a: System = [parent_rule(), ancestor_rule()].into_iter().collect();
+	let b: System = [parent_rule()].into_iter().collect();
+
+	let union = a.union(&b).unwrap();
+	assert_eq!(union.len(), 2);
+
+	let intersection = a.intersection(&b);
+	assert_eq!(intersection.len(), 1);
+	assert!(intersection.contains_equivalent(&parent_rule()));
+
+	let difference = a.difference(&b);
+	assert_eq!(difference.len(), 1);
+	assert!(difference.contains_equivalent(&ancestor_rule()));
+}
+
+#[test]
+fn union_conflict() {
+	let a: System = [parent_rule()].into_iter().collect();
+	let b: System = [rule! {
+		<"https://example.org/#parent"> for ?x, ?y {
+			?x <"https://example.org/#mother"> ?y .
+		} => {
+			?y <"https://example.org/#parent"> ?x .
+		}
+	}]
+	.into_iter()
+	.collect();
+
+	assert!(a.union(&b).is_err())
+}
+
+#[test]
+fn renamed_variables() {
+	let a = ancestor_rule();
+	let b = rule! {
+		for ?person, ?parent {
+			?person <"https://example.org/#parent"> ?parent .
+		} => {
+			?person <"https://example.org/#ancestor"> ?parent .
+		}
+	};
+
+	assert_eq!(a, b);
+	assert!(a.is_equivalent(&b));
+
+	let mut system = System::new();
+	system.insert(a.clone());
+	system.insert(b.clone());
+	assert_eq!(system.len(), 1);
+
+	let left: System = [a].into_iter().collect();
+	let right: System = [b].into_iter().collect();
+	assert_eq!(left.union(&right).unwrap().len(), 1);
+	assert_eq!(left.intersection(&right).len(), 1);
+	assert!(left.difference(&right).is_empty())
+}
@@ -0,0 +1,574 @@
+use std::time::Duration;
+
+use inferdf::{
+	display::DisplayContext,
+	rule,
+	same_as::Identities,
+	system::{
+		CancellationToken, ClosureError, ClosureLimits, GraphPolicy, Interruption, JsonLinesTrace,
+	},
+	System,
+};
+use rdf_types::{
+	dataset::{IndexedBTreeDataset, TraversableDataset},
+	grdf_triples, BlankIdBuf, Quad, Term, Triple,
+};
+use static_iref::iri;
+
+fn system() -> System {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?x <"https://example.org/#ancestor"> ?y .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#ancestor"> ?y .
+			?y <"https://example.org/#parent"> ?z .
+		} => {
+			?x <"https://example.org/#ancestor"> ?z .
+		}
+	});
+
+	system
+}
+
+fn dataset(triples: impl IntoIterator<Item = Triple>) -> IndexedBTreeDataset {
+	triples.into_iter().map(|t| t.into_quad(None)).collect()
+}
+
+#[test]
+fn close() {
+	let mut input = dataset(grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+	]);
+
+	let count = system().close(&mut input).unwrap();
+	assert_eq!(count, 3);
+
+	let expected = dataset(grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+		_:"a" <"https://example.org/#ancestor"> _:"b" .
+		_:"b" <"https://example.org/#ancestor"> _:"c" .
+		_:"a" <"https://example.org/#ancestor"> _:"c" .
+	]);
+
+	assert_eq!(input, expected)
+}
+
+#[test]
+fn close_observed() {
+	let mut input = dataset(grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+	]);
+
+	let system = system();
+	let transitive = system.get(1).unwrap();
+	let mut facts = Vec::new();
+	system
+		.close_observed(&mut input, |triple, cause| {
+			facts.push((triple.clone(), std::ptr::eq(cause.rule, transitive)))
+		})
+		.unwrap();
+
+	let expected: Triple = grdf_triples![
+		_:"a" <"https://example.org/#ancestor"> _:"c" .
+	]
+	.into_iter()
+	.next()
+	.unwrap();
+
+	assert_eq!(facts.len(), 3);
+	assert!(facts.contains(&(expected, true)))
+}
+
+/// Every human has a human parent.
+fn infinite_system() -> System {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		} => for ?p {
+			?x <"https://example.org/#parent"> ?p .
+			?p <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		}
+	});
+
+	system
+}
+
+fn human() -> IndexedBTreeDataset {
+	dataset(grdf_triples![
+		_:"a" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+	])
+}
+
+#[test]
+fn close_max_facts() {
+	let mut input = human();
+	let limits = ClosureLimits {
+		max_facts: Some(10),
+		..Default::default()
+	};
+
+	match infinite_system().close_bounded(&mut input, &limits, |_, _| ()) {
+		Err(ClosureError::Interrupted(e)) => {
+			assert_eq!(e.reason, Interruption::MaxFacts);
+			assert_eq!(e.inserted, 10);
+			assert_eq!(input.len(), 11)
+		}
+		other => panic!("unexpected result: {other:?}"),
+	}
+}
+
+#[test]
+fn close_max_iterations() {
+	let mut input = human();
+	let limits = ClosureLimits {
+		max_iterations: Some(3),
+		..Default::default()
+	};
+
+	match infinite_system().close_bounded(&mut input, &limits, |_, _| ()) {
+		Err(ClosureError::Interrupted(e)) => {
+			assert_eq!(e.reason, Interruption::MaxIterations);
+			assert_eq!(e.inserted, 6)
+		}
+		other => panic!("unexpected result: {other:?}"),
+	}
+}
+
+#[test]
+fn close_max_new_resources() {
+	let mut input = human();
+	let limits = ClosureLimits {
+		max_new_resources: Some(4),
+		..Default::default()
+	};
+
+	match infinite_system().close_bounded(&mut input, &limits, |_, _| ()) {
+		Err(ClosureError::Interrupted(e)) => {
+			assert_eq!(e.reason, Interruption::MaxNewResources);
+			assert_eq!(e.inserted, 8)
+		}
+		other => panic!("unexpected result: {other:?}"),
+	}
+}
+
+#[test]
+fn close_cancelled() {
+	let mut input = human();
+	let token = CancellationToken::new();
+	let limits = ClosureLimits {
+		cancellation: Some(token.clone()),
+		..Default::default()
+	};
+
+	let mut count = 0;
+	let result = infinite_system().close_bounded(&mut input, &limits, |_, _| {
+		count += 1;
+		if count == 4 {
+			token.cancel()
+		}
+	});
+
+	match result {
+		Err(ClosureError::Interrupted(e)) => {
+			assert_eq!(e.reason, Interruption::Cancelled);
+			assert_eq!(e.inserted, 4)
+		}
+		other => panic!("unexpected result: {other:?}"),
+	}
+}
+
+#[test]
+fn close_cancelled_before_start() {
+	let mut input = human();
+	let token = CancellationToken::new();
+	token.cancel();
+	let limits = ClosureLimits {
+		cancellation: Some(token),
+		..Default::default()
+	};
+
+	match infinite_system().close_bounded(&mut input, &limits, |_, _| ()) {
+		Err(ClosureError::Interrupted(e)) => {
+			assert_eq!(e.reason, Interruption::Cancelled);
+			assert_eq!(e.inserted, 0);
+			assert_eq!(input.len(), 1)
+		}
+		other => panic!("unexpected result: {other:?}"),
+	}
+}
+
+#[test]
+fn close_timeout_before_start() {
+	let mut input = human();
+	let limits = ClosureLimits {
+		timeout: Some(Duration::ZERO),
+		..Default::default()
+	};
+
+	match infinite_system().close_bounded(&mut input, &limits, |_, _| ()) {
+		Err(ClosureError::Interrupted(e)) => {
+			assert_eq!(e.reason, Interruption::Timeout);
+			assert_eq!(e.inserted, 0)
+		}
+		other => panic!("unexpected result: {other:?}"),
+	}
+}
+
+#[test]
+fn close_with_identities() {
+	let mut system = System::new();
+
+	// Email addresses are inverse functional.
+	system.insert(rule! {
+		for ?x, ?y, ?e {
+			?x <"https://example.org/#email"> ?e .
+			?y <"https://example.org/#email"> ?e .
+		} => {
+			?x = ?y .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#name"> ?y .
+			?x <"https://example.org/#knows"> <"https://example.org/#carol"> .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#CarolFriend"> .
+		}
+	});
+
+	let mut input = dataset(grdf_triples![
+		_:"a" <"https://example.org/#email"> "alice@example.org" .
+		_:"a" <"https://example.org/#name"> "Alice" .
+		_:"b" <"https://example.org/#email"> "alice@example.org" .
+		_:"b" <"https://example.org/#knows"> <"https://example.org/#carol"> .
+	]);
+
+	let mut identities = Identities::new();
+	let count = system
+		.close_with_identities(&mut input, &mut identities, &ClosureLimits::default())
+		.unwrap();
+
+	// The merged `knows` or `name` triple, and the `CarolFriend` type.
+	assert_eq!(count, 2);
+
+	let [a, b]: [Term; 2] =
+		["a", "b"].map(|b| Term::blank(BlankIdBuf::new(format!("_:{b}")).unwrap()));
+	assert!(identities.are_same(&a, &b));
+
+	let alice = identities.representative(&a).clone();
+	let expected = dataset(
+		grdf_triples![
+			_:"a" <"https://example.org/#email"> "alice@example.org" .
+			_:"a" <"https://example.org/#name"> "Alice" .
+			_:"a" <"https://example.org/#knows"> <"https://example.org/#carol"> .
+			_:"a" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#CarolFriend"> .
+		]
+		.into_iter()
+		.map(|Triple(s, p, o)| Triple(if s == a { alice.clone() } else { s }, p, o)),
+	);
+
+	assert_eq!(input, expected)
+}
+
+#[test]
+fn close_with_contradictory_identities() {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x, ?y, ?e {
+			?x <"https://example.org/#email"> ?e .
+			?y <"https://example.org/#email"> ?e .
+		} => {
+			?x = ?y .
+		}
+	});
+
+	let mut input = dataset(grdf_triples![
+		_:"a" <"https://example.org/#email"> "alice@example.org" .
+		_:"b" <"https://example.org/#email"> "alice@example.org" .
+	]);
+
+	let mut identities = Identities::new();
+	let [a, b]: [Term; 2] =
+		["a", "b"].map(|b| Term::blank(BlankIdBuf::new(format!("_:{b}")).unwrap()));
+	identities.separate(&a, &b).unwrap();
+
+	assert!(matches!(
+		system.close_with_identities(&mut input, &mut identities, &ClosureLimits::default()),
+		Err(ClosureError::Contradiction(_))
+	))
+}
+
+#[test]
+fn functional_property_contradiction() {
+	let mut system = System::new();
+
+	// `mother` is a functional property.
+	system.insert(rule! {
+		for ?x, ?a, ?b {
+			?x <"https://example.org/#mother"> ?a .
+			?x <"https://example.org/#mother"> ?b .
+		} => {
+			?a = ?b .
+		}
+	});
+
+	// Siblings are different persons.
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#sibling"> ?y .
+		} => {
+			?x != ?y .
+		}
+	});
+
+	let mut input = dataset(grdf_triples![
+		_:"a" <"https://example.org/#mother"> _:"m1" .
+		_:"a" <"https://example.org/#mother"> _:"m2" .
+		_:"m1" <"https://example.org/#sibling"> _:"m2" .
+	]);
+
+	assert!(matches!(
+		system.close_with_identities(
+			&mut input,
+			&mut Identities::new(),
+			&ClosureLimits::default()
+		),
+		Err(ClosureError::Contradiction(_))
+	))
+}
+
+#[test]
+fn close_graphs() {
+	let g = Term::iri(iri!("https://example.org/#g").to_owned());
+	let input: IndexedBTreeDataset = [
+		Quad(
+			Term::blank(BlankIdBuf::from_suffix("a").unwrap()),
+			Term::iri(iri!("https://example.org/#parent").to_owned()),
+			Term::blank(BlankIdBuf::from_suffix("b").unwrap()),
+			None,
+		),
+		Quad(
+			Term::blank(BlankIdBuf::from_suffix("b").unwrap()),
+			Term::iri(iri!("https://example.org/#parent").to_owned()),
+			Term::blank(BlankIdBuf::from_suffix("c").unwrap()),
+			Some(g.clone()),
+		),
+	]
+	.into_iter()
+	.collect();
+
+	let count = |policy, graph: Option<&Term>| {
+		let mut dataset = input.clone();
+		system()
+			.close_graphs(&mut dataset, policy, &ClosureLimits::default())
+			.unwrap();
+		dataset.quads().filter(|q| q.3 == graph).count()
+	};
+
+	// `_:a ancestor _:b`, `_:b ancestor _:c` and `_:a ancestor _:c`.
+	assert_eq!(count(GraphPolicy::Union, None), 4);
+	assert_eq!(count(GraphPolicy::Union, Some(&g)), 1);
+
+	assert_eq!(count(GraphPolicy::PerGraph, None), 2);
+	assert_eq!(count(GraphPolicy::PerGraph, Some(&g)), 2);
+
+	assert_eq!(count(GraphPolicy::DefaultGraph, None), 2);
+	assert_eq!(count(GraphPolicy::DefaultGraph, Some(&g)), 1);
+}
+
+#[test]
+fn close_paraconsistent() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x {
+			?x <"https://example.org/#type"> <"https://example.org/#Bird"> .
+		} => {
+			?x <"https://example.org/#canFly"> "true" .
+		}
+	});
+	system.insert(rule! {
+		for ?x {
+			?x <"https://example.org/#type"> <"https://example.org/#Penguin"> .
+		} => {
+			! ?x <"https://example.org/#canFly"> "true" .
+		}
+	});
+	system.insert(rule! {
+		for ?x {
+			?x <"https://example.org/#canFly"> "true" .
+		} => {
+			?x <"https://example.org/#type"> <"https://example.org/#Flyer"> .
+		}
+	});
+
+	let mut dataset = dataset(grdf_triples![
+		_:"tweety" <"https://example.org/#type"> <"https://example.org/#Bird"> .
+		_:"pingu" <"https://example.org/#type"> <"https://example.org/#Bird"> .
+		_:"pingu" <"https://example.org/#type"> <"https://example.org/#Penguin"> .
+	]);
+
+	let result = system
+		.close_paraconsistent(&mut dataset, &ClosureLimits::default())
+		.unwrap();
+
+	assert!(!result.is_consistent());
+	assert_eq!(result.disputes.len(), 1);
+
+	let dispute = &result.disputes[0];
+	assert_eq!(dispute.triple.0.to_string(), "_:pingu");
+	assert!(!dispute.stated);
+	assert_eq!(dispute.positive.len(), 1);
+	assert_eq!(dispute.negative.len(), 1);
+
+	// Only tweety can fly and is a flyer.
+	assert_eq!(result.inserted, 2);
+	assert!(!dataset.contains(dispute.triple.as_ref().into_quad(None)));
+}
+
+#[test]
+fn close_with_ontology() {
+	let mut input = dataset(grdf_triples![
+		<"https://example.org/#Student"> <"http://www.w3.org/2000/01/rdf-schema#subClassOf"> <"https://example.org/#Person"> .
+		<"https://example.org/#ancestor"> <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"http://www.w3.org/2002/07/owl#TransitiveProperty"> .
+		<"https://example.org/#ancestor"> <"http://www.w3.org/2002/07/owl#inverseOf"> <"https://example.org/#descendant"> .
+		<"https://example.org/#ancestor"> <"http://www.w3.org/2000/01/rdf-schema#domain"> <"https://example.org/#Person"> .
+		_:"a" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Student"> .
+		_:"a" <"https://example.org/#ancestor"> _:"b" .
+		_:"b" <"https://example.org/#ancestor"> _:"c" .
+	]);
+
+	assert_eq!(System::from_ontology(&input).len(), 5);
+
+	let count = System::new().close_with_ontology(&mut input).unwrap();
+	assert_eq!(count, 6);
+
+	for triple in grdf_triples![
+		_:"a" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Person"> .
+		_:"b" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Person"> .
+		_:"a" <"https://example.org/#ancestor"> _:"c" .
+		_:"b" <"https://example.org/#descendant"> _:"a" .
+		_:"c" <"https://example.org/#descendant"> _:"b" .
+		_:"c" <"https://example.org/#descendant"> _:"a" .
+	] {
+		assert!(input.contains(triple.as_ref().into_quad(None)))
+	}
+}
+
+#[test]
+fn close_traced() {
+	let mut input = dataset(grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+	]);
+
+	let mut trace = JsonLinesTrace::new(Vec::new()).with_display_context(
+		DisplayContext::new()
+			.with_prefix("ex".to_owned(), iri!("https://example.org/#").to_owned()),
+	);
+	let count = system()
+		.close_traced(&mut input, &ClosureLimits::default(), |f| trace.record(f))
+		.unwrap();
+	assert_eq!(count, 3);
+
+	let output = String::from_utf8(trace.finish().unwrap()).unwrap();
+	let lines: Vec<_> = output.lines().collect();
+	assert_eq!(lines.len(), 3);
+	assert!(lines[0].starts_with("{\"iteration\":1,\"elapsedMicros\":"));
+	assert!(lines.iter().any(|l| l.starts_with("{\"iteration\":2,")
+		&& l.contains("\"bindings\":{\"?x\":\"_:a\",\"?y\":\"_:b\",\"?z\":\"_:c\"}")
+		&& l.ends_with("\"statements\":[\"_:a ex:ancestor _:c\"]}")));
+}
+
+#[test]
+fn close_twice() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Person"> .
+		} => for ?m {
+			?x <"https://example.org/#mother"> ?m .
+		}
+	});
+
+	let mut input = dataset(grdf_triples![
+		_:"a" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Person"> .
+	]);
+	assert_eq!(system.close(&mut input).unwrap(), 1);
+
+	input.insert(
+		Triple(
+			Term::blank(BlankIdBuf::from_suffix("b").unwrap()),
+			Term::iri(iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#type").to_owned()),
+			Term::iri(iri!("https://example.org/#Person").to_owned()),
+		)
+		.into_quad(None),
+	);
+	system.close(&mut input).unwrap();
+
+	let mothers: Vec<_> = input
+		.iter()
+		.filter(|Quad(_, p, _, _)| {
+			p.as_iri().map(|i| i.as_iri()) == Some(iri!("https://example.org/#mother"))
+		})
+		.collect();
+	assert!(mothers
+		.iter()
+		.any(|Quad(s, _, _, _)| s.as_blank().unwrap().as_str() == "_:b"));
+	for Quad(s, _, o, _) in &mothers {
+		assert!(mothers.iter().all(|Quad(t, _, m, _)| m != o || t == s))
+	}
+}
+
+#[test]
+fn close_with_priorities() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => {
+			?x <"https://example.org/#votesIn"> ?y .
+		}
+	});
+	system.insert(
+		rule! {
+			for ?x, ?y {
+				?x <"https://example.org/#residentOf"> ?y .
+			} => {
+				! ?x <"https://example.org/#votesIn"> ?y .
+			}
+		}
+		.with_priority(1),
+	);
+
+	let input = dataset(grdf_triples![
+		_:"a" <"https://example.org/#citizenOf"> _:"b" .
+		_:"a" <"https://example.org/#residentOf"> _:"b" .
+	]);
+
+	// The negative conclusion overrides the positive one.
+	let mut closed = input.clone();
+	assert_eq!(system.close(&mut closed).unwrap(), 0);
+	assert_eq!(closed, input);
+
+	let mut closed = input.clone();
+	let result = system
+		.close_paraconsistent(&mut closed, &ClosureLimits::default())
+		.unwrap();
+	assert!(result.is_consistent());
+	assert_eq!(result.inserted, 0)
+}
@@ -0,0 +1,135 @@
+use inferdf::{
+	display::{DisplayContext, DisplayWithContext},
+	rule,
+	system::DatalogParseError,
+	Reason, System,
+};
+use rdf_types::Term;
+
+#[test]
+fn round_trip() {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+			! ?x <"https://example.org/#exiled"> ?y .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+			?y <"https://example.org/#name"> "France" ^^ "http://www.w3.org/2001/XMLSchema#string" .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#spouse"> ?y .
+			?x <"https://example.org/#spouse"> ?z .
+		} => {
+			?y = ?z .
+			?x != ?y .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		} => for ?p {
+			?x <"https://example.org/#parent"> ?p .
+		}
+	});
+
+	let datalog = system.to_datalog().unwrap();
+	assert_eq!(datalog.lines().count(), 3);
+
+	let parsed = System::from_datalog(&datalog).unwrap();
+	assert_eq!(parsed.len(), system.len());
+	for (a, b) in system.iter().zip(&parsed) {
+		assert!(a.is_equivalent(b))
+	}
+
+	assert_eq!(parsed.to_datalog().unwrap(), datalog)
+}
+
+#[test]
+fn parse() {
+	let system = System::from_datalog(
+		r#"
+		// Facts.
+		triple(<https://example.org/#France>, <https://example.org/#name>, "France"@fr).
+
+		triple(Person, <http://www.w3.org/1999/02/22-rdf-syntax-ns#type>, <https://example.org/#Human>)
+			:- triple(Person, <https://example.org/#citizenOf>, _:country).
+		"#,
+	)
+	.unwrap();
+
+	assert_eq!(system.len(), 2);
+	assert!(system.get(0).unwrap().hypothesis.is_empty());
+	assert_eq!(system.get(1).unwrap().variables, 1);
+
+	assert!(System::from_datalog("edge(X, Y) :- triple(X, <p>, Y).").is_err());
+	assert!(System::from_datalog("triple(X, <p>, Y) :- X = Y.").is_err());
+	assert!(System::from_datalog("triple(X, <p>, Y)").is_err())
+}
+
+#[test]
+fn unsupported() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x {
+			?x <"https://example.org/#p"> ?x .
+		} => {
+			?x <"https://example.org/#q"> ?x !
+		}
+	});
+
+	assert!(system.to_datalog().is_err())
+}
+
+#[test]
+fn prefixes() {
+	let mut context = DisplayContext::new();
+	let system = System::from_datalog_with_context(
+		r#"
+		@prefix ex: <https://example.org/#> .
+		@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+		@base <https://example.org/people/> .
+
+		triple(<alice>, ex:age, "42"^^xsd:integer).
+		triple(X, ex:type, ex:Human) :- triple(X, ex:citizenOf, Y).
+		"#,
+		&mut context,
+	)
+	.unwrap();
+
+	let expected = System::from_datalog(
+		r#"
+		triple(<https://example.org/people/alice>, <https://example.org/#age>, "42"^^<http://www.w3.org/2001/XMLSchema#integer>).
+		triple(X, <https://example.org/#type>, <https://example.org/#Human>) :- triple(X, <https://example.org/#citizenOf>, Y).
+		"#,
+	)
+	.unwrap();
+
+	for (a, b) in system.iter().zip(&expected) {
+		assert!(a.is_equivalent(b))
+	}
+
+	assert_eq!(
+		context.prefix("ex").unwrap().as_str(),
+		"https://example.org/#"
+	);
+
+	let reason = Reason::NotEq(
+		Term::iri("https://example.org/people/alice".parse().unwrap()),
+		Term::iri("https://example.org/#bob".parse().unwrap()),
+	);
+	assert_eq!(
+		reason.with(&context).to_string(),
+		"<alice> is not equal to ex:bob"
+	);
+
+	assert!(matches!(
+		System::from_datalog("triple(X, ex:p, Y) :- triple(X, ex:q, Y)."),
+		Err(DatalogParseError::UnknownPrefix(_, _))
+	))
+}
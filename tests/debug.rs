@@ -0,0 +1,169 @@
+use inferdf::{debug, rule, Sign, Signed, System};
+use rdf_types::{grdf_triples, Triple};
+
+#[test]
+fn minimize() {
+	let items: Vec<u32> = (0..32).collect();
+	let result = debug::minimize(items, |items| items.contains(&7) && items.contains(&21));
+	assert_eq!(result, Some(vec![7, 21]))
+}
+
+#[test]
+fn minimize_deduction() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#parent"> ?y .
+			?y <"https://example.org/#parent"> ?z .
+		} => {
+			?x <"https://example.org/#grandParent"> ?z .
+		}
+	});
+
+	let dataset: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"a" <"https://example.org/#name"> "Alice" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+		_:"b" <"https://example.org/#name"> "Bob" .
+		_:"c" <"https://example.org/#parent"> _:"d" .
+	]
+	.into_iter()
+	.collect();
+
+	let target: Triple = grdf_triples![
+		_:"a" <"https://example.org/#grandParent"> _:"c" .
+	]
+	.into_iter()
+	.next()
+	.unwrap();
+
+	let minimal = debug::minimize_deduction(&system, dataset, &Signed(Sign::Positive, target))
+		.unwrap()
+		.unwrap();
+
+	let expected: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+	]
+	.into_iter()
+	.collect();
+
+	assert_eq!(minimal, expected)
+}
+
+#[test]
+fn minimize_transitive_deduction() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?x <"https://example.org/#ancestor"> ?y .
+		}
+	});
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#ancestor"> ?y .
+			?y <"https://example.org/#parent"> ?z .
+		} => {
+			?x <"https://example.org/#ancestor"> ?z .
+		}
+	});
+
+	let dataset: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"a" <"https://example.org/#name"> "Alice" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+		_:"c" <"https://example.org/#parent"> _:"d" .
+		_:"d" <"https://example.org/#parent"> _:"e" .
+	]
+	.into_iter()
+	.collect();
+
+	// Deduced after three rounds.
+	let target: Triple = grdf_triples![
+		_:"a" <"https://example.org/#ancestor"> _:"d" .
+	]
+	.into_iter()
+	.next()
+	.unwrap();
+
+	let minimal = debug::minimize_deduction(&system, dataset, &Signed(Sign::Positive, target))
+		.unwrap()
+		.unwrap();
+
+	let expected: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+		_:"c" <"https://example.org/#parent"> _:"d" .
+	]
+	.into_iter()
+	.collect();
+
+	assert_eq!(minimal, expected)
+}
+
+#[test]
+fn deduction_support() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?x <"https://example.org/#ancestor"> ?y .
+		}
+	});
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#ancestor"> ?y .
+			?y <"https://example.org/#parent"> ?z .
+		} => {
+			?x <"https://example.org/#ancestor"> ?z .
+		}
+	});
+
+	let dataset: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"x" <"https://example.org/#parent"> _:"y" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+		_:"c" <"https://example.org/#name"> "Carol" .
+		_:"c" <"https://example.org/#parent"> _:"d" .
+		_:"d" <"https://example.org/#parent"> _:"e" .
+	]
+	.into_iter()
+	.collect();
+
+	let target: Triple = grdf_triples![
+		_:"a" <"https://example.org/#ancestor"> _:"d" .
+	]
+	.into_iter()
+	.next()
+	.unwrap();
+
+	let support = debug::deduction_support(&system, &dataset, &Signed(Sign::Positive, target))
+		.unwrap()
+		.unwrap();
+
+	let expected: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+		_:"c" <"https://example.org/#parent"> _:"d" .
+	]
+	.into_iter()
+	.collect();
+
+	assert_eq!(support, expected);
+
+	let unrelated: Triple = grdf_triples![
+		_:"a" <"https://example.org/#ancestor"> _:"x" .
+	]
+	.into_iter()
+	.next()
+	.unwrap();
+
+	assert!(
+		debug::deduction_support(&system, &dataset, &Signed(Sign::Positive, unrelated))
+			.unwrap()
+			.is_none()
+	)
+}
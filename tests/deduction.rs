@@ -0,0 +1,653 @@
+use inferdf::{dataset::cache::CachedDataset, Bipolar, SignedDatasetMut};
+use inferdf::{
+	rule,
+	system::{ExistentialPolicy, Existentials},
+	Rule, Sign, Signed, System, TripleStatement,
+};
+use rdf_types::{
+	dataset::{IndexedBTreeDataset, IndexedBTreeGraph},
+	generator, grdf_triples, Term, Triple,
+};
+use static_iref::iri;
+
+#[test]
+fn untrusted_policy() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#citizenOf"> _:"1" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> !
+			?y <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Country"> .
+		}
+	};
+
+	let accepted = rule.deduce(&dataset).eval(generator::Blank::new()).unwrap();
+	let deduction = accepted.into_iter().next().unwrap();
+	assert_eq!(deduction.statements.len(), 2);
+	assert!(deduction.checks.is_empty());
+
+	let checked = rule
+		.deduce(&dataset)
+		.eval_checked(generator::Blank::new())
+		.unwrap();
+	let deduction = checked.into_iter().next().unwrap();
+	assert_eq!(deduction.statements.len(), 1);
+	assert_eq!(deduction.checks.len(), 1);
+	assert!(matches!(
+		&deduction.checks[0],
+		Signed(Sign::Positive, TripleStatement::Triple(t)) if t.0.is_blank()
+	));
+}
+
+#[test]
+fn deduce_named_rule() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"FrançoisDupont" <"https://example.org/#citizenOf"> _:"France" .
+	]
+	.into_iter()
+	.collect();
+
+	let mut system = System::new();
+
+	system.insert(rule! {
+		<"https://example.org/#human"> for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		}
+	});
+
+	system.insert(rule! {
+		<"https://example.org/#country"> for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => {
+			?y <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Country"> .
+		}
+	});
+
+	let id = Term::iri(iri!("https://example.org/#country").to_owned());
+	let deductions = system
+		.deduce_rule(&id, &dataset)
+		.unwrap()
+		.eval(generator::Blank::new())
+		.unwrap();
+
+	let deduction = deductions.into_iter().next().unwrap();
+	assert_eq!(deduction.entailment.rule.id.as_ref(), Some(&id));
+
+	let unknown = Term::iri(iri!("https://example.org/#unknown").to_owned());
+	assert!(system.deduce_rule(&unknown, &dataset).is_none());
+}
+
+#[test]
+fn statement_accessors() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#citizenOf"> _:"1" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+			! ?y <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+			! ?x = ?y .
+			(= ?x ?x) .
+		}
+	};
+
+	let deductions = rule.deduce(&dataset).eval(generator::Blank::new()).unwrap();
+	let deduction = deductions.iter().next().unwrap();
+
+	assert_eq!(deduction.positive_triples().count(), 1);
+	assert_eq!(deduction.negative_triples().count(), 1);
+	assert_eq!(deduction.equalities().count(), 0);
+	assert_eq!(deduction.inequalities().count(), 1);
+	assert!(deduction.truths().all(|t| t.is_positive()));
+	assert_eq!(deductions.into_positive_triples().count(), 1);
+}
+
+#[test]
+fn caused_statements() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#citizenOf"> _:"1" .
+		_:"0" <"https://example.org/#residentOf"> _:"1" .
+	]
+	.into_iter()
+	.collect();
+
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#residentOf"> ?y .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+			?y <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Country"> .
+		}
+	});
+
+	let statements = system
+		.deduce(&dataset)
+		.eval(generator::Blank::new())
+		.unwrap()
+		.into_caused_statements();
+
+	assert_eq!(statements.len(), 2);
+	assert_eq!(statements[0].causes.len(), 2);
+	assert_eq!(statements[1].causes.len(), 1);
+}
+
+#[test]
+fn resolve_conflicts() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#citizenOf"> _:"1" .
+		_:"0" <"https://example.org/#residentOf"> _:"1" .
+	]
+	.into_iter()
+	.collect();
+
+	let citizen_rule = rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => {
+			?x <"https://example.org/#votesIn"> ?y .
+		}
+	};
+
+	let resident_rule = rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#residentOf"> ?y .
+		} => {
+			! ?x <"https://example.org/#votesIn"> ?y .
+		}
+	};
+
+	// The citizenship rule wins.
+	let mut system = System::new();
+	system.insert(citizen_rule.clone().with_priority(1));
+	system.insert(resident_rule.clone());
+
+	let mut deductions = system
+		.deduce(&dataset)
+		.eval(generator::Blank::new())
+		.unwrap();
+	assert!(deductions.resolve_conflicts().is_empty());
+	assert_eq!(deductions.positive_triples().count(), 1);
+	assert_eq!(deductions.negative_triples().count(), 0);
+
+	// Equal priorities: the contradiction is reported.
+	let mut system = System::new();
+	system.insert(citizen_rule);
+	system.insert(resident_rule);
+
+	let mut deductions = system
+		.deduce(&dataset)
+		.eval(generator::Blank::new())
+		.unwrap();
+	let conflicts = deductions.resolve_conflicts();
+	assert_eq!(conflicts.len(), 1);
+	assert_eq!(conflicts[0].positive.len(), 1);
+	assert_eq!(conflicts[0].negative.len(), 1);
+	assert_eq!(deductions.positive_triples().count(), 1);
+	assert_eq!(deductions.negative_triples().count(), 1);
+}
+
+#[test]
+fn universal_condition() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"a" <"https://example.org/#member"> _:"alice" .
+		_:"a" <"https://example.org/#member"> _:"bob" .
+		_:"b" <"https://example.org/#member"> _:"alice" .
+		_:"b" <"https://example.org/#member"> _:"carol" .
+		_:"alice" <"https://example.org/#certified"> "true" .
+		_:"bob" <"https://example.org/#certified"> "true" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?t, ?m {
+			?t <"https://example.org/#member"> ?m .
+		} forall ?x {
+			?t <"https://example.org/#member"> ?x .
+		} => {
+			?x <"https://example.org/#certified"> "true" .
+		} => {
+			?t <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#CertifiedTeam"> .
+		}
+	};
+
+	let deductions = rule.deduce(&dataset).eval(generator::Blank::new()).unwrap();
+	let teams: Vec<_> = deductions
+		.into_iter()
+		.map(|d| d.entailment.substitution[0].clone().unwrap())
+		.collect();
+
+	assert_eq!(teams.len(), 2);
+	assert!(teams.iter().all(|t| t.to_string() == "_:a"));
+}
+
+#[test]
+fn existential_policies() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"alice" <"https://example.org/#type"> <"https://example.org/#Person"> .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x {
+			?x <"https://example.org/#type"> <"https://example.org/#Person"> .
+		} => for ?p {
+			?x <"https://example.org/#parent"> ?p .
+		}
+	};
+
+	fn parent<'r>(
+		rule: &'r Rule,
+		dataset: &IndexedBTreeGraph,
+		existentials: &mut Existentials<'r>,
+		prefix: &str,
+	) -> Term {
+		let deductions = rule
+			.deduce(dataset)
+			.eval_existentials(
+				generator::Blank::new_with_prefix(prefix.to_owned()),
+				existentials,
+			)
+			.unwrap();
+		deductions.into_positive_triples().next().unwrap().2
+	}
+
+	let mut fresh = Existentials::new(ExistentialPolicy::Fresh);
+	assert_ne!(
+		parent(&rule, &dataset, &mut fresh, "a"),
+		parent(&rule, &dataset, &mut fresh, "b")
+	);
+
+	let mut reuse = Existentials::new(ExistentialPolicy::Reuse);
+	let p = parent(&rule, &dataset, &mut reuse, "a");
+	assert!(p.is_blank());
+	assert_eq!(parent(&rule, &dataset, &mut reuse, "b"), p);
+
+	let prefix = iri!("https://example.org/.well-known/genid/").to_owned();
+	let mut skolem = Existentials::new(ExistentialPolicy::Skolem(prefix.clone()));
+	let p = parent(&rule, &dataset, &mut skolem, "a");
+	assert!(p.as_iri().unwrap().as_str().starts_with(prefix.as_str()));
+	assert_eq!(
+		parent(
+			&rule,
+			&dataset,
+			&mut Existentials::new(ExistentialPolicy::Skolem(prefix)),
+			"b"
+		),
+		p
+	);
+}
+
+#[test]
+fn lazy_deductions() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"a" <"https://example.org/#knows"> _:"b" .
+		_:"b" <"https://example.org/#knows"> _:"c" .
+		_:"c" <"https://example.org/#knows"> _:"a" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#knows"> ?y .
+		} => {
+			?y <"https://example.org/#knows"> ?x .
+		}
+	};
+
+	assert_eq!(rule.deductions(&dataset).count(), 3);
+	assert_eq!(rule.deductions(&dataset).take(1).count(), 1);
+
+	let first = rule.try_deductions(&dataset).next().unwrap().unwrap();
+	assert_eq!(first.statements.len(), 1);
+}
+
+#[test]
+fn deduplicate_deductions() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"a" <"https://example.org/#sameGroup"> _:"a" .
+	]
+	.into_iter()
+	.collect();
+
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#sameGroup"> ?y .
+			?y <"https://example.org/#sameGroup"> ?z .
+		} => {
+			?x <"https://example.org/#sameGroup"> ?z .
+		}
+	});
+
+	let triple = dataset.iter().next().unwrap();
+	let mut deductions = system.deduce_from_triple(&dataset, Signed(Sign::Positive, triple));
+	assert_eq!(deductions.len(), 2);
+
+	deductions.dedup(&system);
+	let deductions = deductions.eval(generator::Blank::new()).unwrap();
+	assert_eq!(deductions.iter().count(), 1);
+}
+
+#[test]
+fn arithmetic_conclusion() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#width"> "4"^^"http://www.w3.org/2001/XMLSchema#integer" .
+		_:"0" <"https://example.org/#height"> "2.5"^^"http://www.w3.org/2001/XMLSchema#double" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x, ?w, ?h {
+			?x <"https://example.org/#width"> ?w .
+			?x <"https://example.org/#height"> ?h .
+		} => {
+			?x <"https://example.org/#area"> (* ?w ?h) .
+			?x <"https://example.org/#perimeter"> (* 2 (+ ?w ?w)) .
+		}
+	};
+
+	let deductions = rule.deduce(&dataset).eval(generator::Blank::new()).unwrap();
+	let objects: Vec<_> = deductions
+		.into_positive_triples()
+		.map(|t| t.2.as_literal().unwrap().clone())
+		.collect();
+
+	assert_eq!(objects[0].value, "1.0E1");
+	assert_eq!(
+		objects[0].type_,
+		rdf_types::LiteralType::Any(xsd_types::XSD_DOUBLE.to_owned())
+	);
+	assert_eq!(objects[1].value, "16");
+}
+
+#[test]
+fn lang_string_conclusion() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#name"> "Paris" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x, ?name {
+			?x <"https://example.org/#name"> ?name .
+		} => {
+			?x <"http://www.w3.org/2000/01/rdf-schema#label"> (strLang ?name "fr") .
+		}
+	};
+
+	let deductions = rule.deduce(&dataset).eval(generator::Blank::new()).unwrap();
+	let triple = deductions.into_positive_triples().next().unwrap();
+	assert_eq!(triple.2.to_string(), "\"Paris\"@fr");
+}
+
+#[test]
+fn iri_conclusion() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#id"> "42" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x, ?id {
+			?x <"https://example.org/#id"> ?id .
+		} => {
+			?x <"http://www.w3.org/2002/07/owl#sameAs"> (iri (concat "https://example.org/person/" ?id)) .
+		}
+	};
+
+	let deductions = rule.deduce(&dataset).eval(generator::Blank::new()).unwrap();
+	let triple = deductions.into_positive_triples().next().unwrap();
+	assert_eq!(
+		triple.2,
+		<Term>::iri(iri!("https://example.org/person/42").to_owned())
+	);
+}
+
+#[test]
+fn content_addressed_conclusion() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#id"> "42" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x, ?id {
+			?x <"https://example.org/#id"> ?id .
+		} => {
+			?x <"http://www.w3.org/2002/07/owl#sameAs"> (uuid ?id) .
+			?x <"https://example.org/#digest"> (sha256 ?id) .
+		}
+	};
+
+	let deductions = rule.deduce(&dataset).eval(generator::Blank::new()).unwrap();
+	let mut objects: Vec<_> = deductions
+		.into_positive_triples()
+		.map(|triple| triple.2)
+		.collect();
+	objects.sort();
+	assert_eq!(
+		objects,
+		[
+			<Term>::iri(iri!("urn:uuid:73475cb4-0a56-8e8d-a8a0-45ced110137e").to_owned()),
+			Term::Literal(rdf_types::Literal::new(
+				"73475cb40a568e8da8a045ced110137e159f890ac4da883b6b17dc651b3a8049".to_owned(),
+				rdf_types::LiteralType::Any(rdf_types::XSD_STRING.to_owned())
+			)),
+		]
+	);
+}
+
+#[test]
+fn list_patterns() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"x" <"https://example.org/#pair"> _:"l0" .
+		_:"l0" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#first"> _:"a" .
+		_:"l0" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#rest"> _:"l1" .
+		_:"l1" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#first"> _:"b" .
+		_:"l1" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#rest"> <"http://www.w3.org/1999/02/22-rdf-syntax-ns#nil"> .
+	]
+	.into_iter()
+	.collect();
+
+	let pair = rule! {
+		for ?x, ?l, ?a, ?b {
+			?x <"https://example.org/#pair"> ?l .
+			?l ( ?a ?b ) .
+		} => {
+			?a <"https://example.org/#pairedWith"> ?b .
+		}
+	};
+
+	let triples: Vec<_> = pair
+		.deduce(&dataset)
+		.eval(generator::Blank::new())
+		.unwrap()
+		.into_positive_triples()
+		.map(|t| t.to_string())
+		.collect();
+	assert_eq!(triples, ["_:a <https://example.org/#pairedWith> _:b"]);
+
+	let triple = rule! {
+		for ?x, ?l, ?a, ?b, ?c {
+			?x <"https://example.org/#pair"> ?l .
+			?l ( ?a ?b ?c ) .
+		} => {
+			?a <"https://example.org/#pairedWith"> ?b .
+		}
+	};
+	assert!(triple.deduce(&dataset).is_empty());
+
+	let member = rule! {
+		for ?x, ?l, ?m {
+			?x <"https://example.org/#pair"> ?l .
+			?l ( ?m ... ) .
+		} => {
+			?m <"https://example.org/#memberOf"> ?x .
+		}
+	};
+
+	let mut triples: Vec<_> = member
+		.deduce(&dataset)
+		.eval(generator::Blank::new())
+		.unwrap()
+		.into_positive_triples()
+		.map(|t| t.to_string())
+		.collect();
+	triples.sort();
+	assert_eq!(
+		triples,
+		[
+			"_:a <https://example.org/#memberOf> _:x",
+			"_:b <https://example.org/#memberOf> _:x"
+		]
+	);
+}
+
+#[test]
+fn negative_hypothesis_from_triple() {
+	let mut dataset: Bipolar<IndexedBTreeDataset> = Bipolar::default();
+	let [person, dead, cat]: [Triple; 3] = grdf_triples![
+		_:"a" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Person"> .
+		_:"a" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Dead"> .
+		_:"a" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Cat"> .
+	];
+
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Person"> .
+			! ?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Dead"> .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Alive"> .
+		}
+	});
+
+	let deduce = |dataset: &Bipolar<IndexedBTreeDataset>, fact: Signed<Triple<&Term>>| {
+		system
+			.deduce_from_triple(dataset, fact)
+			.eval(generator::Blank::new())
+			.unwrap()
+			.into_positive_triples()
+			.count()
+	};
+
+	dataset.insert(Signed(Sign::Positive, person.clone().into_quad(None)));
+	assert_eq!(deduce(&dataset, Signed(Sign::Positive, person.as_ref())), 0);
+
+	// The negative fact arrives, triggering the negative hypothesis.
+	dataset.insert(Signed(Sign::Negative, dead.clone().into_quad(None)));
+	assert_eq!(deduce(&dataset, Signed(Sign::Negative, dead.as_ref())), 1);
+
+	// The positive fact now matches the rest of the hypothesis.
+	assert_eq!(deduce(&dataset, Signed(Sign::Positive, person.as_ref())), 1);
+
+	// Facts of the wrong sign, or not matching any pattern, trigger nothing.
+	assert_eq!(deduce(&dataset, Signed(Sign::Positive, dead.as_ref())), 0);
+	dataset.insert(Signed(Sign::Negative, cat.clone().into_quad(None)));
+	assert_eq!(deduce(&dataset, Signed(Sign::Negative, cat.as_ref())), 0);
+}
+
+#[test]
+fn cached_pattern_matching() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"FrançoisDupont" <"https://example.org/#citizenOf"> _:"France" .
+		_:"JohnSmith" <"https://example.org/#citizenOf"> _:"England" .
+	]
+	.into_iter()
+	.collect();
+
+	let human = rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		}
+	};
+
+	let country = rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => {
+			?y <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Country"> .
+		}
+	};
+
+	let cached = CachedDataset::new(&dataset);
+	for rule in [&human, &country] {
+		let expected: Vec<_> = rule
+			.deduce(&dataset)
+			.eval(generator::Blank::new())
+			.unwrap()
+			.into_positive_triples()
+			.collect();
+		let deductions: Vec<_> = rule
+			.try_deduce(&cached)
+			.unwrap()
+			.eval(generator::Blank::new())
+			.unwrap()
+			.into_positive_triples()
+			.collect();
+		assert_eq!(deductions.len(), 2);
+		assert_eq!(deductions, expected)
+	}
+
+	assert_eq!(cached.misses(), 1);
+	assert_eq!(cached.hits(), 1);
+}
+
+#[test]
+fn blake3_conclusion() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#id"> "42" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x, ?id {
+			?x <"https://example.org/#id"> ?id .
+		} => {
+			?x <"https://example.org/#digest"> (blake3 ?id) .
+		}
+	};
+
+	let deductions = rule.deduce(&dataset).eval(generator::Blank::new()).unwrap();
+	let triple = deductions.into_positive_triples().next().unwrap();
+	assert_eq!(
+		triple.2,
+		<Term>::Literal(rdf_types::Literal::new(
+			"da136474d7575c325f702bb7aa75f1123864033cc488bf7d9c074eadaf9bd0d3".to_owned(),
+			rdf_types::LiteralType::Any(rdf_types::XSD_STRING.to_owned())
+		))
+	);
+}
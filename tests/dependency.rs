@@ -0,0 +1,131 @@
+use std::convert::Infallible;
+
+use inferdf::{pattern::TryMapResources, rule, System};
+use rdf_types::Term;
+use static_iref::iri;
+
+fn system() -> System {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		<"https://example.org/#parentIsAncestor">
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?x <"https://example.org/#ancestor"> ?y .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#ancestor"> ?y .
+			?y <"https://example.org/#parent"> ?z .
+		} => {
+			?x <"https://example.org/#ancestor"> ?z .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		}
+	});
+
+	system
+}
+
+#[test]
+fn dependency_graph() {
+	let system = system();
+	let graph = system.dependency_graph();
+
+	assert_eq!(graph.dependencies, [(0, 1), (1, 1)].into_iter().collect());
+	assert_eq!(graph.dependents_of(0).collect::<Vec<_>>(), [1]);
+	assert_eq!(graph.dependencies_of(1).collect::<Vec<_>>(), [0, 1]);
+	assert_eq!(graph.dependencies_of(2).count(), 0);
+}
+
+#[test]
+fn dot() {
+	let system = system();
+	assert_eq!(
+		system.dependency_graph().to_dot(),
+		"digraph {\n\
+		\tr0 [label=\"https://example.org/#parentIsAncestor\"];\n\
+		\tr1 [label=\"#1\"];\n\
+		\tr2 [label=\"#2\"];\n\
+		\tr0 -> r1;\n\
+		\tr1 -> r1;\n\
+		}\n"
+	)
+}
+
+#[test]
+fn slice_for_predicates() {
+	let system = system();
+	let ancestor = Term::iri(iri!("https://example.org/#ancestor").to_owned());
+	let ty = Term::iri(iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#type").to_owned());
+
+	let slice = system.slice_for_predicates(&[ancestor]);
+	assert_eq!(slice.len(), 2);
+	assert!(slice.iter().all(|r| system.iter().take(2).any(|s| s == r)));
+
+	let slice = system.slice_for_predicates(&[ty]);
+	assert_eq!(slice.len(), 1);
+	assert_eq!(slice.get(0), system.get(2));
+
+	assert!(system.slice_for_predicates(&[]).is_empty());
+}
+
+#[test]
+fn dot_escape() {
+	let mut rule = rule! {
+		for ?x {
+			?x <"https://example.org/#parent"> ?x .
+		} => {}
+	};
+	rule.id = Some(Term::Literal(rdf_types::Literal::new(
+		"first \"rule\"\nsecond line".to_owned(),
+		rdf_types::LiteralType::Any(rdf_types::XSD_STRING.to_owned()),
+	)));
+
+	// Rule identifiers are displayed as raw strings.
+	let mut system = System::new();
+	system.insert(rule);
+	let system = system
+		.try_map_resources(&mut |t| {
+			Ok::<_, Infallible>(match t {
+				Term::Literal(l) => l.value,
+				t => t.to_string(),
+			})
+		})
+		.unwrap();
+
+	assert_eq!(
+		system.dependency_graph().to_dot(),
+		"digraph {\n\
+		\tr0 [label=\"first \\\"rule\\\"\\nsecond line\"];\n\
+		}\n"
+	)
+}
+
+#[test]
+fn dependency_graph_variable_predicates() {
+	let mut system = system();
+
+	// Concludes triples with any predicate.
+	system.insert(rule! {
+		for ?x, ?p, ?q, ?y {
+			?p <"https://example.org/#inverseOf"> ?q .
+			?y ?q ?x .
+		} => {
+			?x ?p ?y .
+		}
+	});
+
+	let graph = system.dependency_graph();
+	assert_eq!(graph.dependencies_of(3).collect::<Vec<_>>(), [0, 1, 2, 3]);
+	assert_eq!(graph.dependents_of(3).collect::<Vec<_>>(), [0, 1, 2, 3]);
+}
@@ -0,0 +1,76 @@
+use inferdf::{rule, System};
+
+fn base() -> System {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		<"https://example.org/#ancestor"> for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?x <"https://example.org/#ancestor"> ?y .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#child"> ?y .
+		} => {
+			?y <"https://example.org/#parent"> ?x .
+		}
+	});
+
+	system
+}
+
+#[test]
+fn diff_identical() {
+	let (old, new) = (base(), base());
+	let diff = old.diff(&new);
+	assert!(diff.is_empty());
+	assert!(diff.compatibility().monotonic);
+	assert!(diff.compatibility().preserves_validity);
+}
+
+#[test]
+fn diff_extension() {
+	let old = base();
+	let mut new = base();
+	new.insert(rule! {
+		warning for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?y <"https://example.org/#child"> ?x .
+		}
+	});
+
+	let diff = old.diff(&new);
+	assert_eq!(diff.added.len(), 1);
+	assert!(diff.removed.is_empty());
+
+	let compatibility = diff.compatibility();
+	assert!(compatibility.monotonic);
+	assert!(compatibility.preserves_validity);
+}
+
+#[test]
+fn diff_modified() {
+	let old = base();
+	let mut new = System::new();
+
+	new.insert(rule! {
+		<"https://example.org/#ancestor"> for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?x <"https://example.org/#ancestor"> ?y .
+			?y <"https://example.org/#descendant"> ?x .
+		}
+	});
+
+	let diff = old.diff(&new);
+	assert_eq!(diff.modified.len(), 1);
+	assert_eq!(diff.removed.len(), 1);
+
+	let compatibility = diff.compatibility();
+	assert!(!compatibility.monotonic);
+	assert!(!compatibility.preserves_validity);
+}
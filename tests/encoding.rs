@@ -0,0 +1,74 @@
+use inferdf::{
+	rule,
+	system::{DecodeError, SYSTEM_ENCODING_VERSION},
+	System,
+};
+
+fn system() -> System {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x, ?age {
+			?x <"https://example.org/#age"> ?age .
+		} => {
+			(>= ?age 18) .
+			(matches /"^[0-9]+$"/ ?age) !
+		}
+	});
+
+	system.insert(rule! {
+		warning for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+			! ?y <"https://example.org/#name"> "Alice" .
+		} => for ?z {
+			?x <"https://example.org/#grandParent"> ?z !
+			?x = ?y .
+		}
+	});
+
+	system
+}
+
+#[test]
+fn encode_decode() {
+	let system = system();
+	let decoded = System::from_bytes(&system.to_bytes().unwrap()).unwrap();
+	assert!(system.iter().eq(decoded.iter()))
+}
+
+#[test]
+fn decode_unsupported_version() {
+	let mut bytes = system().to_bytes().unwrap();
+	bytes[4] = 0xff;
+	assert!(matches!(
+		System::<rdf_types::Term>::from_bytes(&bytes),
+		Err(DecodeError::UnsupportedVersion(_))
+	))
+}
+
+#[test]
+fn decode_previous_version() {
+	let mut bytes = system().to_bytes().unwrap();
+	let previous = SYSTEM_ENCODING_VERSION - 1;
+	bytes[4..6].copy_from_slice(&previous.to_le_bytes());
+	assert!(matches!(
+		System::<rdf_types::Term>::from_bytes(&bytes),
+		Err(DecodeError::UnsupportedVersion(v)) if v == previous
+	))
+}
+
+#[test]
+fn decode_truncated() {
+	let bytes = system().to_bytes().unwrap();
+	for len in [0, 3, 5] {
+		assert!(matches!(
+			System::<rdf_types::Term>::from_bytes(&bytes[..len]),
+			Err(DecodeError::Truncated)
+		))
+	}
+
+	assert!(matches!(
+		System::<rdf_types::Term>::from_bytes(b"ABCDEF"),
+		Err(DecodeError::InvalidMagic)
+	))
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "gen")]
+use inferdf::{gen::Generator, System};
+use iref::IriBuf;
+use rdf_types::{dataset::TraversableDataset, Quad, Term};
+
+fn iri(iri: &str) -> Term {
+	Term::iri(IriBuf::new(iri.to_owned()).unwrap())
+}
+
+#[test]
+fn deterministic() {
+	let generator = Generator::new().with_persons(50).with_seed(7);
+	assert_eq!(generator.triples(), generator.triples());
+	assert_ne!(
+		generator.triples(),
+		generator.clone().with_seed(8).triples()
+	);
+}
+
+#[test]
+fn size() {
+	let triples = Generator::new()
+		.with_persons(100)
+		.with_organizations(10)
+		.with_fanout(4)
+		.with_ontology(false)
+		.triples();
+
+	// 10 organization types, 9 sub-organization links, and for each person
+	// a type, a name, an employer and 4 acquaintances.
+	assert_eq!(triples.len(), 10 + 9 + 100 * (3 + 4));
+}
+
+#[test]
+fn ontology_closure() {
+	let mut dataset = Generator::new()
+		.with_persons(20)
+		.with_organizations(3)
+		.with_fanout(2)
+		.dataset();
+
+	System::new().close_with_ontology(&mut dataset).unwrap();
+
+	let person = iri("https://example.org/gen/person/0");
+	assert!(dataset.contains(Quad(
+		&person,
+		&iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+		&iri("https://example.org/gen#Employee"),
+		None
+	)));
+	assert!(dataset
+		.quads()
+		.any(|Quad(s, p, _, _)| { *s == person && *p == iri("https://example.org/gen#memberOf") }));
+}
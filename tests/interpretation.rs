@@ -0,0 +1,53 @@
+use inferdf::{
+	pattern::ResourceOrVar,
+	rule,
+	rule::{Conclusion, Hypothesis},
+	Expression, MaybeTrusted, Rule, Signed, System, TripleStatement,
+};
+use rdf_types::{interpretation::Indexed, vocabulary::IndexVocabulary, InterpretationMut};
+
+#[test]
+fn interpret_system() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+			! ?y <"https://example.org/#name"> "France" .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+			_:"foo" <"https://example.org/#count"> 12 !
+		}
+	});
+
+	let mut vocabulary = IndexVocabulary::new();
+	let mut interpretation = Indexed::new();
+
+	let rules: Vec<_> = system.iter().cloned().collect();
+	let interpreted = system.interpret(&mut vocabulary, &mut interpretation);
+	assert_eq!(interpreted.len(), 1);
+
+	let uninterpreted = interpreted
+		.uninterpret(&vocabulary, &interpretation)
+		.unwrap();
+	assert_eq!(uninterpreted.into_iter().collect::<Vec<_>>(), rules)
+}
+
+#[test]
+fn uninterpret_anonymous() {
+	let mut vocabulary = IndexVocabulary::new();
+	let mut interpretation = Indexed::new();
+	let anonymous = interpretation.new_resource(&mut vocabulary);
+
+	let rule = Rule::new(
+		0,
+		Hypothesis::default(),
+		Conclusion::new(
+			0,
+			vec![MaybeTrusted::trusted(Signed::positive(
+				TripleStatement::True(Expression::Resource(ResourceOrVar::Resource(anonymous))),
+			))],
+		),
+	);
+
+	assert!(rule.uninterpret(&vocabulary, &interpretation).is_err())
+}
@@ -0,0 +1,115 @@
+use inferdf::{
+	dataset::isomorphism::{are_isomorphic, are_quads_isomorphic},
+	Sign, Signed,
+};
+use rdf_types::{dataset::IndexedBTreeDataset, grdf_triples, Quad, Triple};
+
+fn dataset(triples: Vec<Triple>) -> IndexedBTreeDataset {
+	triples.into_iter().map(|t| t.into_quad(None)).collect()
+}
+
+#[test]
+fn isomorphic() {
+	let a = dataset(
+		grdf_triples![
+			_:"a" <"https://example.org/#parent"> _:"b" .
+			_:"b" <"https://example.org/#parent"> _:"c" .
+			_:"c" <"https://example.org/#name"> "Alice" .
+		]
+		.into_iter()
+		.collect(),
+	);
+
+	let b = dataset(
+		grdf_triples![
+			_:"y" <"https://example.org/#parent"> _:"z" .
+			_:"x" <"https://example.org/#parent"> _:"y" .
+			_:"z" <"https://example.org/#name"> "Alice" .
+		]
+		.into_iter()
+		.collect(),
+	);
+
+	let c = dataset(
+		grdf_triples![
+			_:"x" <"https://example.org/#parent"> _:"y" .
+			_:"y" <"https://example.org/#parent"> _:"z" .
+			_:"x" <"https://example.org/#name"> "Alice" .
+		]
+		.into_iter()
+		.collect(),
+	);
+
+	assert!(are_isomorphic(&a, &b));
+	assert!(!are_isomorphic(&a, &c));
+}
+
+#[test]
+fn symmetric_cycles() {
+	// Two 2-cycles vs one 4-cycle: same colors, not isomorphic.
+	let a = dataset(
+		grdf_triples![
+			_:"a" <"https://example.org/#next"> _:"b" .
+			_:"b" <"https://example.org/#next"> _:"a" .
+			_:"c" <"https://example.org/#next"> _:"d" .
+			_:"d" <"https://example.org/#next"> _:"c" .
+		]
+		.into_iter()
+		.collect(),
+	);
+
+	let b = dataset(
+		grdf_triples![
+			_:"a" <"https://example.org/#next"> _:"b" .
+			_:"b" <"https://example.org/#next"> _:"c" .
+			_:"c" <"https://example.org/#next"> _:"d" .
+			_:"d" <"https://example.org/#next"> _:"a" .
+		]
+		.into_iter()
+		.collect(),
+	);
+
+	let c = dataset(
+		grdf_triples![
+			_:"w" <"https://example.org/#next"> _:"x" .
+			_:"y" <"https://example.org/#next"> _:"z" .
+			_:"x" <"https://example.org/#next"> _:"w" .
+			_:"z" <"https://example.org/#next"> _:"y" .
+		]
+		.into_iter()
+		.collect(),
+	);
+
+	assert!(!are_isomorphic(&a, &b));
+	assert!(are_isomorphic(&a, &c));
+}
+
+#[test]
+fn signed_isomorphism() {
+	let triples: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"c" <"https://example.org/#parent"> _:"d" .
+	]
+	.into_iter()
+	.collect();
+
+	let quads: Vec<Quad> = triples.into_iter().map(|t| t.into_quad(None)).collect();
+
+	let a = [
+		Signed(Sign::Positive, quads[0].as_ref()),
+		Signed(Sign::Negative, quads[1].as_ref()),
+	];
+
+	let b = [
+		Signed(Sign::Negative, quads[0].as_ref()),
+		Signed(Sign::Positive, quads[1].as_ref()),
+	];
+
+	let c = [
+		Signed(Sign::Positive, quads[0].as_ref()),
+		Signed(Sign::Positive, quads[1].as_ref()),
+	];
+
+	assert!(are_quads_isomorphic(a, b));
+	assert!(!are_quads_isomorphic(a, c));
+}
@@ -0,0 +1,242 @@
+use std::cell::Cell;
+
+use inferdf::{
+	pattern::Canonical,
+	rule,
+	system::{DecodeError, Network},
+	FallibleSignedPatternMatchingDataset, Sign, Signed, SignedPatternMatchingDataset, System,
+};
+use rdf_types::{
+	dataset::{FallibleDataset, IndexedBTreeDataset, TraversableDataset},
+	generator, grdf_triples, Quad, Term, Triple,
+};
+
+fn system() -> System {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?x <"https://example.org/#ancestor"> ?y .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#ancestor"> ?y .
+			?y <"https://example.org/#parent"> ?z .
+		} => {
+			?x <"https://example.org/#ancestor"> ?z .
+		}
+	});
+
+	system
+}
+
+/// Inserts the given facts and their consequences in the network and
+/// dataset.
+fn stream(
+	network: &mut Network,
+	dataset: &mut IndexedBTreeDataset,
+	facts: impl IntoIterator<Item = Triple>,
+) {
+	for fact in facts {
+		let mut stack = vec![fact];
+		while let Some(triple) = stack.pop() {
+			if dataset.insert(triple.clone().into_quad(None)) {
+				let deductions = network.insert(dataset, Signed(Sign::Positive, triple));
+				stack.extend(
+					deductions
+						.eval(generator::Blank::new())
+						.unwrap()
+						.into_positive_triples(),
+				)
+			}
+		}
+	}
+}
+
+#[test]
+fn stream_closure() {
+	let facts: Vec<Triple> = grdf_triples![
+		_:"c" <"https://example.org/#parent"> _:"d" .
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+	]
+	.into_iter()
+	.collect();
+
+	let system = system();
+	let mut network = Network::new(&system);
+	let mut dataset = IndexedBTreeDataset::new();
+	stream(&mut network, &mut dataset, facts.clone());
+
+	let mut expected: IndexedBTreeDataset = facts.into_iter().map(|t| t.into_quad(None)).collect();
+	system.close(&mut expected).unwrap();
+
+	// 3 parent triples, and 6 ancestor triples.
+	assert_eq!(network.len(), 9);
+	assert_eq!(dataset.len(), 9);
+	assert!(expected.quads().all(|q| dataset.contains(q)))
+}
+
+#[test]
+fn self_join() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#sameGroup"> ?y .
+			?y <"https://example.org/#sameGroup"> ?z .
+		} => {
+			?x <"https://example.org/#sameGroup"> ?z .
+		}
+	});
+
+	let triples: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#sameGroup"> _:"a" .
+		_:"b" <"https://example.org/#sameGroup"> _:"a" .
+	]
+	.into_iter()
+	.collect();
+
+	let mut network = Network::new(&system);
+	let mut dataset = IndexedBTreeDataset::new();
+	let mut counts = Vec::new();
+	for triple in triples {
+		dataset.insert(triple.clone().into_quad(None));
+		let deductions = network.insert(&dataset, Signed(Sign::Positive, triple.clone()));
+		counts.push(deductions.into_iter().count());
+
+		// Inserting a triple again does nothing.
+		let deductions = network.insert(&dataset, Signed(Sign::Positive, triple));
+		assert!(deductions.is_empty())
+	}
+
+	// `a a` with `a a`, then `b a` with `a a`.
+	assert_eq!(counts, [1, 1]);
+	assert_eq!(network.partial_matches(), 2)
+}
+
+#[test]
+fn resume_from_bytes() {
+	let system = system();
+	let mut network = Network::new(&system);
+	let mut dataset = IndexedBTreeDataset::new();
+	stream(
+		&mut network,
+		&mut dataset,
+		grdf_triples![
+			_:"a" <"https://example.org/#parent"> _:"b" .
+			_:"b" <"https://example.org/#parent"> _:"c" .
+		],
+	);
+
+	let bytes = network.to_bytes().unwrap();
+	let mut network = Network::from_bytes(&system, &bytes).unwrap();
+	assert_eq!(network.len(), 5);
+	assert!(network.partial_matches() > 0);
+
+	stream(
+		&mut network,
+		&mut dataset,
+		grdf_triples![
+			_:"c" <"https://example.org/#parent"> _:"d" .
+		],
+	);
+
+	// 3 parent triples, and 6 ancestor triples.
+	assert_eq!(network.len(), 9);
+	assert_eq!(dataset.len(), 9)
+}
+
+#[test]
+fn decode_system_mismatch() {
+	let system = system();
+	let bytes = Network::new(&system).to_bytes().unwrap();
+
+	let other: System = system.iter().take(1).cloned().collect();
+	assert!(matches!(
+		Network::from_bytes(&other, &bytes),
+		Err(DecodeError::SystemMismatch)
+	))
+}
+
+/// Dataset failing on demand.
+struct Flaky {
+	dataset: IndexedBTreeDataset,
+	fail: Cell<bool>,
+}
+
+impl FallibleDataset for Flaky {
+	type Resource = Term;
+	type Error = ();
+}
+
+impl FallibleSignedPatternMatchingDataset for Flaky {
+	type TrySignedPatternMatching<'a, 'p>
+		= Box<dyn Iterator<Item = Result<Signed<Quad<&'a Term>>, ()>> + 'a>
+	where
+		Self: 'a,
+		Term: 'p;
+
+	fn try_signed_pattern_matching<'p>(
+		&self,
+		pattern: Signed<Canonical<&'p Term>>,
+	) -> Self::TrySignedPatternMatching<'_, 'p> {
+		if self.fail.get() {
+			Box::new(std::iter::once(Err(())))
+		} else {
+			Box::new(self.dataset.signed_pattern_matching(pattern).map(Ok))
+		}
+	}
+}
+
+#[test]
+fn insert_error() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#parent"> ?y .
+			?y <"https://example.org/#parent"> ?z .
+		} forall ?w {
+			?y <"https://example.org/#parent"> ?w .
+		} => {
+			?w <"https://example.org/#named"> "true" .
+		} => {
+			?x <"https://example.org/#grandParentOfNamed"> ?y .
+		}
+	});
+
+	let facts: Vec<Triple> = grdf_triples![
+		_:"b" <"https://example.org/#parent"> _:"c" .
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"c" <"https://example.org/#named"> "true" .
+	]
+	.into_iter()
+	.collect();
+
+	let dataset = Flaky {
+		dataset: facts.iter().cloned().map(|t| t.into_quad(None)).collect(),
+		fail: Cell::new(false),
+	};
+	let mut network = Network::new(&system);
+	let mut expected = Network::new(&system);
+	for fact in &facts[..2] {
+		expected.insert(&dataset.dataset, Signed(Sign::Positive, fact.clone()));
+	}
+
+	let first = Signed(Sign::Positive, facts[0].clone());
+	assert!(network.try_insert(&dataset, first).unwrap().is_empty());
+
+	// The failed insertion leaves the network untouched.
+	dataset.fail.set(true);
+	let second = Signed(Sign::Positive, facts[1].clone());
+	assert!(network.try_insert(&dataset, second.clone()).is_err());
+	assert_eq!(network.len(), 1);
+
+	dataset.fail.set(false);
+	assert_eq!(network.try_insert(&dataset, second).unwrap().len(), 1);
+	assert_eq!(network.len(), 2);
+	assert_eq!(network.partial_matches(), expected.partial_matches());
+}
@@ -0,0 +1,67 @@
+use inferdf::{rule, System};
+use rdf_types::{dataset::IndexedBTreeDataset, grdf_triples, Triple};
+
+fn system() -> System {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?person, ?country {
+			?person <"https://example.org/#citizenOf"> ?country .
+		} => {
+			?person <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		}
+	});
+
+	system.insert(rule! {
+		["strict", "audit"]
+		for ?person, ?country {
+			?person <"https://example.org/#citizenOf"> ?country .
+		} => {
+			?person <"https://example.org/#passport"> ?country .
+		}
+	});
+
+	system
+}
+
+#[test]
+fn with_profiles() {
+	let system = system();
+	assert!(system.get(0).unwrap().profiles.is_empty());
+	assert_eq!(system.get(1).unwrap().profiles.len(), 2);
+
+	assert_eq!(system.with_profiles::<&str>(&[]).len(), 1);
+	assert_eq!(system.with_profiles(&["lax"]).len(), 1);
+	assert_eq!(system.with_profiles(&["strict"]).len(), 2);
+	assert_eq!(system.with_profiles(&["lax", "audit"]).len(), 2);
+}
+
+#[test]
+fn profile_validation() {
+	let dataset: IndexedBTreeDataset = grdf_triples![
+		_:"a" <"https://example.org/#citizenOf"> _:"b" .
+		_:"a" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+	]
+	.into_iter()
+	.map(|t: Triple| t.into_quad(None))
+	.collect();
+
+	let system = system();
+	assert!(system
+		.with_profiles(&["lax"])
+		.report(&dataset)
+		.unwrap()
+		.is_valid());
+	assert!(system
+		.with_profiles(&["strict"])
+		.report(&dataset)
+		.unwrap()
+		.is_invalid());
+}
+
+#[test]
+fn profiles_encoding() {
+	let system = system();
+	let decoded = System::from_bytes(&system.to_bytes().unwrap()).unwrap();
+	assert_eq!(decoded.get(1), system.get(1))
+}
@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use inferdf::repl::{Error, Session};
+
+fn session() -> Session {
+	let mut session = Session::new();
+	session
+		.execute("@prefix ex: <https://example.org/#> .")
+		.unwrap();
+	session
+}
+
+#[test]
+fn query() {
+	let mut session = session();
+	assert_eq!(
+		session
+			.execute("triple(ex:a, ex:parent, ex:b), triple(ex:b, ex:parent, ex:c).")
+			.unwrap(),
+		"2 facts added"
+	);
+
+	assert_eq!(
+		session
+			.execute("?- triple(X, ex:parent, Y), triple(Y, ex:parent, Z).")
+			.unwrap(),
+		"?X = ex:a, ?Y = ex:b, ?Z = ex:c\n1 match"
+	);
+	assert_eq!(
+		session.execute("?- triple(ex:a, ex:parent, ex:b)").unwrap(),
+		"yes\n1 match"
+	);
+	assert_eq!(
+		session.execute("?- triple(ex:b, ex:parent, ex:a)").unwrap(),
+		"0 matches"
+	)
+}
+
+#[test]
+fn why() {
+	let mut session = session();
+	session
+		.execute(":load tests/fixtures/ancestors.dl")
+		.unwrap();
+	session
+		.execute("triple(ex:a, ex:parent, ex:b), triple(ex:b, ex:parent, ex:c)")
+		.unwrap();
+
+	assert_eq!(
+		session.execute(":rules").unwrap(),
+		"#0 triple(X0, ex:ancestor, X1) :- triple(X0, ex:parent, X1).\n\
+		 #1 triple(X0, ex:ancestor, X2) :- triple(X0, ex:ancestor, X1), triple(X1, ex:parent, X2)."
+	);
+	assert!(session
+		.execute(":close")
+		.unwrap()
+		.ends_with("3 entailed facts"));
+
+	assert_eq!(
+		session
+			.execute(":why triple(ex:a, ex:ancestor, ex:c)")
+			.unwrap(),
+		"ex:a ex:ancestor ex:c .\n  \
+		 by rule #1 with ?X = ex:a, ?Y = ex:b, ?Z = ex:c\n    \
+		 ex:a ex:ancestor ex:b .\n    \
+		 ex:b ex:parent ex:c ."
+	);
+	assert_eq!(
+		session.execute(":why triple(X, ex:parent, ex:c)").unwrap(),
+		"ex:b ex:parent ex:c .\n  stated"
+	);
+}
+
+#[test]
+fn errors() {
+	let mut session = session();
+	assert!(matches!(
+		session.execute("triple(X, ex:p, ex:o)."),
+		Err(Error::NotAFact)
+	));
+	assert!(matches!(
+		session.execute(":why triple(X, ex:p, Y), triple(Y, ex:p, X)."),
+		Err(Error::NotASinglePattern)
+	));
+	assert!(matches!(
+		session.execute(":frobnicate"),
+		Err(Error::UnknownCommand(_))
+	));
+	assert!(matches!(
+		session.execute("?- triple(X, foo:p, Y)"),
+		Err(Error::Datalog(_))
+	));
+}
+
+#[test]
+fn validate() {
+	let mut session = session();
+	session
+		.execute("triple(X, ex:ancestor, Y) :- triple(X, ex:parent, Y)")
+		.unwrap();
+	session.execute("triple(ex:a, ex:parent, ex:b)").unwrap();
+
+	assert_eq!(
+		session.execute(":validate").unwrap(),
+		"#0 1 violation\n  \
+		 error: missing triple ex:a ex:ancestor ex:b .\n    \
+		 with ?X = ex:a, ?Y = ex:b\n\
+		 invalid: 1 error, 0 warnings"
+	);
+
+	session.execute(":close").unwrap();
+	assert_eq!(
+		session.execute(":validate").unwrap(),
+		"#0 ok\nvalid: 0 errors, 0 warnings"
+	);
+}
+
+#[test]
+fn report_json() {
+	let mut session = session();
+	session
+		.execute("triple(X, ex:ancestor, Y) :- triple(X, ex:parent, Y)")
+		.unwrap();
+	session
+		.execute("triple(ex:a, ex:parent, ex:b), triple(ex:b, ex:parent, ex:c)")
+		.unwrap();
+	session.execute(":close").unwrap();
+	session
+		.execute("triple(X, ex:descendant, Y) :- triple(Y, ex:parent, X)")
+		.unwrap();
+
+	let report = session.report().unwrap();
+	assert_eq!(
+		session.report_json(&report, Duration::from_micros(42)),
+		"{\"valid\":false,\"statedFacts\":2,\"derivedFacts\":2,\"rulesFired\":1,\
+		 \"errors\":1,\"warnings\":0,\"elapsedMicros\":42,\"violations\":[\
+		 {\"rule\":1,\"id\":null,\"severity\":\"error\",\
+		 \"reason\":\"missing triple ex:c ex:descendant ex:b .\",\
+		 \"terms\":[\"ex:c\",\"ex:descendant\",\"ex:b\"],\
+		 \"bindings\":{\"?Y\":\"ex:b\",\"?X\":\"ex:c\"}}]}"
+	);
+}
+
+#[test]
+fn describe() {
+	let mut session = session();
+	session
+		.execute("triple(ex:a, ex:address, _:b), triple(_:b, ex:city, \"Paris\"), triple(ex:c, ex:knows, ex:a)")
+		.unwrap();
+
+	assert_eq!(
+		session.execute(":describe ex:a").unwrap(),
+		"_:b ex:city \"Paris\" .\nex:a ex:address _:b ."
+	);
+	assert_eq!(session.execute(":describe ex:z").unwrap(), "no description");
+	assert!(matches!(
+		session.execute(":describe X"),
+		Err(Error::NotAResource)
+	));
+}
@@ -0,0 +1,92 @@
+use inferdf::{rule, same_as::Identities, System};
+use rdf_types::{dataset::IndexedBTreeGraph, generator, grdf_triples, BlankIdBuf, Term, Triple};
+
+#[test]
+fn export_import() {
+	let links: Vec<Triple> = grdf_triples![
+		_:"a" <"http://www.w3.org/2002/07/owl#sameAs"> <"https://example.org/#alice"> .
+		_:"b" <"http://www.w3.org/2002/07/owl#sameAs"> _:"a" .
+		_:"c" <"http://www.w3.org/2002/07/owl#differentFrom"> _:"a" .
+		_:"c" <"https://example.org/#name"> "Carol" .
+	]
+	.into_iter()
+	.collect();
+
+	let mut identities = Identities::new();
+	let mut rest = Vec::new();
+	for t in &links {
+		if !identities.insert_triple(t).unwrap() {
+			rest.push(t.clone())
+		}
+	}
+
+	assert_eq!(rest.len(), 1);
+	assert!(identities.are_same(&links[0].0, &links[1].0));
+	assert!(identities.are_different(&links[2].0, &links[1].0));
+
+	let exported = identities.triples();
+	assert_eq!(exported.len(), 3);
+
+	let mut imported = Identities::new();
+	for t in &exported {
+		assert!(imported.insert_triple(t).unwrap());
+	}
+
+	assert_eq!(imported.triples(), exported);
+}
+
+#[test]
+fn contradiction() {
+	let links: Vec<Triple> = grdf_triples![
+		_:"a" <"http://www.w3.org/2002/07/owl#differentFrom"> _:"c" .
+		_:"a" <"http://www.w3.org/2002/07/owl#sameAs"> _:"b" .
+		_:"b" <"http://www.w3.org/2002/07/owl#sameAs"> _:"c" .
+	]
+	.into_iter()
+	.collect();
+
+	let mut identities = Identities::new();
+	assert!(identities.insert_triple(&links[0]).unwrap());
+	assert!(identities.insert_triple(&links[1]).unwrap());
+	assert!(identities.insert_triple(&links[2]).is_err());
+
+	// The failed merge is reverted.
+	assert!(!identities.are_same(&links[1].0, &links[2].2));
+}
+
+#[test]
+fn apply_equalities_contradiction() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y, ?e {
+			?x <"https://example.org/#email"> ?e .
+			?y <"https://example.org/#email"> ?e .
+		} => {
+			?x = ?y .
+		}
+	});
+
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"a" <"https://example.org/#email"> "alice@example.org" .
+		_:"b" <"https://example.org/#email"> "alice@example.org" .
+		_:"c" <"https://example.org/#email"> "alice@example.org" .
+	]
+	.into_iter()
+	.collect();
+
+	let [a, b, c]: [Term; 3] =
+		["a", "b", "c"].map(|b| Term::blank(BlankIdBuf::new(format!("_:{b}")).unwrap()));
+	let mut identities = Identities::new();
+	identities.separate(&a, &b).unwrap();
+
+	let deductions = system
+		.deduce(&dataset)
+		.eval(generator::Blank::new())
+		.unwrap();
+	assert!(deductions.apply_equalities(&mut identities).is_err());
+
+	// No equality is applied, even those applied before the contradiction.
+	assert!(!identities.are_same(&b, &c));
+	assert!(!identities.are_same(&a, &c));
+	assert!(identities.are_different(&a, &b))
+}
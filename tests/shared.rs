@@ -0,0 +1,96 @@
+use std::{
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+	thread,
+};
+
+use inferdf::{
+	rule,
+	system::{ReloadError, SharedSystem},
+	System,
+};
+use rdf_types::{dataset::IndexedBTreeDataset, grdf_triples, Triple};
+
+#[test]
+fn replace() {
+	let shared = SharedSystem::new(System::new());
+	let added = Arc::new(AtomicUsize::new(0));
+	let hook_added = added.clone();
+	shared.on_reload(move |diff| {
+		hook_added.fetch_add(diff.added.len(), Ordering::Relaxed);
+	});
+
+	let snapshot = shared.get();
+
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?y <"https://example.org/#child"> ?x .
+		}
+	});
+
+	shared.replace(system);
+	assert_eq!(added.load(Ordering::Relaxed), 1);
+	assert!(snapshot.is_empty());
+	assert_eq!(shared.get().len(), 1);
+}
+
+#[test]
+fn reload_from_paths() {
+	let shared = SharedSystem::new(System::new());
+	let removed = Arc::new(AtomicUsize::new(0));
+	let hook_removed = removed.clone();
+	shared.on_reload(move |diff| {
+		hook_removed.fetch_add(diff.removed.len(), Ordering::Relaxed);
+	});
+
+	shared
+		.reload_from_paths(["tests/fixtures/ancestors.dl"])
+		.unwrap();
+	assert_eq!(shared.get().len(), 2);
+
+	assert!(matches!(
+		shared.reload_from_paths(["tests/fixtures/missing.dl"]),
+		Err(ReloadError::Io(_, _))
+	));
+	assert_eq!(shared.get().len(), 2);
+
+	shared.replace(System::new());
+	assert_eq!(removed.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn shared_between_threads() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?w {
+			?x <"https://example.org/#width"> ?w .
+		} => {
+			?x <"https://example.org/#perimeter"> (* 4 ?w) .
+		}
+	});
+
+	let shared = Arc::new(SharedSystem::new(system));
+	let dataset: IndexedBTreeDataset = grdf_triples![
+		_:"0" <"https://example.org/#width"> "2"^^"http://www.w3.org/2001/XMLSchema#integer" .
+	]
+	.into_iter()
+	.map(|t: Triple| t.into_quad(None))
+	.collect();
+
+	let reader = {
+		let shared = shared.clone();
+		thread::spawn(move || {
+			let mut dataset = dataset;
+			shared.get().close(&mut dataset).unwrap()
+		})
+	};
+
+	assert_eq!(reader.join().unwrap(), 1);
+	shared.replace(System::new());
+	assert!(shared.get().is_empty());
+}
@@ -0,0 +1,50 @@
+use inferdf::{
+	dataset::stats::Statistics,
+	display::{DisplayContext, DisplayWithContext},
+	same_as::Identities,
+};
+use iref::IriBuf;
+use rdf_types::Term;
+
+#[test]
+fn statistics() {
+	let dataset = inferdf::testing::parse_nquads(
+		r#"
+		_:a <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/#Person> .
+		_:a <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/#Person> <https://example.org/#g> .
+		_:b <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/#Person> .
+		_:a <https://example.org/#knows> _:b <https://example.org/#g> .
+		"#,
+	)
+	.unwrap();
+
+	let mut identities = Identities::new();
+	identities
+		.merge(
+			&Term::blank("_:a".parse().unwrap()),
+			&Term::blank("_:c".parse().unwrap()),
+		)
+		.unwrap();
+
+	let stats = Statistics::new(&dataset).with_identities(&identities);
+	assert_eq!(stats.quads, 4);
+	assert_eq!(stats.resources, 3);
+	assert_eq!(stats.merged, 1);
+
+	let context = DisplayContext::new().with_prefix(
+		"ex".to_owned(),
+		IriBuf::new("https://example.org/#".to_owned()).unwrap(),
+	);
+	assert_eq!(
+		stats.with(&context).to_string(),
+		"4 quads, 3 resources, 1 merged\n\
+		 predicates:\n  \
+		 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> 3\n  \
+		 ex:knows 1\n\
+		 classes:\n  \
+		 ex:Person 2\n\
+		 graphs:\n  \
+		 (default) 2\n  \
+		 ex:g 2"
+	);
+}
@@ -0,0 +1,68 @@
+use inferdf::dataset::subset::{describe, extract, write_ntriples};
+use rdf_types::{dataset::IndexedBTreeDataset, grdf_triples, BlankIdBuf, Term, Triple};
+
+fn dataset(triples: impl IntoIterator<Item = Triple>) -> IndexedBTreeDataset {
+	triples.into_iter().map(|t| t.into_quad(None)).collect()
+}
+
+fn blank(id: &str) -> Term {
+	Term::blank(BlankIdBuf::from_suffix(id).unwrap())
+}
+
+#[test]
+fn extract_reachable() {
+	let input = dataset(grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+		_:"b" <"https://example.org/#parent"> _:"c" .
+		_:"c" <"https://example.org/#parent"> _:"a" .
+		_:"d" <"https://example.org/#parent"> _:"a" .
+	]);
+
+	let slice: IndexedBTreeDataset = extract(&input, [blank("a")], None);
+	assert_eq!(
+		slice,
+		dataset(grdf_triples![
+			_:"a" <"https://example.org/#parent"> _:"b" .
+			_:"b" <"https://example.org/#parent"> _:"c" .
+			_:"c" <"https://example.org/#parent"> _:"a" .
+		])
+	);
+
+	let slice: IndexedBTreeDataset = extract(&input, [blank("a")], Some(1));
+	assert_eq!(
+		slice,
+		dataset(grdf_triples![
+			_:"a" <"https://example.org/#parent"> _:"b" .
+			_:"b" <"https://example.org/#parent"> _:"c" .
+		])
+	);
+
+	let slice: IndexedBTreeDataset = extract(&input, [blank("d")], Some(0));
+	assert_eq!(
+		slice,
+		dataset(grdf_triples![
+			_:"d" <"https://example.org/#parent"> _:"a" .
+		])
+	);
+}
+
+#[test]
+fn describe_follows_blank_nodes() {
+	let input = dataset(grdf_triples![
+		<"https://example.org/#a"> <"https://example.org/#address"> _:"b" .
+		<"https://example.org/#a"> <"https://example.org/#knows"> <"https://example.org/#c"> .
+		_:"b" <"https://example.org/#city"> "Paris" .
+		_:"b" <"https://example.org/#geo"> _:"d" .
+		_:"d" <"https://example.org/#lat"> "48.85" .
+		<"https://example.org/#c"> <"https://example.org/#name"> "C" .
+	]);
+
+	let a = Term::iri(iref::IriBuf::new("https://example.org/#a".to_owned()).unwrap());
+	let description: Vec<Triple> = describe(&input, &a);
+	assert_eq!(description.len(), 5);
+
+	let mut ntriples = String::new();
+	write_ntriples(&mut ntriples, &description).unwrap();
+	assert!(ntriples.contains("_:d <https://example.org/#lat> \"48.85\" .\n"));
+	assert!(!ntriples.contains("#name"));
+}
@@ -0,0 +1,70 @@
+use inferdf::{rule, System};
+
+#[test]
+fn no_existential() {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#ancestor"> ?y .
+			?y <"https://example.org/#parent"> ?z .
+		} => {
+			?x <"https://example.org/#ancestor"> ?z .
+		}
+	});
+
+	assert!(system.check_termination().is_weakly_acyclic())
+}
+
+#[test]
+fn acyclic_existential() {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#citizenOf"> ?y .
+		} => for ?p {
+			?x <"https://example.org/#passport"> ?p .
+			?p <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Passport"> .
+		}
+	});
+
+	assert!(system.check_termination().is_weakly_acyclic())
+}
+
+#[test]
+fn cyclic_existential() {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		} => for ?p {
+			?x <"https://example.org/#parent"> ?p .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?y <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?y <"https://example.org/#child"> ?x .
+		}
+	});
+
+	let termination = system.check_termination();
+	assert!(!termination.is_weakly_acyclic());
+	assert_eq!(termination.rules, [0].into_iter().collect());
+	assert_eq!(
+		termination.dependencies,
+		[(0, 1), (1, 0)].into_iter().collect()
+	)
+}
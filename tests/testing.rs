@@ -0,0 +1,156 @@
+use inferdf::{
+	display::DisplayContext,
+	rule,
+	system::ClosureLimits,
+	testing::{parse_triples, Batch, ExitCode, Fixture, Manifest},
+	Source, System,
+};
+use static_iref::iri;
+
+fn system() -> System {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?person, ?country {
+			?person <"https://example.org/#citizenOf"> ?country .
+		} => {
+			?person <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Human"> .
+		}
+	});
+	system
+}
+
+#[test]
+fn fixture_deduce() {
+	let fixture = Fixture::load("tests/fixtures/citizens.nq").unwrap();
+	let deduced = fixture.deduce(&system()).unwrap();
+
+	deduced.assert_contains(
+		"_:JohnSmith <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/#Human> .",
+	);
+
+	deduced.assert_not_contains(
+		"_:France <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/#Human> .",
+	);
+
+	fixture.assert_invalid(&system());
+}
+
+#[test]
+#[should_panic(expected = "+ _:FrançoisDupont")]
+fn fixture_deduce_mismatch() {
+	let fixture = Fixture::load("tests/fixtures/citizens.nq").unwrap();
+	fixture.deduce(&system()).unwrap().assert_eq(
+		"_:JohnSmith <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/#Human> .",
+	);
+}
+
+#[test]
+fn fixture_valid() {
+	let fixture = Fixture::parse(
+		r#"
+		_:JohnSmith <https://example.org/#citizenOf> _:UnitedKingdom .
+		_:JohnSmith <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/#Human> .
+		"#,
+	)
+	.unwrap();
+
+	fixture.assert_valid(&system());
+
+	let report = fixture.validate(&system()).unwrap();
+	assert_eq!(ExitCode::from(&report).code(), 0);
+}
+
+#[test]
+fn entailment_manifest() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?a, ?b {
+			?a <"http://www.w3.org/2000/01/rdf-schema#subClassOf"> ?b .
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> ?a .
+		} => {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> ?b .
+		}
+	});
+
+	let manifest = Manifest::load(
+		"tests/fixtures/entailment/manifest.nt",
+		iri!("https://example.org/tests/"),
+	)
+	.unwrap();
+	assert_eq!(manifest.tests.len(), 2);
+	assert_eq!(manifest.skipped, ["inconsistent"]);
+
+	let report = manifest.run(&system, &ClosureLimits::default());
+	assert!(report.is_conformant(), "{report}");
+	assert_eq!(report.passed(), 2);
+
+	let report = manifest.run(&System::new(), &ClosureLimits::default());
+	assert_eq!(report.failures().collect::<Vec<_>>(), ["subclass"]);
+}
+
+#[test]
+fn fixture_sources() {
+	let fixture =
+		Fixture::load_all(["tests/fixtures/citizens.nq", "tests/fixtures/citizens.nq"]).unwrap();
+
+	let triple = parse_triples("_:JohnSmith <https://example.org/#citizenOf> _:UnitedKingdom .")
+		.unwrap()
+		.pop_first()
+		.unwrap();
+
+	assert_eq!(fixture.source_of(&triple), Some(Source::new(0, 2)));
+	assert_eq!(fixture.document(1), Some("tests/fixtures/citizens.nq"));
+}
+
+#[test]
+#[should_panic(expected = "matched facts at tests/fixtures/citizens.nq:")]
+fn fixture_invalid_sources() {
+	Fixture::load("tests/fixtures/citizens.nq")
+		.unwrap()
+		.assert_valid(&system());
+}
+
+#[test]
+#[should_panic(expected = "with ?person = _:")]
+fn fixture_invalid_bindings() {
+	Fixture::load("tests/fixtures/citizens.nq")
+		.unwrap()
+		.assert_valid(&system());
+}
+
+#[test]
+#[should_panic(expected = "rdf:type ex:Human .")]
+fn fixture_invalid_prefixes() {
+	let context = DisplayContext::new()
+		.with_prefix(
+			"rdf".to_owned(),
+			iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#").to_owned(),
+		)
+		.with_prefix("ex".to_owned(), iri!("https://example.org/#").to_owned());
+
+	Fixture::load("tests/fixtures/citizens.nq")
+		.unwrap()
+		.with_display_context(context)
+		.assert_valid(&system());
+}
+
+#[test]
+fn batch() {
+	let batch = Batch::from_dir("tests/fixtures/batch").unwrap();
+	assert_eq!(batch.paths.len(), 3);
+
+	for threads in [0, 1, 2] {
+		let report = batch.run(&system(), threads);
+		assert!(!report.is_valid());
+		assert_eq!(report.valid(), 1);
+		assert_eq!(
+			report.invalid().collect::<Vec<_>>(),
+			[std::path::Path::new("tests/fixtures/batch/citizen.nq")]
+		);
+		assert_eq!(
+			report.errors().map(|(path, _)| path).collect::<Vec<_>>(),
+			[std::path::Path::new("tests/fixtures/batch/malformed.nt")]
+		);
+		assert_eq!(ExitCode::from(&report), ExitCode::Error);
+	}
+}
@@ -0,0 +1,28 @@
+use inferdf::utils::UnionFind;
+
+#[test]
+fn merge() {
+	let mut uf = UnionFind::new();
+	uf.merge(&0, &1);
+	uf.merge(&2, &3);
+	assert!(uf.are_merged(&0, &1));
+	assert!(!uf.are_merged(&1, &2));
+
+	uf.merge(&1, &3);
+	assert!(uf.are_merged(&0, &2));
+	assert_eq!(uf.find(&4), &4);
+}
+
+#[test]
+fn revert() {
+	let mut uf = UnionFind::new();
+	uf.merge(&0, &1);
+
+	let mut tentative = uf.clone();
+	tentative.merge(&1, &2);
+	assert!(tentative.are_merged(&0, &2));
+
+	// Dropping the tentative merges leaves the original untouched.
+	drop(tentative);
+	assert!(!uf.are_merged(&0, &2));
+}
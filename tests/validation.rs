@@ -1,4 +1,4 @@
-use inferdf::{rule, Validation};
+use inferdf::{expression, rule, testing::parse_nquads, Severity, System, Validation};
 use rdf_types::{dataset::IndexedBTreeGraph, grdf_triples};
 
 #[test]
@@ -20,6 +20,58 @@ fn validate_comparison() {
 	assert_eq!(rule.validate(&dataset).unwrap(), Validation::Ok);
 }
 
+#[test]
+fn validate_numeric_promotion() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#height"> "1.5e3"^^"http://www.w3.org/2001/XMLSchema#double" .
+		_:"0" <"https://example.org/#width"> "500"^^"http://www.w3.org/2001/XMLSchema#integer" .
+		_:"0" <"https://example.org/#depth"> "2.5"^^"http://www.w3.org/2001/XMLSchema#float" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x, ?h, ?w, ?d {
+			?x <"https://example.org/#height"> ?h .
+			?x <"https://example.org/#width"> ?w .
+			?x <"https://example.org/#depth"> ?d .
+		} => {
+			(> ?h ?w) .
+			(< ?d ?w) .
+			(= ?h 1500) .
+			(= (+ ?w ?w ?w) ?h) .
+			(= (* ?d 2) 5.0) .
+			(= (/ ?w 4) 125) .
+			(= (/ 1 3) (/ 2 6)) .
+		}
+	};
+
+	assert_eq!(rule.validate(&dataset).unwrap(), Validation::Ok);
+
+	let rule = rule! {
+		for ?x, ?w {
+			?x <"https://example.org/#width"> ?w .
+		} => {
+			(= (- ?w 1) ?w) .
+		}
+	};
+
+	assert!(rule.validate(&dataset).unwrap().is_invalid());
+
+	let rule = rule! {
+		for ?x, ?w {
+			?x <"https://example.org/#width"> ?w .
+		} => {
+			(= (/ ?w 0) 0) .
+		}
+	};
+
+	assert!(matches!(
+		rule.validate(&dataset),
+		Err(expression::Error::DivisionByZero)
+	));
+}
+
 #[test]
 fn validate_regex() {
 	let dataset: IndexedBTreeGraph = grdf_triples![
@@ -39,6 +91,85 @@ fn validate_regex() {
 	assert_eq!(rule.validate(&dataset).unwrap(), Validation::Ok);
 }
 
+#[test]
+fn validate_language_tags() {
+	let dataset = parse_nquads(
+		r#"
+		_:0 <http://www.w3.org/2000/01/rdf-schema#label> "Chat"@fr .
+		_:0 <http://www.w3.org/2000/01/rdf-schema#label> "Cat"@en-GB .
+		_:0 <https://example.org/#code> "fr" .
+		"#,
+	)
+	.unwrap();
+
+	let rule = rule! {
+		for ?x, ?label {
+			?x <"http://www.w3.org/2000/01/rdf-schema#label"> ?label .
+		} => {
+			(langMatches (lang ?label) "*") .
+		}
+	};
+
+	assert_eq!(rule.validate(&dataset).unwrap(), Validation::Ok);
+
+	let rule = rule! {
+		for ?x, ?label {
+			?x <"http://www.w3.org/2000/01/rdf-schema#label"> ?label .
+		} => {
+			(langMatches (lang ?label) "en") .
+		}
+	};
+
+	assert!(rule.validate(&dataset).unwrap().is_invalid());
+
+	let rule = rule! {
+		for ?x, ?code, ?label {
+			?x <"https://example.org/#code"> ?code .
+			?x <"http://www.w3.org/2000/01/rdf-schema#label"> ?label .
+		} => {
+			(!= ?label (strLang "Chat" ?code)) .
+		}
+	};
+
+	assert!(rule.validate(&dataset).unwrap().is_invalid());
+}
+
+#[test]
+fn validate_term_kinds() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#homepage"> <"https://example.org/alice"> .
+		_:"0" <"https://example.org/#age"> "42"^^"http://www.w3.org/2001/XMLSchema#integer" .
+	]
+	.into_iter()
+	.collect();
+
+	let rule = rule! {
+		for ?x, ?page, ?age {
+			?x <"https://example.org/#homepage"> ?page .
+			?x <"https://example.org/#age"> ?age .
+		} => {
+			(isBlank ?x) .
+			(isIri ?page) .
+			(isLiteral ?age) .
+			(= (str ?page) "https://example.org/alice") .
+			(= ?page (iri "https://example.org/alice")) .
+			(= (datatype ?age) <"http://www.w3.org/2001/XMLSchema#integer">) .
+		}
+	};
+
+	assert_eq!(rule.validate(&dataset).unwrap(), Validation::Ok);
+
+	let rule = rule! {
+		for ?x, ?page {
+			?x <"https://example.org/#homepage"> ?page .
+		} => {
+			(isLiteral ?page) .
+		}
+	};
+
+	assert!(rule.validate(&dataset).unwrap().is_invalid());
+}
+
 #[test]
 fn validation_failure() {
 	let dataset: IndexedBTreeGraph = grdf_triples![
@@ -57,3 +188,109 @@ fn validation_failure() {
 
 	assert!(rule.validate(&dataset).unwrap().is_invalid());
 }
+
+#[test]
+fn validation_warning() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#age"> "12"^^"http://www.w3.org/2001/XMLSchema#int" .
+	]
+	.into_iter()
+	.collect();
+
+	let mut system = System::new();
+	system.insert(rule! {
+		warning for ?x, ?age {
+			?x <"https://example.org/#age"> ?age .
+		} => {
+			(>= ?age 18) .
+		}
+	});
+
+	assert_eq!(system.validate(&dataset).unwrap(), Validation::Ok);
+
+	let report = system.report(&dataset).unwrap();
+	assert!(report.is_valid());
+	assert_eq!(report.warnings().count(), 1);
+	assert_eq!(report.violations[0].severity, Severity::Warning);
+
+	system.insert(rule! {
+		for ?x, ?age {
+			?x <"https://example.org/#age"> ?age .
+		} => {
+			(>= ?age 16) .
+		}
+	});
+
+	assert!(system.validate(&dataset).unwrap().is_invalid());
+	assert_eq!(system.report(&dataset).unwrap().errors().count(), 1);
+}
+
+#[test]
+fn validate_universal_condition() {
+	let rule = rule! {
+		for ?t {
+			?t <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Team"> .
+		} forall ?x {
+			?t <"https://example.org/#member"> ?x .
+		} => {
+			?x <"https://example.org/#certified"> "true" .
+		} => {
+			?t <"https://example.org/#certified"> "true" .
+		}
+	};
+
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"t" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Team"> .
+		_:"t" <"https://example.org/#member"> _:"alice" .
+		_:"t" <"https://example.org/#member"> _:"bob" .
+		_:"alice" <"https://example.org/#certified"> "true" .
+		_:"bob" <"https://example.org/#certified"> "true" .
+	]
+	.into_iter()
+	.collect();
+
+	assert!(rule.validate(&dataset).unwrap().is_invalid());
+
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"t" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Team"> .
+		_:"t" <"https://example.org/#member"> _:"alice" .
+		_:"t" <"https://example.org/#member"> _:"bob" .
+		_:"alice" <"https://example.org/#certified"> "true" .
+	]
+	.into_iter()
+	.collect();
+
+	assert_eq!(rule.validate(&dataset).unwrap(), Validation::Ok);
+}
+
+#[test]
+fn violation_bindings() {
+	let dataset: IndexedBTreeGraph = grdf_triples![
+		_:"0" <"https://example.org/#age"> "12"^^"http://www.w3.org/2001/XMLSchema#int" .
+	]
+	.into_iter()
+	.collect();
+
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?person, ?age {
+			?person <"https://example.org/#age"> ?age .
+		} => {
+			(>= ?age 18) .
+		}
+	});
+
+	let report = system.report(&dataset).unwrap();
+	let violation = &report.violations[0];
+	let bindings: Vec<_> = violation
+		.bindings(system.get(violation.rule).unwrap())
+		.map(|(x, value)| format!("{x} = {value}"))
+		.collect();
+	assert_eq!(
+		bindings,
+		[
+			"?person = _:0",
+			"?age = \"12\"^^<http://www.w3.org/2001/XMLSchema#int>"
+		]
+	)
+}
@@ -0,0 +1,265 @@
+use inferdf::{rule, Reason, Sign, Signed, System};
+use rdf_types::{dataset::IndexedBTreeDataset, grdf_triples, BlankIdBuf, Quad, Term, Triple};
+use static_iref::iri;
+
+fn system() -> System {
+	let mut system = System::new();
+
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#parent"> ?y .
+		} => {
+			?x <"https://example.org/#ancestor"> ?y .
+		}
+	});
+
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#ancestor"> ?y .
+			?y <"https://example.org/#parent"> ?z .
+		} => {
+			?x <"https://example.org/#ancestor"> ?z .
+		}
+	});
+
+	system
+}
+
+fn dataset() -> IndexedBTreeDataset {
+	grdf_triples![
+		_:"b" <"https://example.org/#parent"> _:"c" .
+		_:"b" <"https://example.org/#ancestor"> _:"c" .
+	]
+	.into_iter()
+	.map(|t: Triple| t.into_quad(None))
+	.collect()
+}
+
+#[test]
+fn what_if() {
+	let dataset = dataset();
+	let added: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+	]
+	.into_iter()
+	.collect();
+
+	let impact = system().what_if(&dataset, added, 8).unwrap();
+
+	let expected: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#ancestor"> _:"b" .
+		_:"a" <"https://example.org/#ancestor"> _:"c" .
+	]
+	.into_iter()
+	.collect();
+
+	assert_eq!(impact.conclusions, expected.into_iter().collect());
+	assert!(!impact.truncated);
+	assert!(impact.is_consistent());
+
+	// The original dataset is untouched.
+	assert_eq!(dataset, self::dataset());
+}
+
+#[test]
+fn what_if_bounded() {
+	let added: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#parent"> _:"b" .
+	]
+	.into_iter()
+	.collect();
+
+	let impact = system().what_if(&dataset(), added, 1).unwrap();
+	assert_eq!(impact.conclusions.len(), 1);
+	assert!(impact.truncated);
+}
+
+#[test]
+fn what_if_contradiction() {
+	let mut system = system();
+	system.insert(rule! {
+		for ?x, ?age {
+			?x <"https://example.org/#age"> ?age .
+		} => {
+			(>= ?age 18) .
+		}
+	});
+
+	let added: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#age"> "12"^^"http://www.w3.org/2001/XMLSchema#int" .
+	]
+	.into_iter()
+	.collect();
+
+	let impact = system.what_if(&dataset(), added, 8).unwrap();
+	assert!(impact.conclusions.is_empty());
+	assert_eq!(impact.contradictions.len(), 1);
+	assert_eq!(impact.contradictions[0].rule, 2);
+}
+
+#[test]
+fn dependents() {
+	let mut dataset = dataset();
+	dataset.insert(
+		Triple(
+			Term::blank(BlankIdBuf::from_suffix("a").unwrap()),
+			Term::iri(iri!("https://example.org/#ancestor").to_owned()),
+			Term::blank(BlankIdBuf::from_suffix("b").unwrap()),
+		)
+		.into_quad(None),
+	);
+
+	let system = system();
+	let fact = Triple(
+		Term::blank(BlankIdBuf::from_suffix("b").unwrap()),
+		Term::iri(iri!("https://example.org/#parent").to_owned()),
+		Term::blank(BlankIdBuf::from_suffix("c").unwrap()),
+	);
+
+	let dependents = system.dependents(&dataset, Signed(Sign::Positive, fact.as_ref()));
+	assert_eq!(dependents.len(), 2);
+	assert!(dependents
+		.iter()
+		.any(|e| std::ptr::eq(e.rule, system.get(0).unwrap())));
+	assert!(dependents
+		.iter()
+		.any(|e| std::ptr::eq(e.rule, system.get(1).unwrap())));
+}
+
+#[test]
+fn dependents_with_same_conclusion() {
+	let dataset: IndexedBTreeDataset = grdf_triples![
+		_:"a" <"https://example.org/#knows"> _:"b" .
+		_:"b" <"https://example.org/#knows"> _:"c" .
+		_:"b" <"https://example.org/#knows"> _:"d" .
+	]
+	.into_iter()
+	.map(|t: Triple| t.into_quad(None))
+	.collect();
+
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y, ?z {
+			?x <"https://example.org/#knows"> ?y .
+			?y <"https://example.org/#knows"> ?z .
+		} => {
+			?x <"https://example.org/#sociable"> ?x .
+		}
+	});
+
+	let fact = Triple(
+		Term::blank(BlankIdBuf::from_suffix("a").unwrap()),
+		Term::iri(iri!("https://example.org/#knows").to_owned()),
+		Term::blank(BlankIdBuf::from_suffix("b").unwrap()),
+	);
+
+	// Both derivations conclude `_:a sociable _:a`, and both depend on the
+	// fact.
+	let dependents = system.dependents(&dataset, Signed(Sign::Positive, fact.as_ref()));
+	assert_eq!(dependents.len(), 2);
+}
+
+#[test]
+fn what_if_fresh_blank_nodes() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x {
+			?x <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Person"> .
+		} => for ?m {
+			?x <"https://example.org/#mother"> ?m .
+		}
+	});
+
+	let mut dataset: IndexedBTreeDataset = grdf_triples![
+		_:"a" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Person"> .
+	]
+	.into_iter()
+	.map(|t: Triple| t.into_quad(None))
+	.collect();
+	system.close(&mut dataset).unwrap();
+
+	let added: Vec<Triple> = grdf_triples![
+		_:"b" <"http://www.w3.org/1999/02/22-rdf-syntax-ns#type"> <"https://example.org/#Person"> .
+	]
+	.into_iter()
+	.collect();
+
+	// The mother of `_:b` is not the one generated for `_:a`.
+	let impact = system.what_if(&dataset, added, 8).unwrap();
+	assert_eq!(impact.conclusions.len(), 1);
+	for Triple(_, _, m) in &impact.conclusions {
+		assert!(!dataset.iter().any(|Quad(_, _, o, _)| o == m))
+	}
+}
+
+#[test]
+fn what_if_negative_contradiction() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?y {
+			?x <"https://example.org/#residentOf"> ?y .
+		} => {
+			! ?x <"https://example.org/#votesIn"> ?y .
+		}
+	});
+
+	// `_:c` already contradicts the rule.
+	let dataset: IndexedBTreeDataset = grdf_triples![
+		_:"c" <"https://example.org/#residentOf"> _:"d" .
+		_:"c" <"https://example.org/#votesIn"> _:"d" .
+		_:"a" <"https://example.org/#votesIn"> _:"b" .
+	]
+	.into_iter()
+	.map(|t: Triple| t.into_quad(None))
+	.collect();
+
+	let added: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#residentOf"> _:"b" .
+	]
+	.into_iter()
+	.collect();
+
+	let impact = system.what_if(&dataset, added, 8).unwrap();
+	assert_eq!(impact.negative_conclusions.len(), 1);
+	assert_eq!(impact.contradictions.len(), 1);
+	assert_eq!(
+		impact.contradictions[0].reason,
+		Reason::MissingTriple(Signed(
+			Sign::Negative,
+			impact.negative_conclusions.first().unwrap().clone()
+		))
+	);
+}
+
+#[test]
+fn what_if_existing_violation() {
+	let mut system = System::new();
+	system.insert(rule! {
+		for ?x, ?age {
+			?x <"https://example.org/#age"> ?age .
+		} => {
+			(>= ?age 18) .
+		}
+	});
+
+	let dataset: IndexedBTreeDataset = grdf_triples![
+		_:"b" <"https://example.org/#age"> "10"^^"http://www.w3.org/2001/XMLSchema#int" .
+	]
+	.into_iter()
+	.map(|t: Triple| t.into_quad(None))
+	.collect();
+
+	let added: Vec<Triple> = grdf_triples![
+		_:"a" <"https://example.org/#age"> "12"^^"http://www.w3.org/2001/XMLSchema#int" .
+	]
+	.into_iter()
+	.collect();
+
+	// The rule is already violated by `_:b`, but not by `_:a`.
+	let impact = system.what_if(&dataset, added, 8).unwrap();
+	assert_eq!(impact.contradictions.len(), 1);
+	assert_eq!(
+		impact.contradictions[0].substitution[0],
+		Some(Term::blank(BlankIdBuf::from_suffix("a").unwrap()))
+	);
+}